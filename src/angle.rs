@@ -14,7 +14,7 @@ use serde::{Deserialize, Serialize};
 #[cfg(feature = "vulkano")]
 use vulkano::pipeline::vertex::{VertexMember, VertexMemberTy};
 
-use crate::fp::ApproxEq;
+use crate::fp::{self, ApproxEq};
 
 /// An angle struct independent of representation as either degrees or radians.
 #[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
@@ -132,6 +132,20 @@ impl Angle {
     pub fn sin_cos(self) -> (f32, f32) {
         self.radians.sin_cos()
     }
+
+    /// Compare two angles for equality, within an absolute tolerance in radians.
+    ///
+    /// See [`fp::approx_eq_eps`](../fp/fn.approx_eq_eps.html).
+    pub fn approx_eq_eps(&self, other: &Angle, eps: f32) -> bool {
+        fp::approx_eq_eps(self.radians, other.radians, eps)
+    }
+
+    /// Compare two angles for equality, within a relative tolerance.
+    ///
+    /// See [`fp::approx_eq_rel`](../fp/fn.approx_eq_rel.html).
+    pub fn approx_eq_rel(&self, other: &Angle, max_rel: f32) -> bool {
+        fp::approx_eq_rel(self.radians, other.radians, max_rel)
+    }
 }
 
 #[cfg(feature = "vulkano")]
@@ -196,6 +210,17 @@ impl SubAssign for Angle {
     }
 }
 
+impl Neg for Angle {
+    type Output = Angle;
+
+    #[inline(always)]
+    fn neg(self) -> Angle {
+        Angle {
+            radians: -self.radians,
+        }
+    }
+}
+
 impl Mul<f32> for Angle {
     type Output = Angle;
 
@@ -247,6 +272,35 @@ mod test {
     use crate::ApproxEq;
     use std::f32::consts::PI;
 
+    #[test]
+    fn test_approx_eq_eps_rel() {
+        let a = Angle::from_radians(1.0);
+        let b = Angle::from_radians(1.5);
+        assert!(a.approx_eq_eps(&b, 0.5));
+        assert!(!a.approx_eq_eps(&b, 0.49));
+        assert!(a.approx_eq_rel(&b, 1.0 / 3.0));
+        assert!(!a.approx_eq_rel(&b, 0.33));
+
+        let nan = Angle::from_radians(f32::NAN);
+        assert!(!nan.approx_eq_eps(&nan, f32::INFINITY));
+        assert!(!nan.approx_eq_rel(&nan, f32::INFINITY));
+
+        let inf = Angle::from_radians(f32::INFINITY);
+        assert!(inf.approx_eq_rel(&inf, 0.0));
+        assert!(!inf.approx_eq_rel(&Angle::from_radians(f32::NEG_INFINITY), 1e-6));
+        assert!(!inf.approx_eq_rel(&a, 1e-6));
+
+        let big = Angle::from_radians(1000.0);
+        let big2 = Angle::from_radians(1000.5);
+        assert!(!big.approx_eq_eps(&big2, 0.1));
+        assert!(big.approx_eq_rel(&big2, 1e-3));
+
+        let small = Angle::from_radians(1e-6);
+        let small2 = Angle::from_radians(2e-6);
+        assert!(small.approx_eq_eps(&small2, 1e-3));
+        assert!(!small.approx_eq_rel(&small2, 0.1));
+    }
+
     #[test]
     fn test_defaults() {
         let z = Angle::zero();
@@ -0,0 +1,207 @@
+//! Wrappers for comparing and hashing vectors and matrices by exact value.
+//!
+//! The `f32`-based types in this crate cannot implement `Eq`, `Ord`, or `Hash`, since
+//! floating-point equality is not reflexive (NaN is not equal to itself) and is not consistent
+//! with the bit representation (`-0.0 == 0.0`). The newtypes in this module fill that gap, e.g. to
+//! use vertex positions as `HashMap` keys when welding a mesh:
+//!
+//! ```
+//! # #[macro_use] extern crate gramit;
+//! # use gramit::*;
+//! use gramit::exact::ExactVec3;
+//! use std::collections::HashMap;
+//!
+//! # fn main() {
+//! let positions = [vec3!(0.0, 1.0, 0.0), vec3!(1.0, 0.0, 0.0), vec3!(-0.0, 1.0, 0.0)];
+//!
+//! let mut indices = HashMap::new();
+//! for p in positions.iter() {
+//!     let next = indices.len();
+//!     indices.entry(ExactVec3::from(*p)).or_insert(next);
+//! }
+//!
+//! assert_eq!(indices.len(), 2);
+//! # }
+//! ```
+//!
+//! # Comparison policy
+//!
+//! Elements are compared by their bit patterns after normalization:
+//!
+//! - `-0.0` is normalized to `0.0`, so that the two zeros are equal, as they are for `f32`.
+//! - Every NaN, regardless of sign or payload, is normalized to a single canonical NaN. NaN is
+//!   therefore equal to itself (and to every other NaN), unlike for `f32`.
+//!
+//! Hashing uses the same normalized bits, so it is consistent with equality. The ordering is
+//! lexicographic by element (in column-major order for matrices), using the IEEE 754 total order
+//! on the normalized elements; the canonical NaN sorts after positive infinity.
+
+use super::*;
+
+use std::cmp::Ordering;
+use std::hash::{Hash, Hasher};
+
+/// Normalize an element for exact comparison, according to the module's comparison policy.
+fn normalize(x: f32) -> f32 {
+    if x == 0.0 {
+        0.0
+    } else if x.is_nan() {
+        f32::NAN.copysign(1.0)
+    } else {
+        x
+    }
+}
+
+macro_rules! decl_exact {
+    ($name:ident, $inner:ident, $desc:expr) => {
+        #[doc = "A"]
+        #[doc = $desc]
+        #[doc = "that implements `Eq`, `Ord`, and `Hash` by exact value."]
+        #[doc = ""]
+        #[doc = "See the [module documentation](index.html) for the comparison policy."]
+        #[derive(Debug, Clone, Copy)]
+        pub struct $name(pub $inner);
+
+        impl $name {
+            fn elements(&self) -> impl Iterator<Item = f32> + '_ {
+                self.0.as_slice().iter().map(|&x| normalize(x))
+            }
+        }
+
+        impl From<$inner> for $name {
+            fn from(v: $inner) -> $name {
+                $name(v)
+            }
+        }
+
+        impl From<$name> for $inner {
+            fn from(v: $name) -> $inner {
+                v.0
+            }
+        }
+
+        impl PartialEq for $name {
+            fn eq(&self, other: &$name) -> bool {
+                self.cmp(other) == Ordering::Equal
+            }
+        }
+
+        impl Eq for $name {}
+
+        impl PartialOrd for $name {
+            fn partial_cmp(&self, other: &$name) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl Ord for $name {
+            fn cmp(&self, other: &$name) -> Ordering {
+                self.elements()
+                    .zip(other.elements())
+                    .map(|(a, b)| a.total_cmp(&b))
+                    .find(|&o| o != Ordering::Equal)
+                    .unwrap_or(Ordering::Equal)
+            }
+        }
+
+        impl Hash for $name {
+            fn hash<H: Hasher>(&self, state: &mut H) {
+                for x in self.elements() {
+                    x.to_bits().hash(state);
+                }
+            }
+        }
+    };
+}
+
+decl_exact!(ExactVec3, Vec3, "`Vec3`");
+decl_exact!(ExactVec4, Vec4, "`Vec4`");
+decl_exact!(ExactMat4, Mat4, "`Mat4`");
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::hash_map::DefaultHasher;
+    use std::collections::{BTreeSet, HashSet};
+
+    fn hash_of<T: Hash>(t: &T) -> u64 {
+        let mut h = DefaultHasher::new();
+        t.hash(&mut h);
+        h.finish()
+    }
+
+    #[test]
+    fn equal_values_hash_equally() {
+        let a = ExactVec3::from(vec3!(1.0, 2.5, -3.0));
+        let b = ExactVec3::from(vec3!(1.0, 2.5, -3.0));
+        let c = ExactVec3::from(vec3!(1.0, 2.5, 3.0));
+        assert_eq!(a, b);
+        assert_eq!(hash_of(&a), hash_of(&b));
+        assert_ne!(a, c);
+
+        let set: HashSet<ExactVec3> = [a, b, c].iter().cloned().collect();
+        assert_eq!(set.len(), 2);
+
+        let m = ExactMat4::from(Mat4::identity());
+        assert_eq!(m, ExactMat4(Mat4::IDENTITY));
+        assert_eq!(hash_of(&m), hash_of(&ExactMat4(Mat4::IDENTITY)));
+        assert_eq!(Mat4::from(m), Mat4::identity());
+        assert_eq!(Vec4::from(ExactVec4(Vec4::W)), Vec4::W);
+    }
+
+    #[test]
+    fn signed_zero() {
+        let pos = ExactVec4::from(vec4!(0.0, 1.0, 0.0, 0.0));
+        let neg = ExactVec4::from(vec4!(-0.0, 1.0, 0.0, -0.0));
+        assert_eq!(pos, neg);
+        assert_eq!(hash_of(&pos), hash_of(&neg));
+        assert_eq!(pos.cmp(&neg), Ordering::Equal);
+    }
+
+    #[test]
+    fn nan() {
+        let payload = f32::from_bits(0x7fc0_1234);
+        let negative = f32::from_bits(0xffc0_0000);
+        assert!(payload.is_nan() && negative.is_nan());
+
+        let a = ExactVec3::from(vec3!(f32::NAN, 0.0, 0.0));
+        let b = ExactVec3::from(vec3!(payload, 0.0, 0.0));
+        let c = ExactVec3::from(vec3!(negative, 0.0, 0.0));
+        assert_eq!(a, a);
+        assert_eq!(a, b);
+        assert_eq!(a, c);
+        assert_eq!(hash_of(&a), hash_of(&b));
+        assert_eq!(hash_of(&a), hash_of(&c));
+
+        let inf = ExactVec3::from(vec3!(f32::INFINITY, 0.0, 0.0));
+        assert!(a > inf);
+    }
+
+    #[test]
+    fn ordering() {
+        let vals = [
+            vec3!(1.0, 0.0, 0.0),
+            vec3!(-1.0, 5.0, 0.0),
+            vec3!(0.0, 0.0, 1.0),
+            vec3!(0.0, -2.0, 7.0),
+            vec3!(1.0, 0.0, -0.0),
+        ];
+        let sorted: Vec<Vec3> = vals
+            .iter()
+            .map(|&v| ExactVec3::from(v))
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .map(Vec3::from)
+            .collect();
+
+        assert_eq!(
+            sorted,
+            vec![
+                vec3!(-1.0, 5.0, 0.0),
+                vec3!(0.0, -2.0, 7.0),
+                vec3!(0.0, 0.0, 1.0),
+                vec3!(1.0, 0.0, 0.0),
+            ]
+        );
+    }
+}
@@ -1,5 +1,7 @@
 //! Helpers for performing floating-point arithmetic.
 
+use std::fmt::Formatter;
+
 /// The default threshold for approximate equality for `f32`s.
 ///
 /// This value is based on (but larger than) the host system's machine epsilon for 32-bit floating
@@ -18,7 +20,7 @@
 ///
 /// [`ApproxEq`]: trait.ApproxEq.html
 /// [`within_threshold`]: trait.ApproxEq.html#tymethod.within_threshold
-pub const EQ_THRESHOLD_F32: f32 = 64.0 * std::f32::EPSILON;
+pub const EQ_THRESHOLD_F32: f32 = 64.0 * f32::EPSILON;
 
 /// Trait for types that can be compared for _approximate_ equality.
 ///
@@ -56,3 +58,66 @@ impl ApproxEq for f32 {
         f32::abs(self - rhs) <= threshold
     }
 }
+
+/// Compare two `f32`s for equality within an absolute tolerance.
+///
+/// Returns `true` if the values are exactly equal, or if their absolute difference is at most
+/// `eps`. NaN is never equal to anything, including itself.
+#[inline(always)]
+pub fn approx_eq_eps(a: f32, b: f32, eps: f32) -> bool {
+    a == b || (a - b).abs() <= eps
+}
+
+/// Compare two `f32`s for equality within a relative tolerance.
+///
+/// Returns `true` if the values are exactly equal, or if their absolute difference is at most
+/// `max_rel` times the larger of their magnitudes. NaN is never equal to anything, including
+/// itself, and an infinity is only equal to itself.
+#[inline(always)]
+pub fn approx_eq_rel(a: f32, b: f32, max_rel: f32) -> bool {
+    if a == b {
+        return true;
+    }
+    if !a.is_finite() || !b.is_finite() {
+        return false;
+    }
+
+    (a - b).abs() <= max_rel * a.abs().max(b.abs())
+}
+
+/// Format an `f32` with the precision requested by the given formatter, if any.
+///
+/// Used by the `Display` implementations of vectors and matrices, which format each element
+/// separately and handle width and alignment themselves.
+pub(crate) fn format_element(f: &Formatter, x: f32) -> String {
+    match f.precision() {
+        Some(prec) => format!("{:.*}", prec, x),
+        None => format!("{}", x),
+    }
+}
+
+/// Format an `f32` with `Debug`, with the precision requested by the given formatter, if any.
+pub(crate) fn format_element_debug(f: &Formatter, x: f32) -> String {
+    match f.precision() {
+        Some(prec) => format!("{:.*?}", prec, x),
+        None => format!("{:?}", x),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_approx_eq_rel() {
+        assert!(approx_eq_rel(1.0, 1.5, 1.0 / 3.0));
+        assert!(!approx_eq_rel(1.0, 1.5, 0.33));
+        assert!(approx_eq_rel(1000.0, 1000.5, 1e-3));
+        assert!(!approx_eq_rel(f32::NAN, f32::NAN, f32::INFINITY));
+
+        assert!(approx_eq_rel(f32::INFINITY, f32::INFINITY, 0.0));
+        assert!(!approx_eq_rel(f32::INFINITY, f32::NEG_INFINITY, 1e-6));
+        assert!(!approx_eq_rel(f32::INFINITY, 1.0, 1e-6));
+        assert!(!approx_eq_rel(f32::MAX, f32::NEG_INFINITY, f32::INFINITY));
+    }
+}
@@ -28,7 +28,7 @@ impl Fraction {
     ///
     /// Returns `None` if the given value is less than 0 or greater than 1.
     pub fn new(val: f32) -> Option<Fraction> {
-        if val < 0.0 || val > 1.0 {
+        if !(0.0..=1.0).contains(&val) {
             None
         } else {
             Some(Fraction(val))
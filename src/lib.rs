@@ -119,16 +119,18 @@ macro_rules! vec4 {
 }
 
 pub mod angle;
+pub mod exact;
 pub mod fp;
 pub mod mat;
 pub mod quaternion;
 pub mod transform;
+pub mod transform2;
 pub mod vec;
 pub mod lerp;
 
 pub use angle::Angle;
 pub use fp::ApproxEq;
-pub use mat::{Mat2, Mat3, Mat4, SquareMatrix};
+pub use mat::{Lu4, Mat2, Mat3, Mat4, RowMut, SquareMatrix};
 pub use quaternion::Quaternion;
 pub use vec::{Vec2, Vec3, Vec4, Vector};
 
@@ -139,6 +141,6 @@ mod test_util;
 #[macro_export]
 macro_rules! offset_of {
     ($type:ty, $member:ident) => {
-        unsafe { &(*(::std::ptr::null::<$type>())).$member as *const _ as usize }
-    }
+        ::std::mem::offset_of!($type, $member)
+    };
 }
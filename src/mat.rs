@@ -1,7 +1,30 @@
 //! Two, three, and four-dimensional real square matrices with `f32` components.
+//!
+//! Matrices are stored in column-major order, and indexed as `m[col][row]`. Each matrix type is
+//! `#[repr(C)]` over its column vectors, which are themselves contiguous, so a matrix is laid out
+//! exactly like an array of `DIMS * DIMS` `f32`s. This layout is a stable guarantee, and is
+//! exposed without copying through `as_slice` and `as_ptr`.
+//!
+//! The array conversions (`to_cols_array`, `from_cols_array` and friends) use the same order,
+//! which is what OpenGL-style APIs expect by default:
+//!
+//! ```rust
+//! # use gramit::*;
+//! use gramit::transform;
+//!
+//! # fn uniform_matrix_4fv(_location: i32, _count: i32, _transpose: bool, value: &[f32; 16]) {
+//! #     assert_eq!(value[12], 1.0);
+//! # }
+//! let mvp = transform::translate(Vec3::x());
+//!
+//! // The translation is in the last column, i.e. elements 12 through 14.
+//! uniform_matrix_4fv(0, 1, false, &mvp.to_cols_array());
+//! ```
 
 use super::*;
+use std::cmp::Ordering;
 use std::convert::*;
+use std::fmt::{self, Debug, Display, Formatter};
 use std::ops::*;
 
 #[cfg(feature = "serde")]
@@ -10,7 +33,23 @@ use serde::{Deserialize, Serialize};
 #[cfg(feature = "vulkano")]
 use vulkano::pipeline::vertex::{VertexMember, VertexMemberTy};
 
-pub trait SquareMatrix: Copy {
+mod private {
+    pub trait Sealed {}
+}
+
+/// Operations common to all of the square matrix types in this module.
+///
+/// This trait is sealed, and is implemented only for [`Mat2`], [`Mat3`], and [`Mat4`]. It provides
+/// no element access of its own, so methods such as [`trace`] and [`cofactor_matrix`] cannot have
+/// default implementations; sealing it allows such methods to be added without breaking
+/// implementations elsewhere.
+///
+/// [`Mat2`]: struct.Mat2.html
+/// [`Mat3`]: struct.Mat3.html
+/// [`Mat4`]: struct.Mat4.html
+/// [`trace`]: #tymethod.trace
+/// [`cofactor_matrix`]: #tymethod.cofactor_matrix
+pub trait SquareMatrix: Copy + private::Sealed {
     /// The type used to represent the columns and rows of the matrix type.
     type VecType;
 
@@ -29,12 +68,33 @@ pub trait SquareMatrix: Copy {
     /// Get the transpose of this matrix.
     fn transpose(&self) -> Self;
 
+    /// Transpose this matrix in place.
+    ///
+    /// This swaps the off-diagonal elements directly, without constructing a temporary matrix.
+    fn transpose_mut(&mut self);
+
+    /// Get the trace of this matrix, i.e. the sum of its diagonal elements.
+    fn trace(&self) -> f32;
+
     /// Get the determinant of this matrix.
     fn determinant(&self) -> f32;
 
     /// Get the inverse of this matrix.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the matrix is singular. See [`try_inverse`] for a non-panicking
+    /// version.
+    ///
+    /// [`try_inverse`]: #tymethod.try_inverse
     fn inverse(&self) -> Self;
 
+    /// Get the inverse of this matrix, or `None` if it is singular.
+    ///
+    /// The matrix is considered singular if its determinant is exactly zero, or if the inverse
+    /// would contain non-finite elements.
+    fn try_inverse(&self) -> Option<Self>;
+
     /// Get the `row`-`col` minor of this matrix.
     ///
     /// This is the determinant of the matrix produced by omitting the `row`'th row and the
@@ -55,6 +115,17 @@ pub trait SquareMatrix: Copy {
     /// This function panics if either `col` or `row` is out of bounds of the matrix.
     fn cofactor(&self, col: usize, row: usize) -> f32;
 
+    /// Get the cofactor matrix of this matrix.
+    ///
+    /// Element `[col][row]` of the result is `self.cofactor(col, row)`.
+    fn cofactor_matrix(&self) -> Self;
+
+    /// Get the adjugate of this matrix, i.e. the transpose of its cofactor matrix.
+    ///
+    /// This satisfies `m * m.adjugate() == m.determinant() * I`, even for singular matrices, and
+    /// so equals the inverse scaled by the determinant.
+    fn adjugate(&self) -> Self;
+
     /// Get the `i`'th column of this matrix.
     ///
     /// # Panics
@@ -87,43 +158,391 @@ pub trait SquareMatrix: Copy {
 macro_rules! decl_mat {
     ($name:ident, $coltype:ident, $($cols:ident),+ | $($dims:ident),+) => {
         #[repr(C)]
-        #[derive(Debug, PartialEq, Clone, Copy)]
+        #[derive(PartialEq, Clone, Copy)]
         #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
         pub struct $name {
             $($cols: $coltype),+
         }
 
         impl $name {
+            // The total number of elements of this matrix type.
+            const ELEMS: usize = count_args!($($cols),+) * count_args!($($cols),+);
+
             #[doc = "Construct a new matrix with the given vectors as columns."]
             #[inline(always)]
-            pub fn new($($cols: $coltype),+) -> $name {
+            pub const fn new($($cols: $coltype),+) -> $name {
                 $name {
                     $($cols),+
                 }
             }
+
+            #[doc = "The identity matrix."]
+            pub const IDENTITY: $name = $name::new($($coltype::$dims()),+);
+
+            #[doc = "The matrix with all elements zero."]
+            pub const ZERO: $name = $name::filled(0.0);
+
+            #[doc = "Construct a new matrix with all elements set to `x`."]
+            #[inline(always)]
+            pub const fn filled(x: f32) -> $name {
+                $name::new($(replace_tt!($cols, $coltype::filled(x))),+)
+            }
+
+            #[doc = "Construct a diagonal matrix with the given vector's elements on the main"]
+            #[doc = "diagonal, and zeros elsewhere."]
+            pub fn from_diagonal(d: $coltype) -> $name {
+                let mut m = $name::zeros();
+                for i in 0..$name::DIMS {
+                    m[i][i] = d[i];
+                }
+                m
+            }
+
+            #[doc = "Construct a matrix by calling `f(col, row)` for each element."]
+            #[doc = ""]
+            #[doc = "The first argument is the column index and the second is the row index,"]
+            #[doc = "matching the `m[col][row]` indexing of matrices in this crate. Elements are"]
+            #[doc = "produced column by column."]
+            pub fn from_fn(mut f: impl FnMut(usize, usize) -> f32) -> $name {
+                let mut m = $name::zeros();
+                for col in 0..$name::DIMS {
+                    for row in 0..$name::DIMS {
+                        m[col][row] = f(col, row);
+                    }
+                }
+                m
+            }
+
+            #[doc = "Apply `f` to every element of this matrix, column by column."]
+            pub fn map(&self, mut f: impl FnMut(f32) -> f32) -> $name {
+                $name::from_fn(|col, row| f(self[col][row]))
+            }
+
+            #[doc = "Combine this matrix with another element by element, applying `f` to each"]
+            #[doc = "pair of corresponding elements, column by column."]
+            pub fn zip_with(&self, other: &$name, mut f: impl FnMut(f32, f32) -> f32) -> $name {
+                $name::from_fn(|col, row| f(self[col][row], other[col][row]))
+            }
+
+            #[doc = "View the elements of this matrix as a flat slice, in column-major order."]
+            #[inline(always)]
+            pub fn as_slice(&self) -> &[f32] {
+                let len = $name::DIMS * $name::DIMS;
+                unsafe { std::slice::from_raw_parts(self.as_ptr(), len) }
+            }
+
+            #[doc = "View the elements of this matrix as a flat mutable slice, in column-major"]
+            #[doc = "order."]
+            #[inline(always)]
+            pub fn as_mut_slice(&mut self) -> &mut [f32] {
+                let len = $name::DIMS * $name::DIMS;
+                unsafe { std::slice::from_raw_parts_mut(self as *mut $name as *mut f32, len) }
+            }
+
+            #[doc = "Get a pointer to the first element of this matrix."]
+            #[doc = ""]
+            #[doc = "The remaining elements follow contiguously, in column-major order."]
+            #[inline(always)]
+            pub fn as_ptr(&self) -> *const f32 {
+                self as *const $name as *const f32
+            }
+
+            #[doc = "Get a reference to the element at the given column and row, or `None` if"]
+            #[doc = "either index is out of bounds."]
+            #[doc = ""]
+            #[doc = "The arguments are in the same order as `m[col][row]` indexing."]
+            pub fn get(&self, col: usize, row: usize) -> Option<&f32> {
+                if row < $name::DIMS {
+                    self.as_ref().get(col).map(|c| &c[row])
+                } else {
+                    None
+                }
+            }
+
+            #[doc = "Get a mutable reference to the element at the given column and row, or"]
+            #[doc = "`None` if either index is out of bounds."]
+            #[doc = ""]
+            #[doc = "The arguments are in the same order as `m[col][row]` indexing."]
+            pub fn get_mut(&mut self, col: usize, row: usize) -> Option<&mut f32> {
+                if row < $name::DIMS {
+                    self.as_mut().get_mut(col).map(|c| &mut c[row])
+                } else {
+                    None
+                }
+            }
+
+            #[doc = "Get the `i`'th column of this matrix, or `None` if `i` is out of bounds."]
+            #[doc = ""]
+            #[doc = "This is the non-panicking counterpart of `get_col`."]
+            pub fn col(&self, i: usize) -> Option<$coltype> {
+                self.as_ref().get(i).cloned()
+            }
+
+            #[doc = "Get a mutable reference to the `i`'th column of this matrix."]
+            #[doc = ""]
+            #[doc = "# Panics"]
+            #[doc = ""]
+            #[doc = "This function panics if `i` is out of bounds."]
+            pub fn col_mut(&mut self, i: usize) -> &mut $coltype {
+                assert!(
+                    i < $name::DIMS,
+                    "[{}::col_mut] Column index {} out of bounds for {}x{} matrix",
+                    stringify!($name), i, $name::DIMS, $name::DIMS
+                );
+                &mut self[i]
+            }
+
+            #[doc = "Get a mutable proxy for the `i`'th row of this matrix."]
+            #[doc = ""]
+            #[doc = "Since the matrix is stored column-major, its rows are not contiguous, and"]
+            #[doc = "cannot be borrowed as vectors directly. See [`RowMut`](struct.RowMut.html)."]
+            #[doc = ""]
+            #[doc = "# Panics"]
+            #[doc = ""]
+            #[doc = "This function panics if `i` is out of bounds."]
+            pub fn row_mut(&mut self, i: usize) -> RowMut<'_, $name> {
+                assert!(
+                    i < $name::DIMS,
+                    "[{}::row_mut] Row index {} out of bounds for {}x{} matrix",
+                    stringify!($name), i, $name::DIMS, $name::DIMS
+                );
+                RowMut { mat: self, row: i }
+            }
+
+            #[doc = "Get the `i`'th row of this matrix, or `None` if `i` is out of bounds."]
+            #[doc = ""]
+            #[doc = "This is the non-panicking counterpart of `get_row`."]
+            pub fn row(&self, i: usize) -> Option<$coltype> {
+                if i < $name::DIMS {
+                    Some(SquareMatrix::get_row(self, i))
+                } else {
+                    None
+                }
+            }
+
+            #[doc = "Swap the `a`'th and `b`'th columns of this matrix."]
+            #[doc = ""]
+            #[doc = "This is a no-op if `a == b`."]
+            #[doc = ""]
+            #[doc = "# Panics"]
+            #[doc = ""]
+            #[doc = "This function panics if either index is out of bounds."]
+            pub fn swap_cols(&mut self, a: usize, b: usize) {
+                assert!(
+                    a < $name::DIMS && b < $name::DIMS,
+                    "[{}::swap_cols] Column index out of bounds",
+                    stringify!($name)
+                );
+                self.as_mut().swap(a, b);
+            }
+
+            #[doc = "Swap the `a`'th and `b`'th rows of this matrix."]
+            #[doc = ""]
+            #[doc = "This is a no-op if `a == b`."]
+            #[doc = ""]
+            #[doc = "# Panics"]
+            #[doc = ""]
+            #[doc = "This function panics if either index is out of bounds."]
+            pub fn swap_rows(&mut self, a: usize, b: usize) {
+                assert!(
+                    a < $name::DIMS && b < $name::DIMS,
+                    "[{}::swap_rows] Row index out of bounds",
+                    stringify!($name)
+                );
+                for col in self.as_mut() {
+                    col.as_mut().swap(a, b);
+                }
+            }
+
+            #[doc = "Iterate over the columns of this matrix, in order."]
+            pub fn cols(&self) -> impl Iterator<Item = $coltype> + '_ {
+                self.as_ref().iter().cloned()
+            }
+
+            #[doc = "Iterate over the rows of this matrix, in order."]
+            pub fn rows(&self) -> impl Iterator<Item = $coltype> + '_ {
+                (0..$name::DIMS).map(move |i| SquareMatrix::get_row(self, i))
+            }
+
+            #[doc = "Iterate over the elements of this matrix, in column-major order."]
+            #[doc = ""]
+            #[doc = "That is, `m[0][0]`, `m[0][1]`, ..., `m[1][0]`, and so on."]
+            pub fn iter(&self) -> impl Iterator<Item = f32> + '_ {
+                self.as_slice().iter().cloned()
+            }
+
+            #[doc = "Iterate mutably over the elements of this matrix, in column-major order."]
+            pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut f32> {
+                self.as_mut_slice().iter_mut()
+            }
+
+            #[doc = "Get the elements of this matrix as a flat array, in column-major order."]
+            #[doc = ""]
+            #[doc = "Element `[col][row]` of the matrix is at index `col * DIMS + row`."]
+            pub fn to_cols_array(
+                &self,
+            ) -> [f32; count_args!($($cols),+) * count_args!($($cols),+)] {
+                let mut a = [0.0; count_args!($($cols),+) * count_args!($($cols),+)];
+                for col in 0..$name::DIMS {
+                    for row in 0..$name::DIMS {
+                        a[col * $name::DIMS + row] = self[col][row];
+                    }
+                }
+                a
+            }
+
+            #[doc = "Get the elements of this matrix as an array of column arrays."]
+            #[doc = ""]
+            #[doc = "Element `[col][row]` of the matrix is at `[col][row]` of the result."]
+            pub fn to_cols_array_2d(
+                &self,
+            ) -> [[f32; count_args!($($cols),+)]; count_args!($($cols),+)] {
+                let mut a = [[0.0; count_args!($($cols),+)]; count_args!($($cols),+)];
+                for col in 0..$name::DIMS {
+                    for row in 0..$name::DIMS {
+                        a[col][row] = self[col][row];
+                    }
+                }
+                a
+            }
+
+            #[doc = "Construct a matrix from a flat array of elements in column-major order."]
+            #[doc = ""]
+            #[doc = "Index `col * DIMS + row` of the array becomes element `[col][row]`."]
+            pub fn from_cols_array(
+                a: &[f32; count_args!($($cols),+) * count_args!($($cols),+)],
+            ) -> $name {
+                $name::from_fn(|col, row| a[col * $name::DIMS + row])
+            }
+
+            #[doc = "Construct a matrix from an array of column arrays."]
+            #[doc = ""]
+            #[doc = "Element `[col][row]` of the array becomes element `[col][row]`."]
+            pub fn from_cols_array_2d(
+                a: &[[f32; count_args!($($cols),+)]; count_args!($($cols),+)],
+            ) -> $name {
+                $name::from_fn(|col, row| a[col][row])
+            }
+
+            #[doc = "Check whether this matrix is the identity matrix, within the given"]
+            #[doc = "tolerance."]
+            #[doc = ""]
+            #[doc = "Every element must differ from the corresponding element of the identity by"]
+            #[doc = "at most `eps`."]
+            pub fn is_identity(&self, eps: f32) -> bool {
+                let id = $name::identity();
+                (0..$name::DIMS)
+                    .all(|c| (0..$name::DIMS).all(|r| (self[c][r] - id[c][r]).abs() <= eps))
+            }
+
+            #[doc = "Compare two matrices for equality, within an absolute tolerance."]
+            #[doc = ""]
+            #[doc = "Every pair of elements must be equal within `eps`, i.e. the maximum absolute"]
+            #[doc = "difference must be at most `eps`. See"]
+            #[doc = "[`fp::approx_eq_eps`](../fp/fn.approx_eq_eps.html)."]
+            pub fn approx_eq_eps(&self, other: &$name, eps: f32) -> bool {
+                self.iter().zip(other.iter()).all(|(a, b)| fp::approx_eq_eps(a, b, eps))
+            }
+
+            #[doc = "Compare two matrices for equality, within a relative tolerance."]
+            #[doc = ""]
+            #[doc = "Every pair of elements must be equal within `max_rel`; see"]
+            #[doc = "[`fp::approx_eq_rel`](../fp/fn.approx_eq_rel.html)."]
+            pub fn approx_eq_rel(&self, other: &$name, max_rel: f32) -> bool {
+                self.iter().zip(other.iter()).all(|(a, b)| fp::approx_eq_rel(a, b, max_rel))
+            }
+
+            #[doc = "Check whether this matrix is symmetric, within the given tolerance."]
+            #[doc = ""]
+            #[doc = "Every element `m[c][r]` must differ from `m[r][c]` by at most `eps`."]
+            pub fn is_symmetric(&self, eps: f32) -> bool {
+                (0..$name::DIMS)
+                    .all(|c| (0..c).all(|r| (self[c][r] - self[r][c]).abs() <= eps))
+            }
+
+            #[doc = "Solve the linear system `self * x = b` for `x`."]
+            #[doc = ""]
+            #[doc = "This uses Gaussian elimination with partial pivoting, which is both faster"]
+            #[doc = "and more accurate than multiplying `b` by the inverse."]
+            #[doc = ""]
+            #[doc = "Returns `None` if `self` or `b` contain non-finite elements, if elimination"]
+            #[doc = "encounters an exactly zero or non-finite pivot (i.e. the matrix is singular,"]
+            #[doc = "or elimination overflowed), or if the solution contains non-finite elements."]
+            #[doc = "Nearly singular matrices do produce a solution, but it may be very"]
+            #[doc = "inaccurate; the residual `self * x - b` will nevertheless be small."]
+            pub fn solve(&self, b: $coltype) -> Option<$coltype> {
+                let finite = self.as_slice().iter().chain(b.as_ref().iter()).all(|e| e.is_finite());
+                if !finite {
+                    return None;
+                }
+
+                let mut a = *self;
+                let mut x = b;
+
+                for k in 0..$name::DIMS {
+                    let p = (k..$name::DIMS)
+                        .max_by(|&i, &j| a[k][i].abs().total_cmp(&a[k][j].abs()))
+                        .unwrap();
+                    if a[k][p] == 0.0 || !a[k][p].is_finite() {
+                        return None;
+                    }
+
+                    a.swap_rows(k, p);
+                    let t = x[k];
+                    x[k] = x[p];
+                    x[p] = t;
+
+                    for r in (k + 1)..$name::DIMS {
+                        let f = a[k][r] / a[k][k];
+                        for c in k..$name::DIMS {
+                            a[c][r] -= f * a[c][k];
+                        }
+                        x[r] -= f * x[k];
+                    }
+                }
+
+                for k in (0..$name::DIMS).rev() {
+                    let mut sum = x[k];
+                    for c in (k + 1)..$name::DIMS {
+                        sum -= a[c][k] * x[c];
+                    }
+                    x[k] = sum / a[k][k];
+                }
+
+                if x.as_ref().iter().all(|e| e.is_finite()) {
+                    Some(x)
+                } else {
+                    None
+                }
+            }
+
+            #[doc = "Get the main diagonal of this matrix as a vector."]
+            pub fn diagonal(&self) -> $coltype {
+                let mut d = $coltype::zeros();
+                for i in 0..$name::DIMS {
+                    d[i] = self[i][i];
+                }
+                d
+            }
         }
 
+        impl private::Sealed for $name {}
+
         impl SquareMatrix for $name {
             type VecType = $coltype;
 
             const DIMS: usize = count_args!($($cols),+);
 
             fn ones() -> Self {
-                $name {
-                    $($cols: $coltype::ones()),+
-                }
+                $name::filled(1.0)
             }
 
             fn zeros() -> Self {
-                $name {
-                    $($cols: $coltype::zeros()),+
-                }
+                $name::ZERO
             }
 
             fn identity() -> Self {
-                $name {
-                    $($cols: $coltype::$dims()),+
-                }
+                $name::IDENTITY
             }
 
             fn transpose(&self) -> Self {
@@ -134,14 +553,44 @@ macro_rules! decl_mat {
                 m
             }
 
+            fn transpose_mut(&mut self) {
+                for col in 0..$name::DIMS {
+                    for row in (col + 1)..$name::DIMS {
+                        let tmp = self[col][row];
+                        self[col][row] = self[row][col];
+                        self[row][col] = tmp;
+                    }
+                }
+            }
+
+            fn trace(&self) -> f32 {
+                (0..$name::DIMS).map(|i| self[i][i]).sum()
+            }
+
             #[inline(always)]
             fn determinant(&self) -> f32 {
                 self.matrix_determinant()
             }
 
-            #[inline(always)]
             fn inverse(&self) -> Self {
-                self.matrix_inverse()
+                match self.try_inverse() {
+                    Some(inv) => inv,
+                    None => panic!("[{}::inverse] Matrix is singular", stringify!($name)),
+                }
+            }
+
+            fn try_inverse(&self) -> Option<Self> {
+                let det = self.determinant();
+                if det == 0.0 || !det.is_finite() {
+                    return None;
+                }
+
+                let inv = self.adjugate() / det;
+                if inv.as_ref().iter().all(|col| col.as_ref().iter().all(|e| e.is_finite())) {
+                    Some(inv)
+                } else {
+                    None
+                }
             }
 
             #[inline(always)]
@@ -154,6 +603,22 @@ macro_rules! decl_mat {
                 neg * self.minor(col, row)
             }
 
+            fn cofactor_matrix(&self) -> Self {
+                let mut m = $name::zeros();
+                for col in 0..$name::DIMS {
+                    for row in 0..$name::DIMS {
+                        m[col][row] = self.cofactor(col, row);
+                    }
+                }
+                m
+            }
+
+            fn adjugate(&self) -> Self {
+                let mut m = self.cofactor_matrix();
+                m.transpose_mut();
+                m
+            }
+
             fn get_col(&self, i: usize) -> $coltype {
                 assert!(i < $name::DIMS, "Column index out of bounds");
                 self[i].clone()
@@ -184,6 +649,54 @@ macro_rules! decl_mat {
             }
         }
 
+        impl From<[f32; count_args!($($cols),+) * count_args!($($cols),+)]> for $name {
+            #[doc = "Construct a matrix from a flat array of elements in column-major order."]
+            fn from(a: [f32; count_args!($($cols),+) * count_args!($($cols),+)]) -> $name {
+                $name::from_cols_array(&a)
+            }
+        }
+
+        impl From<$name> for [f32; count_args!($($cols),+) * count_args!($($cols),+)] {
+            #[doc = "Get the elements of the matrix as a flat array, in column-major order."]
+            fn from(m: $name) -> Self {
+                m.to_cols_array()
+            }
+        }
+
+        impl From<[[f32; count_args!($($cols),+)]; count_args!($($cols),+)]> for $name {
+            #[doc = "Construct a matrix from an array of column arrays."]
+            fn from(a: [[f32; count_args!($($cols),+)]; count_args!($($cols),+)]) -> $name {
+                $name::from_cols_array_2d(&a)
+            }
+        }
+
+        impl From<$name> for [[f32; count_args!($($cols),+)]; count_args!($($cols),+)] {
+            #[doc = "Get the elements of the matrix as an array of column arrays."]
+            fn from(m: $name) -> Self {
+                m.to_cols_array_2d()
+            }
+        }
+
+        impl IntoIterator for $name {
+            type Item = f32;
+            type IntoIter = std::array::IntoIter<f32, { $name::ELEMS }>;
+
+            #[doc = "Iterate over the elements of the matrix, in column-major order."]
+            fn into_iter(self) -> Self::IntoIter {
+                IntoIterator::into_iter(self.to_cols_array())
+            }
+        }
+
+        impl<'a> IntoIterator for &'a $name {
+            type Item = &'a f32;
+            type IntoIter = std::slice::Iter<'a, f32>;
+
+            #[doc = "Iterate over the elements of the matrix, in column-major order."]
+            fn into_iter(self) -> Self::IntoIter {
+                self.as_slice().iter()
+            }
+        }
+
         impl Default for $name {
             fn default() -> $name {
                 $name::identity()
@@ -224,6 +737,171 @@ macro_rules! decl_mat {
             }
         }
 
+        impl Index<(usize, usize)> for $name {
+            type Output = f32;
+
+            #[doc = "Index a single element of this matrix by `(row, col)`."]
+            #[doc = ""]
+            #[doc = "Note that this is the mathematical convention, and the opposite order to"]
+            #[doc = "double indexing: `m[(row, col)]` is the same element as `m[col][row]`."]
+            #[doc = ""]
+            #[doc = "# Panics"]
+            #[doc = ""]
+            #[doc = "This function panics if either index is out of bounds."]
+            fn index(&self, (row, col): (usize, usize)) -> &f32 {
+                $name::check_element_index(row, col);
+                &self[col][row]
+            }
+        }
+
+        impl IndexMut<(usize, usize)> for $name {
+            #[doc = "Mutably index a single element of this matrix by `(row, col)`."]
+            #[doc = ""]
+            #[doc = "`m[(row, col)]` is the same element as `m[col][row]`."]
+            #[doc = ""]
+            #[doc = "# Panics"]
+            #[doc = ""]
+            #[doc = "This function panics if either index is out of bounds."]
+            fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut f32 {
+                $name::check_element_index(row, col);
+                &mut self[col][row]
+            }
+        }
+
+        impl $name {
+            fn check_element_index(row: usize, col: usize) {
+                let n = $name::DIMS;
+                assert!(
+                    row < n && col < n,
+                    "[{}::index] (row, col) index ({}, {}) out of bounds for {}x{} matrix",
+                    stringify!($name),
+                    row,
+                    col,
+                    n,
+                    n
+                );
+            }
+        }
+
+        impl std::iter::Sum for $name {
+            #[doc = "Sum an iterator of matrices element-wise."]
+            #[doc = ""]
+            #[doc = "The sum of an empty iterator is the zero matrix."]
+            fn sum<I: Iterator<Item = $name>>(iter: I) -> $name {
+                iter.fold($name::zeros(), |acc, m| acc + m)
+            }
+        }
+
+        impl<'a> std::iter::Sum<&'a $name> for $name {
+            #[doc = "Sum an iterator of matrix references element-wise."]
+            #[doc = ""]
+            #[doc = "The sum of an empty iterator is the zero matrix."]
+            fn sum<I: Iterator<Item = &'a $name>>(iter: I) -> $name {
+                iter.fold($name::zeros(), |acc, m| acc + m)
+            }
+        }
+
+        impl std::iter::Product for $name {
+            #[doc = "Multiply an iterator of matrices together, in iteration order."]
+            #[doc = ""]
+            #[doc = "Each matrix is multiplied on the right of the running product, as with"]
+            #[doc = "`*=`, so `[a, b, c].iter().product()` is `a * b * c`. The product of an"]
+            #[doc = "empty iterator is the identity matrix."]
+            fn product<I: Iterator<Item = $name>>(iter: I) -> $name {
+                iter.fold($name::IDENTITY, |acc, m| acc * m)
+            }
+        }
+
+        impl<'a> std::iter::Product<&'a $name> for $name {
+            #[doc = "Multiply an iterator of matrix references together, in iteration order."]
+            #[doc = ""]
+            #[doc = "Each matrix is multiplied on the right of the running product, as with"]
+            #[doc = "`*=`. The product of an empty iterator is the identity matrix."]
+            fn product<I: Iterator<Item = &'a $name>>(iter: I) -> $name {
+                iter.fold($name::IDENTITY, |acc, m| acc * m)
+            }
+        }
+
+        impl Debug for $name {
+            #[doc = "Prints this matrix, making its orientation explicit."]
+            #[doc = ""]
+            #[doc = "The compact form lists the columns, e.g. `Mat2 { cols: [[1.0, 2.0], [3.0,"]
+            #[doc = "4.0]] }`. The alternate form (`{:#?}`) instead lays the matrix out visually,"]
+            #[doc = "one row per line with right-aligned columns, under a `// rows` annotation."]
+            fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+                let elems: Vec<Vec<String>> = self
+                    .cols()
+                    .map(|col| {
+                        col.as_ref().iter().map(|e| fp::format_element_debug(f, *e)).collect()
+                    })
+                    .collect();
+
+                if !f.alternate() {
+                    write!(f, "{} {{ cols: [", stringify!($name))?;
+                    for (c, col) in elems.iter().enumerate() {
+                        if c > 0 {
+                            write!(f, ", ")?;
+                        }
+                        write!(f, "[{}]", col.join(", "))?;
+                    }
+                    return write!(f, "] }}");
+                }
+
+                let widths: Vec<usize> = elems
+                    .iter()
+                    .map(|col| col.iter().map(String::len).max().unwrap_or(0))
+                    .collect();
+
+                writeln!(f, "{} {{", stringify!($name))?;
+                writeln!(f, "    // rows")?;
+                for r in 0..$name::DIMS {
+                    write!(f, "    [")?;
+                    for c in 0..$name::DIMS {
+                        if c > 0 {
+                            write!(f, ", ")?;
+                        }
+                        write!(f, "{:>width$}", elems[c][r], width = widths[c])?;
+                    }
+                    writeln!(f, "],")?;
+                }
+                write!(f, "}}")
+            }
+        }
+
+        impl Display for $name {
+            #[doc = "Prints this matrix as a grid, one row per line, in the usual visual order"]
+            #[doc = "(i.e. columns of the matrix are printed as columns of text)."]
+            #[doc = ""]
+            #[doc = "Each column is right-aligned. The precision of the formatter, if any,"]
+            #[doc = "applies to each element, and the width, if any, is the minimum width of each"]
+            #[doc = "column."]
+            fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+                let elems: Vec<Vec<String>> = self
+                    .cols()
+                    .map(|col| col.as_ref().iter().map(|e| fp::format_element(f, *e)).collect())
+                    .collect();
+                let widths: Vec<usize> = elems
+                    .iter()
+                    .map(|col| col.iter().map(String::len).fold(f.width().unwrap_or(0), usize::max))
+                    .collect();
+
+                for r in 0..$name::DIMS {
+                    if r > 0 {
+                        writeln!(f)?;
+                    }
+                    write!(f, "[")?;
+                    for c in 0..$name::DIMS {
+                        if c > 0 {
+                            write!(f, ", ")?;
+                        }
+                        write!(f, "{:>width$}", elems[c][r], width = widths[c])?;
+                    }
+                    write!(f, "]")?;
+                }
+                Ok(())
+            }
+        }
+
         impl ApproxEq for $name {
             fn approx_eq(self, rhs: $name) -> bool {
                 $(self.$cols.approx_eq(rhs.$cols))&+
@@ -245,6 +923,8 @@ macro_rules! decl_mat {
         decl_mat!(@VECMUL $name, $coltype, $($cols),+);
 
         decl_mat!(@MATMUL $name, $($cols),+);
+        decl_mat!(@MATMULASGN $name, $name);
+        decl_mat!(@MATMULASGN $name, &$name);
 
         decl_mat!(@SCALAR_MUL $name, $name, $($cols),+);
         decl_mat!(@SCALAR_MUL $name, &$name, $($cols),+);
@@ -321,6 +1001,17 @@ macro_rules! decl_mat {
         }
     };
 
+    (@MATMULASGN $name:ident, $rhs:ty) => {
+        impl MulAssign<$rhs> for $name {
+            #[doc = "Matrix-matrix multiplication assignment operation."]
+            #[doc = ""]
+            #[doc = "`a *= b` is equivalent to `a = a * b`, i.e. `b` is multiplied on the right."]
+            fn mul_assign(&mut self, rhs: $rhs) {
+                *self = &*self * rhs;
+            }
+        }
+    };
+
     (@MATASGN $name:ident, $trait:ident, $func:ident, $op:tt, $($cols:ident),+) => {
         decl_mat!(@MATASGN_SINGLE $name, $trait, $func, $op, $name, $($cols),+);
         decl_mat!(@MATASGN_SINGLE $name, $trait, $func, $op, &$name, $($cols),+);
@@ -402,15 +1093,23 @@ decl_mat!(Mat3, Vec3, col1, col2, col3 | x, y, z);
 decl_mat!(Mat4, Vec4, col1, col2, col3, col4 | x, y, z, w);
 
 impl Mat2 {
-    fn matrix_determinant(&self) -> f32 {
-        self[0][0] * self[1][1] - self[1][0] * self[0][1]
+    /// Construct a new matrix with the given vectors as rows.
+    ///
+    /// This is equivalent to `Mat2::new(r0, r1).transpose()`.
+    pub fn from_rows(r0: Vec2, r1: Vec2) -> Mat2 {
+        let mut m = Mat2::new(r0, r1);
+        m.transpose_mut();
+        m
+    }
+
+    /// Construct the matrix of a counterclockwise rotation by the given angle.
+    pub fn from_angle(angle: Angle) -> Mat2 {
+        let (s, c) = angle.sin_cos();
+        Mat2::new(Vec2::new(c, s), Vec2::new(-s, c))
     }
 
-    fn matrix_inverse(&self) -> Mat2 {
-        Mat2::new(
-            Vec2::new(self[1][1], -self[0][1]),
-            Vec2::new(-self[1][0], self[0][0]),
-        ) / self.determinant()
+    fn matrix_determinant(&self) -> f32 {
+        self[0][0] * self[1][1] - self[1][0] * self[0][1]
     }
 
     fn matrix_minor(&self, col: usize, row: usize) -> f32 {
@@ -421,50 +1120,535 @@ impl Mat2 {
     }
 }
 
+/// Tolerance used by `rotation_angle` when checking that a matrix is a rotation.
+const ROTATION_CHECK_THRESHOLD: f32 = 1e-4;
+
+/// Maximum number of Newton iterations performed by `polar_decompose`.
+const POLAR_MAX_ITERATIONS: usize = 32;
+
+/// Element-wise change below which the `polar_decompose` iteration is considered converged.
+const POLAR_CONVERGENCE_THRESHOLD: f32 = 1e-6;
+
+/// Maximum number of Jacobi sweeps performed by `symmetric_eigen`.
+const EIGEN_MAX_SWEEPS: usize = 32;
+
 impl Mat3 {
-    fn matrix_determinant(&self) -> f32 {
-        self[0][0] * (self[1][1] * self[2][2] - self[2][1] * self[1][2])
-            + self[1][0] * (self[2][1] * self[0][2] - self[0][1] * self[2][2])
-            + self[2][0] * (self[0][1] * self[1][2] - self[1][1] * self[0][2])
+    /// Construct a new matrix with the given vectors as rows.
+    ///
+    /// This is equivalent to `Mat3::new(r0, r1, r2).transpose()`.
+    pub fn from_rows(r0: Vec3, r1: Vec3, r2: Vec3) -> Mat3 {
+        let mut m = Mat3::new(r0, r1, r2);
+        m.transpose_mut();
+        m
     }
 
-    fn matrix_minor(&self, col: usize, row: usize) -> f32 {
-        assert!(col < 3, "[Mat3::minor] Column index out of bounds");
-        assert!(row < 3, "[Mat3::minor] Row index out of bounds");
+    /// Construct the matrix of a rotation about the given axis by the given angle.
+    ///
+    /// This is the upper-left 3x3 block of
+    /// [`transform::rotate`](../transform/fn.rotate.html)`(axis, angle)`. As there, the axis is
+    /// normalized internally, and a zero axis produces a matrix of NaNs.
+    pub fn from_rotation(axis: Vec3, angle: Angle) -> Mat3 {
+        let (c1, c2, c3) = transform::rotation_columns(axis, angle);
+        Mat3::new(c1, c2, c3)
+    }
 
-        let mut m = Mat2::zeros();
-        for i in 0..2 {
-            let c = if i >= col { i + 1 } else { i };
-            for j in 0..2 {
-                let r = if j >= row { j + 1 } else { j };
+    /// Extend this matrix to a homogeneous `Mat4`.
+    ///
+    /// The result has this matrix as its upper-left 3x3 block, and the last row and column of the
+    /// identity.
+    pub fn extend(&self) -> Mat4 {
+        Mat4::from_mat3_translation(*self, Vec3::zeros())
+    }
 
-                m[i][j] = self[c][r];
-            }
+    /// Check whether this matrix is orthogonal, within the given tolerance.
+    ///
+    /// This holds when every element of `MᵀM` is within `eps` of the identity.
+    pub fn is_orthogonal(&self, eps: f32) -> bool {
+        (self.transpose() * self).is_identity(eps)
+    }
+
+    /// Check whether this matrix is a proper rotation, within the given tolerance.
+    ///
+    /// This holds when the matrix is orthogonal and its determinant is within `eps` of 1. In
+    /// particular, reflections are orthogonal but not rotations.
+    pub fn is_rotation(&self, eps: f32) -> bool {
+        self.is_orthogonal(eps) && (self.determinant() - 1.0).abs() <= eps
+    }
+
+    /// Check whether this matrix preserves handedness.
+    ///
+    /// Returns `None` if the absolute value of the determinant is less than `eps` (i.e. the matrix
+    /// is degenerate, and has no meaningful handedness), and otherwise `Some(det > 0)`. Matrices
+    /// with a negative determinant mirror space, turning right-handed frames left-handed.
+    pub fn is_right_handed(&self, eps: f32) -> Option<bool> {
+        let det = self.determinant();
+        if det.abs() < eps {
+            None
+        } else {
+            Some(det > 0.0)
         }
+    }
 
-        m.determinant()
+    /// Whether this matrix reverses the winding order of triangles, i.e. whether its determinant
+    /// is negative.
+    ///
+    /// This is `false` for degenerate matrices. Mirrored geometry must have its triangle winding
+    /// and normal directions flipped.
+    pub fn winding_flipped(&self) -> bool {
+        self.determinant() < 0.0
     }
 
-    fn matrix_inverse(&self) -> Mat3 {
-        let c00 = self.cofactor(0, 0);
-        let c01 = self.cofactor(0, 1);
-        let c02 = self.cofactor(0, 2);
-        let c10 = self.cofactor(1, 0);
-        let c11 = self.cofactor(1, 1);
-        let c12 = self.cofactor(1, 2);
-        let c20 = self.cofactor(2, 0);
-        let c21 = self.cofactor(2, 1);
-        let c22 = self.cofactor(2, 2);
+    /// Get the rotation angle of this matrix, if it is a proper rotation.
+    ///
+    /// The result is in [0°, 180°]. `None` is returned if this matrix is not orthogonal with
+    /// determinant 1, within a small tolerance.
+    ///
+    /// The angle satisfies `trace = 1 + 2 cos(θ)`. Rather than taking the arccosine of the trace,
+    /// which is ill-conditioned near 0° and 180°, the sine is recovered from the skew-symmetric
+    /// part of the matrix and the angle computed with `atan2`.
+    pub fn rotation_angle(&self) -> Option<Angle> {
+        if !self.is_rotation(ROTATION_CHECK_THRESHOLD) {
+            return None;
+        }
 
-        Mat3::new(
-            Vec3::new(c00, c10, c20),
-            Vec3::new(c01, c11, c21),
-            Vec3::new(c02, c12, c22),
-        ) / self.determinant()
+        let skew = Vec3::new(
+            self[1][2] - self[2][1],
+            self[2][0] - self[0][2],
+            self[0][1] - self[1][0],
+        );
+        Some(Angle::atan2(skew.length() / 2.0, (self.trace() - 1.0) / 2.0))
     }
-}
+
+    /// Compute the QR decomposition of this matrix.
+    ///
+    /// Returns `(q, r)`, where `q` is orthogonal and `r` is upper triangular with a non-negative
+    /// diagonal, such that `q * r` reconstructs this matrix. The columns of `q` are computed via
+    /// the modified Gram–Schmidt process.
+    ///
+    /// If the columns of this matrix are linearly dependent, the result will contain NaNs.
+    pub fn qr(&self) -> (Mat3, Mat3) {
+        let r00 = self[0].length();
+        let q0 = self[0] / r00;
+
+        let r01 = q0.dot(&self[1]);
+        let v1 = self[1] - q0 * r01;
+        let r11 = v1.length();
+        let q1 = v1 / r11;
+
+        let r02 = q0.dot(&self[2]);
+        let v2 = self[2] - q0 * r02;
+        let r12 = q1.dot(&v2);
+        let v2 = v2 - q1 * r12;
+        let r22 = v2.length();
+        let q2 = v2 / r22;
+
+        (
+            Mat3::new(q0, q1, q2),
+            Mat3::new(
+                Vec3::new(r00, 0.0, 0.0),
+                Vec3::new(r01, r11, 0.0),
+                Vec3::new(r02, r12, r22),
+            ),
+        )
+    }
+
+    /// Orthonormalize the columns of this matrix.
+    ///
+    /// The columns are orthonormalized in order via the modified Gram–Schmidt process; this is
+    /// the `q` factor of [`qr`]. It is useful for correcting floating point drift in a matrix
+    /// that should be a rotation. The orientation is preserved, so a reflection stays a
+    /// reflection.
+    ///
+    /// If the columns of this matrix are linearly dependent, the result will contain NaNs.
+    ///
+    /// [`qr`]: #method.qr
+    pub fn orthonormalize(&self) -> Mat3 {
+        self.qr().0
+    }
+
+    /// Compute the polar decomposition of this matrix.
+    ///
+    /// Returns `(r, s)`, where `r` is the closest rotation to this matrix and `s` is symmetric,
+    /// such that `r * s` reconstructs this matrix. This extracts "the rotation part" of a matrix
+    /// that also contains scale and shear.
+    ///
+    /// `r` is always a proper rotation (its determinant is 1). If this matrix has a positive
+    /// determinant, `s` is positive definite. If it has a negative determinant (i.e. it contains a
+    /// reflection), `s` carries the reflection along its smallest principal axis: it has exactly
+    /// one negative eigenvalue, whose magnitude is the smallest singular value of this matrix.
+    /// This is the choice that makes `r` the closest rotation. If the smallest singular value is
+    /// repeated (e.g. for a pure reflection), the reflected axis is any one of its principal axes.
+    ///
+    /// The orthogonal factor is computed by a scaled Newton iteration, averaging the matrix with
+    /// its inverse transpose, which converges quadratically; the reflected axis, if any, is then
+    /// found with [`symmetric_eigen`]. Returns `None` if this matrix is singular.
+    ///
+    /// [`symmetric_eigen`]: #method.symmetric_eigen
+    pub fn polar_decompose(&self) -> Option<(Mat3, Mat3)> {
+        self.polar_iterate().map(|(r, s, _)| (r, s))
+    }
+
+    // Implementation of `polar_decompose`, also returning the number of iterations performed.
+    fn polar_iterate(&self) -> Option<(Mat3, Mat3, usize)> {
+        let det = self.determinant();
+        if det == 0.0 || !det.is_finite() {
+            return None;
+        }
+
+        let m = *self;
+        let mut r = m;
+        let mut iterations = 0;
+        while iterations < POLAR_MAX_ITERATIONS {
+            iterations += 1;
+
+            let inv_t = r.try_inverse()?.transpose();
+            let gamma = (inv_t.determinant() / r.determinant()).abs().powf(1.0 / 6.0);
+            let next = (gamma * r + inv_t / gamma) * 0.5;
+            let delta = (next - r).iter().fold(0.0f32, |acc, e| acc.max(e.abs()));
+            r = next;
+
+            if delta <= POLAR_CONVERGENCE_THRESHOLD {
+                break;
+            }
+        }
+
+        let s = r.transpose() * m;
+        let s = (s + s.transpose()) * 0.5;
+        if det > 0.0 {
+            return Some((r, s, iterations));
+        }
+
+        // `r` is orthogonal with determinant -1. Move the reflection into `s`, along the
+        // eigenvector of its smallest eigenvalue: with `h = I - 2vvᵀ`, `r * h` is a rotation and
+        // `h * s = s - 2λvvᵀ` is still symmetric.
+        let (vals, vecs) = s.symmetric_eigen();
+        let v = vecs[2];
+        let vvt = Mat3::new(v * v.x, v * v.y, v * v.z);
+        let h = Mat3::identity() - vvt * 2.0;
+        Some((r * h, s - vvt * (2.0 * vals[2]), iterations))
+    }
+
+    /// Compute the eigenvalues and eigenvectors of this matrix, treating it as symmetric.
+    ///
+    /// Returns the eigenvalues sorted in descending order, along with a matrix whose columns are
+    /// the corresponding orthonormal eigenvectors, so that `v * Mat3::from_diagonal(λ) *
+    /// v.transpose()` reconstructs this matrix. This is useful for principal axis analysis of
+    /// covariance matrices.
+    ///
+    /// The matrix is first symmetrized (i.e. replaced by `(m + mᵀ) / 2`), so non-symmetric input
+    /// is accepted but only its symmetric part is decomposed. The decomposition is computed by
+    /// cyclic Jacobi iteration. For repeated eigenvalues, the eigenvectors are an arbitrary
+    /// orthonormal basis of the corresponding eigenspace.
+    ///
+    /// If the matrix contains non-finite elements, the results are unspecified (and will
+    /// generally contain NaN), but this function does not panic.
+    pub fn symmetric_eigen(&self) -> ([f32; 3], Mat3) {
+        let mut a = (*self + self.transpose()) * 0.5;
+        let mut v = Mat3::identity();
+
+        for _ in 0..EIGEN_MAX_SWEEPS {
+            let off = a[1][0] * a[1][0] + a[2][0] * a[2][0] + a[2][1] * a[2][1];
+            if off == 0.0 || off <= f32::EPSILON * f32::EPSILON * a.diagonal().dot(&a.diagonal()) {
+                break;
+            }
+
+            for &(p, q) in [(0, 1), (0, 2), (1, 2)].iter() {
+                let apq = a[q][p];
+                if apq == 0.0 {
+                    continue;
+                }
+
+                let theta = (a[q][q] - a[p][p]) / (2.0 * apq);
+                let t = theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt());
+                let c = 1.0 / (t * t + 1.0).sqrt();
+                let s = t * c;
+
+                let mut j = Mat3::identity();
+                j[p][p] = c;
+                j[q][q] = c;
+                j[q][p] = s;
+                j[p][q] = -s;
+
+                a = j.transpose() * a * j;
+                a[q][p] = 0.0;
+                a[p][q] = 0.0;
+                v *= j;
+            }
+        }
+
+        let mut order = [0, 1, 2];
+        order.sort_by(|&i, &j| a[j][j].total_cmp(&a[i][i]));
+        (
+            [a[order[0]][order[0]], a[order[1]][order[1]], a[order[2]][order[2]]],
+            Mat3::new(v[order[0]], v[order[1]], v[order[2]]),
+        )
+    }
+
+    fn matrix_determinant(&self) -> f32 {
+        self[0][0] * (self[1][1] * self[2][2] - self[2][1] * self[1][2])
+            + self[1][0] * (self[2][1] * self[0][2] - self[0][1] * self[2][2])
+            + self[2][0] * (self[0][1] * self[1][2] - self[1][1] * self[0][2])
+    }
+
+    fn matrix_minor(&self, col: usize, row: usize) -> f32 {
+        assert!(col < 3, "[Mat3::minor] Column index out of bounds");
+        assert!(row < 3, "[Mat3::minor] Row index out of bounds");
+
+        let mut m = Mat2::zeros();
+        for i in 0..2 {
+            let c = if i >= col { i + 1 } else { i };
+            for j in 0..2 {
+                let r = if j >= row { j + 1 } else { j };
+
+                m[i][j] = self[c][r];
+            }
+        }
+
+        m.determinant()
+    }
+}
 
 impl Mat4 {
+    /// Construct a new matrix with the given vectors as rows.
+    ///
+    /// This is equivalent to `Mat4::new(r0, r1, r2, r3).transpose()`, and is convenient for
+    /// transcribing matrices as they are usually written in references.
+    ///
+    /// # Example
+    /// ```rust
+    /// # #[macro_use] extern crate gramit;
+    /// # use gramit::*;
+    /// use gramit::transform;
+    ///
+    /// # fn main() {
+    /// let (l, r, b, t, n, f) = (-2.0, 3.0, -1.0, 4.0, 0.5, 10.0);
+    /// let m = Mat4::from_rows(
+    ///     vec4!(2.0 / (r - l), 0.0, 0.0, -(r + l) / (r - l)),
+    ///     vec4!(0.0, 2.0 / (t - b), 0.0, -(t + b) / (t - b)),
+    ///     vec4!(0.0, 0.0, -2.0 / (f - n), -(f + n) / (f - n)),
+    ///     vec4!(0.0, 0.0, 0.0, 1.0),
+    /// );
+    ///
+    /// assert_approx_eq!(m, transform::ortho(l, r, b, t, n, f));
+    /// # }
+    /// ```
+    pub fn from_rows(r0: Vec4, r1: Vec4, r2: Vec4, r3: Vec4) -> Mat4 {
+        let mut m = Mat4::new(r0, r1, r2, r3);
+        m.transpose_mut();
+        m
+    }
+
+    /// Get the rotation angle of the upper-left 3x3 block of this matrix, if it is a proper
+    /// rotation.
+    ///
+    /// The remaining elements are ignored. See
+    /// [`Mat3::rotation_angle`](struct.Mat3.html#method.rotation_angle).
+    pub fn rotation_angle(&self) -> Option<Angle> {
+        self.truncate().rotation_angle()
+    }
+
+    /// Check whether the upper-left 3x3 block of this matrix is orthogonal, within the given
+    /// tolerance.
+    ///
+    /// See [`Mat3::is_orthogonal`](struct.Mat3.html#method.is_orthogonal).
+    pub fn is_orthogonal(&self, eps: f32) -> bool {
+        self.truncate().is_orthogonal(eps)
+    }
+
+    /// Check whether the upper-left 3x3 block of this matrix is a proper rotation, within the
+    /// given tolerance.
+    ///
+    /// See [`Mat3::is_rotation`](struct.Mat3.html#method.is_rotation).
+    pub fn is_rotation(&self, eps: f32) -> bool {
+        self.truncate().is_rotation(eps)
+    }
+
+    /// Check whether the upper-left 3x3 block of this matrix preserves handedness.
+    ///
+    /// See [`Mat3::is_right_handed`](struct.Mat3.html#method.is_right_handed).
+    pub fn is_right_handed(&self, eps: f32) -> Option<bool> {
+        self.truncate().is_right_handed(eps)
+    }
+
+    /// Whether the upper-left 3x3 block of this matrix reverses the winding order of triangles.
+    ///
+    /// See [`Mat3::winding_flipped`](struct.Mat3.html#method.winding_flipped).
+    pub fn winding_flipped(&self) -> bool {
+        self.truncate().winding_flipped()
+    }
+
+    /// Get the upper-left 3x3 block of this matrix.
+    ///
+    /// For a homogeneous affine transformation, this is its linear part.
+    pub fn truncate(&self) -> Mat3 {
+        Mat3::new(self[0].truncate(), self[1].truncate(), self[2].truncate())
+    }
+
+    /// Construct a homogeneous affine transformation matrix from its linear part and translation.
+    ///
+    /// The upper-left 3x3 block of the result is `linear`, the last column is `(translation, 1)`,
+    /// and the remainder of the last row is zero.
+    pub fn from_mat3_translation(linear: Mat3, translation: Vec3) -> Mat4 {
+        Mat4::new(
+            linear[0].extend(0.0),
+            linear[1].extend(0.0),
+            linear[2].extend(0.0),
+            translation.extend(1.0),
+        )
+    }
+
+    /// Get the translation part of this matrix, i.e. the first three elements of its last column.
+    pub fn translation(&self) -> Vec3 {
+        self[3].truncate()
+    }
+
+    /// Orthonormalize the upper-left 3x3 block of this matrix, leaving its last row and column
+    /// untouched.
+    ///
+    /// See [`Mat3::orthonormalize`].
+    ///
+    /// [`Mat3::orthonormalize`]: struct.Mat3.html#method.orthonormalize
+    pub fn orthonormalize(&self) -> Mat4 {
+        let q = self.truncate().orthonormalize();
+        Mat4::new(
+            q[0].extend(self[0].w),
+            q[1].extend(self[1].w),
+            q[2].extend(self[2].w),
+            self[3],
+        )
+    }
+
+    /// Transform a point by this matrix.
+    ///
+    /// The point is extended with _w_ = 1 and multiplied by this matrix. The perspective divide
+    /// is skipped if the resulting _w_ is approximately 1, i.e. within [`EQ_THRESHOLD_F32`] of it,
+    /// which is the case for affine matrices (including those whose last row differs from
+    /// `(0, 0, 0, 1)` only by rounding error). Skipping the divide then changes the result by at
+    /// most that relative amount.
+    ///
+    /// [`EQ_THRESHOLD_F32`]: ../fp/constant.EQ_THRESHOLD_F32.html
+    pub fn transform_point3(&self, p: Vec3) -> Vec3 {
+        Mat4::divide_unless_affine(self * p.extend(1.0))
+    }
+
+    /// Transform each of a slice of points in place by this matrix.
+    ///
+    /// Each point is transformed as by [`transform_point3`].
+    ///
+    /// [`transform_point3`]: #method.transform_point3
+    pub fn transform_points3(&self, points: &mut [Vec3]) {
+        let (c0, c1, c2, c3) = (self[0], self[1], self[2], self[3]);
+        for p in points.iter_mut() {
+            *p = Self::transform_point_cols(&c0, &c1, &c2, &c3, *p);
+        }
+    }
+
+    /// Transform each of a slice of points by this matrix, writing the results into another slice.
+    ///
+    /// Each point is transformed as by [`transform_point3`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `src` and `dst` have different lengths.
+    ///
+    /// [`transform_point3`]: #method.transform_point3
+    pub fn transform_points3_into(&self, src: &[Vec3], dst: &mut [Vec3]) {
+        if src.len() != dst.len() {
+            panic!("[Mat4::transform_points3_into] Source and destination lengths differ");
+        }
+
+        let (c0, c1, c2, c3) = (self[0], self[1], self[2], self[3]);
+        for (d, s) in dst.iter_mut().zip(src.iter()) {
+            *d = Self::transform_point_cols(&c0, &c1, &c2, &c3, *s);
+        }
+    }
+
+    // Kernel for the batch point transforms; the columns are loaded once by the caller.
+    #[inline(always)]
+    fn transform_point_cols(c0: &Vec4, c1: &Vec4, c2: &Vec4, c3: &Vec4, p: Vec3) -> Vec3 {
+        Mat4::divide_unless_affine(c0 * p.x + c1 * p.y + c2 * p.z + c3)
+    }
+
+    #[inline(always)]
+    fn divide_unless_affine(v: Vec4) -> Vec3 {
+        if v.w.approx_eq(1.0) {
+            v.truncate()
+        } else {
+            v.homogenize()
+        }
+    }
+
+    /// Transform a direction vector by this matrix.
+    ///
+    /// The vector is extended with _w_ = 0, so that translation is ignored, and no perspective
+    /// divide is performed.
+    pub fn transform_vector3(&self, v: Vec3) -> Vec3 {
+        (self * v.extend(0.0)).truncate()
+    }
+
+    /// Transform a point by this matrix, always performing the perspective divide.
+    ///
+    /// This is the same as `(m * p.homogeneous()).homogenize()`, and is intended for projection
+    /// matrices. Points that map to _w_ = 0 produce non-finite results.
+    pub fn project_point3(&self, p: Vec3) -> Vec3 {
+        (self * p.extend(1.0)).homogenize()
+    }
+
+    /// Check whether this matrix represents an affine transformation, within the given tolerance.
+    ///
+    /// This holds when the last row is within `eps` of `(0, 0, 0, 1)` element-wise.
+    pub fn is_affine(&self, eps: f32) -> bool {
+        self[0][3].abs() <= eps
+            && self[1][3].abs() <= eps
+            && self[2][3].abs() <= eps
+            && (self[3][3] - 1.0).abs() <= eps
+    }
+
+    /// Compute the LU decomposition of this matrix, with partial pivoting.
+    ///
+    /// The resulting factorization can be reused to solve many systems against the same matrix,
+    /// which is cheaper than calling [`solve`] repeatedly.
+    ///
+    /// Returns `None` if the matrix contains non-finite elements, or if it is singular, i.e. if
+    /// elimination encounters an exactly zero pivot. Pivots that become non-finite during
+    /// elimination also produce `None`.
+    ///
+    /// [`solve`]: #method.solve
+    pub fn lu(&self) -> Option<Lu4> {
+        if !self.as_slice().iter().all(|e| e.is_finite()) {
+            return None;
+        }
+
+        let mut lu = *self;
+        let mut perm = [0, 1, 2, 3];
+        let mut sign = 1.0;
+
+        for k in 0..4 {
+            let p = (k..4)
+                .max_by(|&i, &j| lu[k][i].abs().total_cmp(&lu[k][j].abs()))
+                .unwrap();
+            if lu[k][p] == 0.0 || !lu[k][p].is_finite() {
+                return None;
+            }
+
+            if p != k {
+                lu.swap_rows(k, p);
+                perm.swap(k, p);
+                sign = -sign;
+            }
+
+            for r in (k + 1)..4 {
+                let f = lu[k][r] / lu[k][k];
+                lu[k][r] = f;
+                for c in (k + 1)..4 {
+                    lu[c][r] -= f * lu[c][k];
+                }
+            }
+        }
+
+        Some(Lu4 { lu, perm, sign })
+    }
+
     fn matrix_minor(&self, col: usize, row: usize) -> f32 {
         assert!(col < 4, "[Mat4::minor] Column index out of bounds");
         assert!(row < 4, "[Mat4::minor] Row index out of bounds");
@@ -519,31 +1703,163 @@ impl Mat4 {
         let (m0, m1, m2, m3) = self.first_row_minors();
         self.det_minors(m0, m1, m2, m3)
     }
+}
+
+/// A mutable proxy for one row of a matrix.
+///
+/// Returned by the `row_mut` methods of the matrix types. Individual elements of the row can be
+/// read and written by column index, and the whole row can be read or replaced by value.
+///
+/// # Example
+/// ```rust
+/// # #[macro_use] extern crate gramit;
+/// # use gramit::*;
+/// # fn main() {
+/// let mut m = Mat3::identity();
+/// {
+///     let mut row = m.row_mut(0);
+///     row[2] = 5.0;
+///     assert_eq!(row.get(), vec3!(1.0, 0.0, 5.0));
+/// }
+/// assert_eq!(m[2][0], 5.0);
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct RowMut<'a, M> {
+    mat: &'a mut M,
+    row: usize,
+}
+
+impl<'a, M: SquareMatrix> RowMut<'a, M> {
+    /// Get the index of this row within its matrix.
+    pub fn row_index(&self) -> usize {
+        self.row
+    }
+
+    /// Get the elements of this row, by value.
+    pub fn get(&self) -> M::VecType {
+        self.mat.get_row(self.row)
+    }
+
+    /// Replace the elements of this row.
+    pub fn set(&mut self, r: M::VecType) {
+        self.mat.set_row(self.row, r);
+    }
 
-    fn matrix_inverse(&self) -> Mat4 {
-        let c00 = self.cofactor(0, 0);
-        let c01 = self.cofactor(0, 1);
-        let c02 = self.cofactor(0, 2);
-        let c03 = self.cofactor(0, 3);
-        let c10 = self.cofactor(1, 0);
-        let c11 = self.cofactor(1, 1);
-        let c12 = self.cofactor(1, 2);
-        let c13 = self.cofactor(1, 3);
-        let c20 = self.cofactor(2, 0);
-        let c21 = self.cofactor(2, 1);
-        let c22 = self.cofactor(2, 2);
-        let c23 = self.cofactor(2, 3);
-        let c30 = self.cofactor(3, 0);
-        let c31 = self.cofactor(3, 1);
-        let c32 = self.cofactor(3, 2);
-        let c33 = self.cofactor(3, 3);
+    fn check_col(col: usize, op: &str) {
+        assert!(
+            col < M::DIMS,
+            "[RowMut::{}] Column index {} out of bounds for {}x{} matrix",
+            op, col, M::DIMS, M::DIMS
+        );
+    }
+}
 
+impl<'a, M> Index<usize> for RowMut<'a, M>
+where
+    M: SquareMatrix + Index<usize>,
+    <M as Index<usize>>::Output: Index<usize, Output = f32>,
+{
+    type Output = f32;
+
+    /// Get the element of this row in the given column.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `col` is out of bounds.
+    fn index(&self, col: usize) -> &f32 {
+        RowMut::<M>::check_col(col, "index");
+        &self.mat[col][self.row]
+    }
+}
+
+impl<'a, M> IndexMut<usize> for RowMut<'a, M>
+where
+    M: SquareMatrix + IndexMut<usize>,
+    <M as Index<usize>>::Output: IndexMut<usize, Output = f32>,
+{
+    /// Get a mutable reference to the element of this row in the given column.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `col` is out of bounds.
+    fn index_mut(&mut self, col: usize) -> &mut f32 {
+        RowMut::<M>::check_col(col, "index_mut");
+        &mut self.mat[col][self.row]
+    }
+}
+
+/// The LU decomposition of a `Mat4`, with partial pivoting.
+///
+/// This holds a unit lower triangular matrix _L_, an upper triangular matrix _U_, and a row
+/// permutation _P_ such that _P_ · _A_ = _L_ · _U_. The factors are stored packed into a single
+/// matrix.
+///
+/// Construct one with [`Mat4::lu`].
+///
+/// [`Mat4::lu`]: struct.Mat4.html#method.lu
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Lu4 {
+    lu: Mat4,
+    perm: [usize; 4],
+    sign: f32,
+}
+
+impl Lu4 {
+    /// Get the unit lower triangular factor _L_.
+    pub fn l(&self) -> Mat4 {
+        Mat4::from_fn(|c, r| match c.cmp(&r) {
+            Ordering::Less => self.lu[c][r],
+            Ordering::Equal => 1.0,
+            Ordering::Greater => 0.0,
+        })
+    }
+
+    /// Get the upper triangular factor _U_.
+    pub fn u(&self) -> Mat4 {
+        Mat4::from_fn(|c, r| if c >= r { self.lu[c][r] } else { 0.0 })
+    }
+
+    /// Get the permutation matrix _P_.
+    pub fn p(&self) -> Mat4 {
+        Mat4::from_fn(|c, r| if self.perm[r] == c { 1.0 } else { 0.0 })
+    }
+
+    /// Solve the linear system _A_ · `x` = `b` for `x`, where _A_ is the decomposed matrix.
+    pub fn solve(&self, b: Vec4) -> Vec4 {
+        let mut x = Vec4::zeros();
+        for r in 0..4 {
+            let mut sum = b[self.perm[r]];
+            for c in 0..r {
+                sum -= self.lu[c][r] * x[c];
+            }
+            x[r] = sum;
+        }
+
+        for r in (0..4).rev() {
+            let mut sum = x[r];
+            for c in (r + 1)..4 {
+                sum -= self.lu[c][r] * x[c];
+            }
+            x[r] = sum / self.lu[r][r];
+        }
+
+        x
+    }
+
+    /// Get the determinant of the decomposed matrix.
+    pub fn determinant(&self) -> f32 {
+        self.sign * self.lu.diagonal().as_ref().iter().product::<f32>()
+    }
+
+    /// Get the inverse of the decomposed matrix.
+    pub fn inverse(&self) -> Mat4 {
         Mat4::new(
-            Vec4::new(c00, c10, c20, c30),
-            Vec4::new(c01, c11, c21, c31),
-            Vec4::new(c02, c12, c22, c32),
-            Vec4::new(c03, c13, c23, c33),
-        ) / self.determinant()
+            self.solve(Vec4::x()),
+            self.solve(Vec4::y()),
+            self.solve(Vec4::z()),
+            self.solve(Vec4::w()),
+        )
     }
 }
 
@@ -612,6 +1928,423 @@ macro_rules! test_mat {
             }
         }
 
+        #[test]
+        fn transpose_mut() {
+            let mut rng = crate::test_util::Lcg::new(0x7);
+            for _ in 0..50 {
+                let m: $name = rng.mat(-4.0, 4.0);
+
+                let mut t = m;
+                t.transpose_mut();
+                assert_eq!(t, m.transpose());
+
+                t.transpose_mut();
+                assert_eq!(t, m);
+
+                let sym = m + m.transpose();
+                let mut t = sym;
+                t.transpose_mut();
+                assert_eq!(t, sym);
+            }
+        }
+
+        #[test]
+        fn trace() {
+            let mut rng = crate::test_util::Lcg::new(0x74);
+            for _ in 0..50 {
+                let a: $name = rng.mat(-4.0, 4.0);
+                let b: $name = rng.mat(-4.0, 4.0);
+                let expected: f32 = (0..$name::DIMS).map(|i| a[i][i]).sum();
+                assert_eq!(a.trace(), expected);
+                assert_eq!(a.transpose().trace(), a.trace());
+                assert_within_threshold!((a + b).trace(), a.trace() + b.trace(), 1e-4);
+                assert_within_threshold!((a * b).trace(), (b * a).trace(), 1e-3);
+            }
+
+            assert_eq!($name::identity().trace(), $name::DIMS as f32);
+            assert_eq!($name::zeros().trace(), 0.0);
+        }
+
+        #[test]
+        fn adjugate() {
+            let mut rng = crate::test_util::Lcg::new(0xad);
+            for i in 0..100 {
+                let mut m: $name = rng.mat(-3.0, 3.0);
+                if i % 4 == 0 {
+                    m.set_col(1, m.get_col(0) * 2.0);
+                }
+
+                let det = m.determinant();
+                let adj = m.adjugate();
+                let expected = $name::identity() * det;
+                let thresh = $name::ones() * 1e-3;
+                assert_within_threshold!(m * adj, expected, thresh, "m: {:?}", m);
+                assert_within_threshold!(adj * m, expected, thresh, "m: {:?}", m);
+                assert_eq!(adj, m.cofactor_matrix().transpose());
+
+                if i % 4 == 0 {
+                    assert_within_threshold!(m * adj, $name::zeros(), thresh, "m: {:?}", m);
+                }
+            }
+        }
+
+        #[test]
+        fn from_rows() {
+            let mut rng = crate::test_util::Lcg::new(0x70);
+            for _ in 0..20 {
+                let m: $name = rng.mat(-4.0, 4.0);
+
+                // m * e_i is the i'th column of m, so these rows are m's columns.
+                let from_rows = $name::from_rows($(m * $vec::$dims()),+);
+                assert_eq!(from_rows, m.transpose());
+                for i in 0..$name::DIMS {
+                    assert_eq!(from_rows.get_row(i), m[i]);
+                }
+            }
+        }
+
+        #[test]
+        fn diagonal() {
+            let mut rng = crate::test_util::Lcg::new(0xd1);
+            for _ in 0..20 {
+                let d = rng.mat::<$name>(-4.0, 4.0)[0];
+                let m = $name::from_diagonal(d.clone());
+                assert_eq!(m.diagonal(), d);
+                assert_eq!(m.transpose(), m);
+                assert_approx_eq!(m.determinant(), d.as_ref().iter().product());
+
+                let mut off_diagonal = m;
+                for i in 0..$name::DIMS {
+                    off_diagonal[i][i] = 0.0;
+                }
+                assert_eq!(off_diagonal, $name::zeros());
+            }
+
+            assert_eq!($name::from_diagonal($vec::ones()), $name::identity());
+            assert_eq!($name::identity().diagonal(), $vec::ones());
+        }
+
+        #[test]
+        fn from_fn_map() {
+            let id = $name::from_fn(|c, r| if c == r { 1.0 } else { 0.0 });
+            assert_eq!(id, $name::identity());
+
+            let m = $name::from_fn(|c, r| (10 * c + r) as f32);
+            for c in 0..$name::DIMS {
+                for r in 0..$name::DIMS {
+                    assert_eq!(m[c][r], (10 * c + r) as f32);
+                }
+            }
+            assert_eq!(m.get_row(1)[0], 1.0);
+
+            let mut order = Vec::new();
+            $name::from_fn(|c, r| {
+                order.push((c, r));
+                0.0
+            });
+            assert_eq!(order[1], (0, 1));
+            assert_eq!(order.len(), $name::DIMS * $name::DIMS);
+
+            assert_eq!(m.map(|x| 2.0 * x), m * 2.0);
+            assert_eq!(m.map(|x| -x), -m);
+
+            let mut visited = 0;
+            m.map(|x| {
+                visited += 1;
+                x
+            });
+            assert_eq!(visited, $name::DIMS * $name::DIMS);
+        }
+
+        #[test]
+        fn slices() {
+            let n = $name::DIMS;
+            let mut m = $name::zeros();
+            for (i, e) in m.as_mut_slice().iter_mut().enumerate() {
+                *e = i as f32;
+            }
+
+            assert_eq!(m.as_slice().len(), n * n);
+            for c in 0..n {
+                for r in 0..n {
+                    assert_eq!(m[c][r], (c * n + r) as f32);
+                    assert_eq!(unsafe { *m.as_ptr().add(c * n + r) }, m[c][r]);
+                }
+            }
+            assert_eq!(m.as_slice(), &m.to_cols_array()[..]);
+            assert_eq!(std::mem::size_of::<$name>(), n * n * 4);
+            assert_eq!(std::mem::align_of::<$name>(), std::mem::align_of::<f32>());
+        }
+
+        #[test]
+        fn iterators() {
+            let n = $name::DIMS;
+            let m = $name::from_fn(|c, r| (10 * c + r) as f32);
+
+            let elems: Vec<f32> = m.iter().collect();
+            for c in 0..n {
+                for r in 0..n {
+                    assert_eq!(elems[c * n + r], m[c][r]);
+                }
+            }
+            assert_eq!(m.into_iter().collect::<Vec<_>>(), elems);
+            assert_eq!((&m).into_iter().cloned().collect::<Vec<_>>(), elems);
+            assert_eq!(m.into_iter().sum::<f32>(), elems.iter().sum::<f32>());
+
+            let cols: Vec<$vec> = m.cols().collect();
+            let rows: Vec<$vec> = m.rows().collect();
+            assert_eq!(cols.len(), n);
+            for i in 0..n {
+                assert_eq!(cols[i], m[i]);
+                assert_eq!(rows[i], m.get_row(i));
+            }
+            assert_eq!(m.transpose().rows().collect::<Vec<_>>(), cols);
+
+            let mut m2 = m;
+            for e in m2.iter_mut() {
+                *e += 1.0;
+            }
+            assert_eq!(m2, m + $name::ones());
+        }
+
+        #[test]
+        fn tuple_index() {
+            let n = $name::DIMS;
+            let mut m = $name::from_fn(|c, r| (10 * c + r) as f32);
+            for r in 0..n {
+                for c in 0..n {
+                    assert_eq!(m[(r, c)], m[c][r]);
+                    assert_eq!(m[(r, c)], m.get_row(r)[c]);
+                }
+            }
+
+            m[(0, n - 1)] = -1.0;
+            assert_eq!(m[n - 1][0], -1.0);
+            assert_eq!(m.get_row(0)[n - 1], -1.0);
+            assert_eq!(m[(n - 1, 0)], (n - 1) as f32);
+        }
+
+        #[test]
+        #[should_panic(expected = "out of bounds for")]
+        fn tuple_index_row_out_of_bounds() {
+            let m = $name::identity();
+            let _ = m[($name::DIMS, 0)];
+        }
+
+        #[test]
+        #[should_panic(expected = "out of bounds for")]
+        fn tuple_index_col_out_of_bounds() {
+            let mut m = $name::identity();
+            m[(0, $name::DIMS)] = 1.0;
+        }
+
+        #[test]
+        fn checked_access() {
+            let n = $name::DIMS;
+            let mut m = $name::from_fn(|c, r| (10 * c + r) as f32);
+            for c in 0..n {
+                assert_eq!(m.col(c), Some(m[c]));
+                assert_eq!(m.row(c), Some(m.get_row(c)));
+                for r in 0..n {
+                    assert_eq!(m.get(c, r), Some(&m[c][r]));
+                }
+            }
+
+            assert_eq!(m.get(n - 1, n - 1), Some(&((10 * (n - 1) + n - 1) as f32)));
+            assert_eq!(m.get(n, 0), None);
+            assert_eq!(m.get(0, n), None);
+            assert_eq!(m.get(usize::MAX, usize::MAX), None);
+            assert_eq!(m.col(n), None);
+            assert_eq!(m.row(n), None);
+            assert!(m.get_mut(n, 0).is_none());
+            assert!(m.get_mut(0, n).is_none());
+
+            *m.get_mut(n - 1, 0).unwrap() = -5.0;
+            assert_eq!(m[n - 1][0], -5.0);
+        }
+
+        #[test]
+        fn swap() {
+            let n = $name::DIMS;
+            let mut rng = crate::test_util::Lcg::new(0x5a);
+            for _ in 0..20 {
+                let m: $name = rng.mat(-4.0, 4.0);
+                for a in 0..n {
+                    for b in 0..n {
+                        let mut s = m;
+                        s.swap_cols(a, b);
+                        assert_eq!(s[a], m[b]);
+                        assert_eq!(s[b], m[a]);
+                        s.swap_cols(a, b);
+                        assert_eq!(s, m);
+
+                        s.swap_rows(a, b);
+                        assert_eq!(s.get_row(a), m.get_row(b));
+                        assert_eq!(s.get_row(b), m.get_row(a));
+                        if a != b {
+                            let det = m.determinant();
+                            let thresh = 1e-4 * (1.0 + det.abs());
+                            assert_within_threshold!(s.determinant(), -det, thresh);
+                        }
+                        s.swap_rows(a, b);
+                        assert_eq!(s, m);
+                    }
+                }
+
+                let mut s = m;
+                s.swap_rows(0, 0);
+                s.swap_cols(n - 1, n - 1);
+                assert_eq!(s, m);
+            }
+
+            // Swapping rows of the identity gives a permutation matrix.
+            let mut p = $name::identity();
+            p.swap_rows(0, n - 1);
+            let mut expected = $name::identity();
+            expected[0][0] = 0.0;
+            expected[n - 1][n - 1] = 0.0;
+            expected[0][n - 1] = 1.0;
+            expected[n - 1][0] = 1.0;
+            assert_eq!(p, expected);
+            assert_eq!(p.determinant(), -1.0);
+
+            let m = $name::from_fn(|c, r| (10 * c + r) as f32);
+            let mut swapped = m;
+            swapped.swap_rows(0, n - 1);
+            assert_eq!(p * m, swapped);
+        }
+
+        #[test]
+        #[should_panic(expected = "Row index out of bounds")]
+        fn swap_rows_out_of_bounds() {
+            $name::identity().swap_rows(0, $name::DIMS);
+        }
+
+        #[test]
+        #[should_panic(expected = "Column index out of bounds")]
+        fn swap_cols_out_of_bounds() {
+            $name::identity().swap_cols($name::DIMS, 0);
+        }
+
+        #[test]
+        fn scalar_ops() {
+            let mut rng = crate::test_util::Lcg::new(0x79);
+            for _ in 0..20 {
+                let a: $name = rng.mat(-4.0, 4.0);
+                let b: $name = rng.mat(-4.0, 4.0);
+                let v = rng.mat::<$name>(-4.0, 4.0)[0];
+
+                assert_eq!((2.0 * a) * v, (a * v) * 2.0);
+                assert_eq!(a * 0.5, 0.5 * a);
+                assert_within_threshold!(3.0 * (a + b), 3.0 * a + 3.0 * b, $name::ones() * 1e-5);
+                assert_eq!(a / 1.0, a);
+                assert_eq!(a / 2.0, a * 0.5);
+
+                let mut m = a;
+                m *= 2.0;
+                assert_eq!(m, a * 2.0);
+                m /= 4.0;
+                assert_eq!(m, a * 0.5);
+            }
+
+            let inf = $name::identity() / 0.0;
+            assert_eq!(inf[0][0], f32::INFINITY);
+            assert!(inf[0][1].is_nan());
+        }
+
+        #[test]
+        fn assign_ops() {
+            let mut rng = crate::test_util::Lcg::new(0x80);
+            for _ in 0..20 {
+                let a: $name = rng.mat(-4.0, 4.0);
+                let b: $name = rng.mat(-4.0, 4.0);
+
+                let mut m = a;
+                m += b;
+                assert_eq!(m, a + b);
+                m += &b;
+                assert_eq!(m, a + b + b);
+
+                let mut m = a;
+                m -= b;
+                assert_eq!(m, a - b);
+                m -= &b;
+                assert_eq!(m, a - b - b);
+
+                // `*=` multiplies on the right.
+                let mut m = a;
+                m *= b;
+                assert_eq!(m, a * b);
+                m *= &a;
+                assert_eq!(m, (a * b) * a);
+
+                let mut m = a;
+                m *= 3.0;
+                assert_eq!(m, a * 3.0);
+                m /= 3.0;
+                assert_eq!(m, (a * 3.0) / 3.0);
+            }
+
+            let mut p = $name::identity();
+            p.swap_rows(0, 1);
+            let d = $name::from_fn(|c, r| if c == r { (c + 1) as f32 } else { 0.0 });
+            let mut m = p;
+            m *= d;
+            assert_eq!(m, p * d);
+            assert_ne!(m, d * p);
+        }
+
+        #[test]
+        fn identity_symmetric_predicates() {
+            let n = $name::DIMS;
+            let id = $name::identity();
+            assert!(id.is_identity(0.0));
+            assert!(id.is_symmetric(0.0));
+
+            let mut near = id;
+            near[n - 1][0] = 1e-5;
+            assert!(near.is_identity(1e-4));
+            assert!(!near.is_identity(1e-6));
+            assert!(near.is_symmetric(1e-4));
+            assert!(!near.is_symmetric(1e-6));
+            assert!(!(id * 2.0).is_identity(0.5));
+
+            let m = $name::from_fn(|c, r| (10 * c + r) as f32);
+            assert!(!m.is_symmetric(1.0));
+            assert!((m + m.transpose()).is_symmetric(0.0));
+        }
+
+        #[test]
+        fn cols_arrays() {
+            let n = $name::DIMS;
+            let m = $name::from_fn(|c, r| (10 * c + r) as f32 + 0.1);
+            let flat = m.to_cols_array();
+            let nested = m.to_cols_array_2d();
+            assert_eq!(flat.len(), n * n);
+            for c in 0..n {
+                for r in 0..n {
+                    assert_eq!(flat[c * n + r], m[c][r]);
+                    assert_eq!(nested[c][r], m[c][r]);
+                }
+            }
+
+            let mut rng = crate::test_util::Lcg::new(0xa7);
+            for _ in 0..20 {
+                let m: $name = rng.mat(-1e3, 1e3);
+                let bits = |m: &$name| {
+                    m.to_cols_array().iter().map(|e| e.to_bits()).collect::<Vec<_>>()
+                };
+
+                assert_eq!(bits(&$name::from_cols_array(&m.to_cols_array())), bits(&m));
+                assert_eq!(bits(&$name::from_cols_array_2d(&m.to_cols_array_2d())), bits(&m));
+
+                let flat: [f32; count_args!($($dims),+) * count_args!($($dims),+)] = m.into();
+                let nested: [[f32; count_args!($($dims),+)]; count_args!($($dims),+)] = m.into();
+                assert_eq!(bits(&$name::from(flat)), bits(&m));
+                assert_eq!(bits(&$name::from(nested)), bits(&m));
+            }
+        }
+
         #[test]
         fn identity_determinant() {
             let i = $name::identity();
@@ -640,15 +2373,259 @@ macro_rules! test_mat {
         }
 
         #[test]
-        fn zero_determinant() {
-            let mut v = $vec::ones();
-            let mut m = $name::zeros();
-            for i in 0..count_args!($($dims),+) {
-                m.set_col(i, v.clone());
-                v += $vec::ones();
-            }
+        fn determinant_multiplicative() {
+            let mut rng = crate::test_util::Lcg::new(0xde7);
+            for _ in 0..200 {
+                let a: $name = rng.mat(-2.0, 2.0);
+                let b: $name = rng.mat(-2.0, 2.0);
+                let expected = a.determinant() * b.determinant();
+                let actual = (a * b).determinant();
+                let thresh = 1e-4 * (1.0 + expected.abs());
+                assert_within_threshold!(actual, expected, thresh, "a: {:?}\nb: {:?}", a, b);
+                assert_within_threshold!(a.transpose().determinant(), a.determinant(), 1e-4);
+            }
+        }
+
+        #[test]
+        fn repeated_column_determinant() {
+            let mut rng = crate::test_util::Lcg::new(0xc01);
+            for _ in 0..50 {
+                let mut m: $name = rng.mat(-2.0, 2.0);
+                m.set_col(1, m.get_col(0));
+                assert_within_threshold!(m.determinant(), 0.0, 1e-5, "m: {:?}", m);
+            }
+        }
+
+        #[test]
+        fn zero_determinant() {
+            let mut v = $vec::ones();
+            let mut m = $name::zeros();
+            for i in 0..count_args!($($dims),+) {
+                m.set_col(i, v.clone());
+                v += $vec::ones();
+            }
+
+            assert_approx_eq!(m.determinant(), 0.0);
+        }
+
+        #[test]
+        fn approx_eq_eps_rel() {
+            let a = $name::ones();
+            let b = a * 1.5;
+            assert!(a.approx_eq_eps(&b, 0.5));
+            assert!(!a.approx_eq_eps(&b, 0.49));
+            assert!(a.approx_eq_rel(&b, 1.0 / 3.0));
+            assert!(!a.approx_eq_rel(&b, 0.33));
+
+            let mut nan = $name::identity();
+            nan[0][$name::DIMS - 1] = f32::NAN;
+            assert!(!nan.approx_eq_eps(&nan, f32::INFINITY));
+            assert!(!nan.approx_eq_rel(&nan, f32::INFINITY));
+
+            let inf = a * f32::INFINITY;
+            assert!(inf.approx_eq_rel(&inf, 0.0));
+            assert!(!inf.approx_eq_rel(&-inf, 1e-6));
+            assert!(!inf.approx_eq_rel(&a, 1e-6));
+
+            let big = $name::identity() * 1000.0;
+            let big2 = $name::identity() * 1000.5;
+            assert!(!big.approx_eq_eps(&big2, 0.1));
+            assert!(big.approx_eq_rel(&big2, 1e-3));
+
+            let small = a * 1e-6;
+            let small2 = a * 2e-6;
+            assert!(small.approx_eq_eps(&small2, 1e-3));
+            assert!(!small.approx_eq_rel(&small2, 0.1));
+        }
+
+        #[test]
+        fn sum_and_zip_with() {
+            let mut rng = crate::test_util::Lcg::new(0x5e);
+            for _ in 0..50 {
+                let a: $name = rng.mat(-4.0, 4.0);
+                let b: $name = rng.mat(-4.0, 4.0);
+                assert_eq!(a.zip_with(&b, |x, y| x + y), a + b);
+                assert_eq!(a.zip_with(&b, f32::max), $name::from_fn(|c, r| a[c][r].max(b[c][r])));
+
+                let weights = [0.25; 4];
+                let blended: $name = weights.iter().map(|w| a * *w).sum();
+                assert_eq!(blended, a);
+
+                let all = [a, b, a];
+                assert_eq!(all.iter().sum::<$name>(), a + b + a);
+                assert_eq!(all.iter().cloned().sum::<$name>(), a + b + a);
+            }
+
+            assert_eq!(Vec::<$name>::new().into_iter().sum::<$name>(), $name::zeros());
+            assert_eq!([].iter().sum::<$name>(), $name::zeros());
+        }
+
+        #[test]
+        fn product() {
+            let mut rng = crate::test_util::Lcg::new(0x9d);
+            for _ in 0..50 {
+                let a: $name = rng.mat(-2.0, 2.0);
+                let b: $name = rng.mat(-2.0, 2.0);
+                let c: $name = rng.mat(-2.0, 2.0);
+
+                let all = [a, b, c];
+                assert_eq!(all.iter().product::<$name>(), a * b * c);
+                assert_eq!(all.iter().cloned().product::<$name>(), a * b * c);
+
+                let mut acc = $name::identity();
+                for m in all.iter() {
+                    acc *= m;
+                }
+                assert_eq!(all.iter().product::<$name>(), acc);
+            }
+
+            assert_eq!(Vec::<$name>::new().into_iter().product::<$name>(), $name::identity());
+            assert_eq!([].iter().product::<$name>(), $name::identity());
+        }
+
+        #[test]
+        fn consts() {
+            static IDENTITY: $name = $name::IDENTITY;
+            const ZERO: $name = $name::ZERO;
+            const FILLED: $name = $name::filled(2.5);
+            const NEW: $name = $name::new($(replace_tt!($dims, $vec::ONE)),+);
+
+            assert_eq!(IDENTITY, $name::identity());
+            assert_eq!(ZERO, $name::zeros());
+            assert_eq!(FILLED, $name::ones() * 2.5);
+            assert_eq!(NEW, $name::ones());
+            assert_eq!($name::filled(0.0), $name::ZERO);
+            assert!($name::filled(f32::NAN).iter().all(f32::is_nan));
+        }
+
+        #[test]
+        fn col_mut_row_mut() {
+            let n = $name::DIMS;
+            let mut m = $name::from_fn(|c, r| (10 * c + r) as f32);
+
+            for i in 0..n {
+                m.col_mut(i)[n - 1] = -1.0;
+                m.col_mut(i)[0] += 100.0;
+            }
+            for i in 0..n {
+                assert_eq!(m[i][n - 1], -1.0);
+                assert_eq!(m[i][0], (10 * i) as f32 + 100.0);
+            }
+
+            let original = m;
+            for i in 0..n {
+                let mut row = m.row_mut(i);
+                assert_eq!(row.row_index(), i);
+                assert_eq!(row.get(), original.get_row(i));
+
+                let r = row.get();
+                row.set(r * 2.0);
+                row[0] = 7.0;
+                assert_eq!(row[0], 7.0);
+            }
+            for c in 0..n {
+                for r in 0..n {
+                    let expected = if c == 0 { 7.0 } else { original[c][r] * 2.0 };
+                    assert_eq!(m[c][r], expected);
+                }
+            }
+        }
+
+        #[test]
+        #[should_panic(expected = "::col_mut] Column index")]
+        fn col_mut_out_of_bounds() {
+            let mut m = $name::identity();
+            m.col_mut($name::DIMS);
+        }
+
+        #[test]
+        #[should_panic(expected = "::row_mut] Row index")]
+        fn row_mut_out_of_bounds() {
+            let mut m = $name::identity();
+            m.row_mut($name::DIMS);
+        }
+
+        #[test]
+        #[should_panic(expected = "[RowMut::index] Column index")]
+        fn row_mut_index_out_of_bounds() {
+            let mut m = $name::identity();
+            let row = m.row_mut(0);
+            let _ = row[$name::DIMS];
+        }
+
+        #[test]
+        #[should_panic(expected = "[RowMut::index_mut] Column index")]
+        fn row_mut_index_mut_out_of_bounds() {
+            let mut m = $name::identity();
+            m.row_mut(0)[$name::DIMS] = 1.0;
+        }
+
+        #[test]
+        fn solve() {
+            let mut rng = crate::test_util::Lcg::new(0x501);
+            let mut solved = 0;
+            while solved < 200 {
+                let m: $name = rng.mat(-2.0, 2.0);
+                if m.determinant().abs() < 0.5 {
+                    continue;
+                }
+
+                let mut x = $vec::zeros();
+                for i in 0..$name::DIMS {
+                    x[i] = rng.next_f32(-5.0, 5.0);
+                }
+                let b = &m * &x;
+                let sol = m.solve(b).expect("solve failed for invertible matrix");
+                assert_within_threshold!(sol, x, $vec::ones() * 1e-3, "m: {:?}", m);
+                solved += 1;
+            }
+        }
+
+        #[test]
+        fn solve_singular() {
+            let mut rng = crate::test_util::Lcg::new(0x502);
+            for _ in 0..50 {
+                let mut m: $name = rng.mat(-2.0, 2.0);
+                m.set_col(1, m.get_col(0) * 2.0);
+                let b = $vec::ones();
+                if let Some(x) = m.solve(b) {
+                    // Rounding can leave a tiny nonzero pivot; the result is then huge.
+                    let big = x.as_ref().iter().any(|e| e.abs() > 1e4);
+                    assert!(big, "m: {:?}\nx: {:?}", m, x);
+                }
+            }
+
+            assert_eq!($name::zeros().solve($vec::ones()), None);
+            let mut m = $name::identity();
+            m.set_row(1, $vec::zeros());
+            assert_eq!(m.solve($vec::ones()), None);
+        }
+
+        #[test]
+        fn solve_non_finite() {
+            let mut m = $name::identity();
+            m[0][0] = f32::NAN;
+            assert_eq!(m.solve($vec::ones()), None);
+
+            let mut m = $name::identity();
+            m[$name::DIMS - 1][0] = f32::INFINITY;
+            assert_eq!(m.solve($vec::ones()), None);
+
+            let mut b = $vec::ones();
+            b[1] = f32::NAN;
+            assert_eq!($name::identity().solve(b), None);
+        }
 
-            assert_approx_eq!(m.determinant(), 0.0);
+        #[test]
+        fn solve_nearly_singular() {
+            let mut m = $name::identity();
+            m[0][1] = 1.0;
+            m[1][0] = 1.0;
+            m[1][1] = 1.0 + 1e-4;
+
+            let b = $vec::ones();
+            let x = m.solve(b).expect("nearly singular matrix should still be solved");
+            assert_within_threshold!(&m * &x, b, $vec::ones() * 1e-3, "x: {:?}", x);
         }
 
         #[test]
@@ -681,9 +2658,71 @@ macro_rules! test_mat {
 #[cfg(test)]
 mod test_mat2 {
     use super::*;
+    use crate::test_util::*;
 
     test_mat!(Mat2, Vec2, x, y);
 
+    #[test]
+    fn display() {
+        assert_eq!(format!("{}", Mat2::identity()), "[1, 0]\n[0, 1]");
+        let m = Mat2::from_rows(vec2!(1.0, -20.0), vec2!(300.0, 4.0));
+        assert_eq!(format!("{}", m), "[  1, -20]\n[300,   4]");
+    }
+
+    #[test]
+    fn debug() {
+        let m = Mat2::from_rows(vec2!(1.0, 2.0), vec2!(-30.0, 4.5));
+        assert_eq!(format!("{:?}", m), "Mat2 { cols: [[1.0, -30.0], [2.0, 4.5]] }");
+        assert_eq!(
+            format!("{:#?}", m),
+            "Mat2 {\n    // rows\n    [  1.0, 2.0],\n    [-30.0, 4.5],\n}"
+        );
+    }
+
+    #[test]
+    fn from_angle() {
+        let thresh = Vec2::ones() * 1e-5;
+        let quarter = Mat2::from_angle(Angle::from_degrees(90.0));
+        assert_within_threshold!(quarter * Vec2::x(), Vec2::y(), thresh);
+        assert_within_threshold!(quarter * Vec2::y(), -Vec2::x(), thresh);
+
+        for deg in (-360..=360).step_by(15) {
+            let a = Angle::from_degrees(deg as f32);
+            let m = Mat2::from_angle(a);
+            assert_within_threshold!(m.determinant(), 1.0, 1e-5);
+            assert_within_threshold!(m.try_inverse().unwrap(), m.transpose(), Mat2::ones() * 1e-5);
+            assert_within_threshold!(
+                m * Mat2::from_angle(-a),
+                Mat2::identity(),
+                Mat2::ones() * 1e-5
+            );
+
+            let (s, c) = a.sin_cos();
+            for v in GenVec2::new(-3, 3) {
+                let expected = vec2!(c * v.x - s * v.y, s * v.x + c * v.y);
+                assert_within_threshold!(m * v, expected, thresh);
+                assert_within_threshold!((m * v).length(), v.length(), 1e-4);
+            }
+        }
+    }
+
+    #[test]
+    fn shear() {
+        for amt in -4..=4 {
+            let amt = amt as f32 * 0.5;
+            let x_shear = Mat2::new(Vec2::x(), vec2!(amt, 1.0));
+            let y_shear = Mat2::new(vec2!(1.0, amt), Vec2::y());
+            assert_eq!(x_shear.determinant(), 1.0);
+            assert_eq!(x_shear.inverse(), Mat2::new(Vec2::x(), vec2!(-amt, 1.0)));
+
+            for v in GenVec2::new(-3, 3) {
+                assert_eq!(x_shear * v, vec2!(v.x + amt * v.y, v.y));
+                assert_eq!(y_shear * v, vec2!(v.x, v.y + amt * v.x));
+                assert_approx_eq!(x_shear.inverse() * (x_shear * v), v);
+            }
+        }
+    }
+
     use std::mem::{size_of, align_of};
 
     #[test]
@@ -699,6 +2738,7 @@ mod test_mat2 {
 #[cfg(test)]
 mod test_mat3 {
     use super::*;
+    use crate::test_util::*;
 
     test_mat!(Mat3, Vec3, x, y, z);
 
@@ -740,6 +2780,321 @@ mod test_mat3 {
         }
     }
 
+    #[test]
+    fn from_rotation() {
+        let thresh = Vec3::ones() * 1e-5;
+        for axis in GenVec3::new(-2, 2) {
+            if axis == Vec3::zeros() {
+                continue;
+            }
+
+            for deg in (-360..=360).step_by(30) {
+                let a = Angle::from_degrees(deg as f32);
+                let m = Mat3::from_rotation(axis, a);
+                let m4 = crate::transform::rotate(axis, a);
+                for col in 0..3 {
+                    assert_eq!(m[col], m4[col].truncate());
+                }
+
+                assert_within_threshold!(m.determinant(), 1.0, 1e-5);
+                assert_within_threshold!(m.inverse(), m.transpose(), Mat3::ones() * 1e-5);
+
+                for v in GenVec3::new(-2, 2) {
+                    let expected = crate::transform::rotate_vec3(axis, a, v);
+                    assert_within_threshold!(m * v, expected, thresh);
+                }
+            }
+        }
+
+        let m = Mat3::from_rotation(Vec3::z(), Angle::from_degrees(90.0));
+        assert_within_threshold!(m * Vec3::x(), Vec3::y(), thresh);
+    }
+
+    #[test]
+    fn rotation_angle() {
+        let degrees = [
+            0.0, 1e-3, 0.1, 1.0, 30.0, 90.0, 135.0, 179.0, 179.9, 179.999, 180.0, 180.001, 270.0,
+            359.999, -1e-3, -45.0, -179.999, -180.0,
+        ];
+
+        for axis in GenVec3::new(-2, 2) {
+            if axis == Vec3::zeros() {
+                continue;
+            }
+
+            for &deg in degrees.iter() {
+                let m = Mat3::from_rotation(axis, Angle::from_degrees(deg));
+                let angle = m.rotation_angle().unwrap();
+
+                let folded = deg.abs() % 360.0;
+                let expected = if folded > 180.0 { 360.0 - folded } else { folded };
+                assert_within_threshold!(
+                    angle.degrees(),
+                    expected,
+                    1e-2,
+                    "Failure with axis {:?}, {} degrees: got {:?}",
+                    axis,
+                    deg,
+                    angle
+                );
+
+                let m4 = crate::transform::rotate(axis, Angle::from_degrees(deg));
+                assert_eq!(m4.rotation_angle(), Some(angle));
+            }
+        }
+
+        let mut scaled = Mat3::identity();
+        scaled[1][1] = 2.0;
+        assert!(scaled.rotation_angle().is_none());
+
+        let mut reflected = Mat3::identity();
+        reflected[2][2] = -1.0;
+        assert!(reflected.rotation_angle().is_none());
+        assert!(Mat3::zeros().rotation_angle().is_none());
+    }
+
+    #[test]
+    fn qr() {
+        let mut rng = Lcg::new(0x9e);
+        for _ in 0..200 {
+            let m: Mat3 = rng.mat(-3.0, 3.0);
+            if m.determinant().abs() < 0.1 {
+                continue;
+            }
+
+            let (q, r) = m.qr();
+            assert_within_threshold!(q.transpose() * q, Mat3::identity(), Mat3::ones() * 1e-5);
+            for c in 0..3 {
+                assert!(r[c][c] >= 0.0, "r: {:?}", r);
+                for row in (c + 1)..3 {
+                    assert_eq!(r[c][row], 0.0);
+                }
+            }
+            assert_within_threshold!(q * r, m, Mat3::ones() * 1e-4, "m: {:?}", m);
+            assert_eq!(m.orthonormalize(), q);
+        }
+    }
+
+    #[test]
+    fn orthonormalize_orthogonal() {
+        let ulps = Mat3::ones() * (4.0 * f32::EPSILON);
+        for axis in GenVec3::new(-1, 1) {
+            if axis == Vec3::zeros() {
+                continue;
+            }
+
+            let r = Mat3::from_rotation(axis, Angle::from_degrees(37.0));
+            assert_within_threshold!(r.orthonormalize(), r, ulps);
+
+            let mut reflected = r;
+            reflected[1] = -reflected[1];
+            assert_within_threshold!(reflected.orthonormalize(), reflected, ulps);
+        }
+
+        assert_eq!(Mat3::identity().orthonormalize(), Mat3::identity());
+    }
+
+    #[test]
+    fn polar_decompose() {
+        let mut rng = Lcg::new(0x9a);
+        for _ in 0..100 {
+            let axis = rng.vec3(-1.0, 1.0);
+            if axis.length() < 0.1 {
+                continue;
+            }
+
+            let rot = Mat3::from_rotation(axis, Angle::from_degrees(rng.next_f32(-180.0, 180.0)));
+            let scale = Mat3::from_diagonal(rng.vec3(0.2, 5.0));
+            let m = rot * scale;
+
+            let (r, s, iterations) = m.polar_iterate().unwrap();
+            assert!(iterations < POLAR_MAX_ITERATIONS, "m: {:?}", m);
+            assert_within_threshold!(r, rot, Mat3::ones() * 1e-4, "m: {:?}", m);
+            assert_within_threshold!(s, scale, Mat3::ones() * 1e-4, "m: {:?}", m);
+            assert!(r.is_rotation(1e-5));
+            assert!(s.is_symmetric(0.0));
+        }
+    }
+
+    #[test]
+    fn polar_decompose_shear_and_reflection() {
+        let mut rng = Lcg::new(0x9b);
+        let mut checked = 0;
+        while checked < 100 {
+            let m: Mat3 = rng.mat(-3.0, 3.0);
+            if m.determinant().abs() < 0.1 {
+                continue;
+            }
+
+            let (r, s, iterations) = m.polar_iterate().unwrap();
+            assert!(iterations < POLAR_MAX_ITERATIONS, "m: {:?}", m);
+            assert!(r.is_rotation(1e-5), "m: {:?}\nr: {:?}", m, r);
+            assert!(s.is_symmetric(0.0));
+            assert_within_threshold!(r * s, m, Mat3::ones() * 1e-4, "m: {:?}", m);
+
+            let (vals, _) = s.symmetric_eigen();
+            let negative = vals.iter().filter(|&&x| x < 0.0).count();
+            assert_eq!(negative, if m.determinant() > 0.0 { 0 } else { 1 }, "s: {:?}", s);
+
+            // No nearby rotation is closer to `m` than `r`.
+            let dist = |q: Mat3| (m - q).iter().map(|e| e * e).sum::<f32>();
+            for _ in 0..10 {
+                let axis = rng.vec3(-1.0, 1.0);
+                let nudge = Mat3::from_rotation(axis, Angle::from_degrees(rng.next_f32(-5.0, 5.0)));
+                assert!(dist(r) <= dist(r * nudge) + 1e-4, "m: {:?}\nr: {:?}", m, r);
+            }
+            checked += 1;
+        }
+
+        // The reflected axis is the one with the smallest scale.
+        let m = Mat3::from_diagonal(vec3!(2.0, -1.0, 3.0));
+        let (r, s) = m.polar_decompose().unwrap();
+        assert_within_threshold!(r, Mat3::identity(), Mat3::ones() * 1e-6);
+        assert_within_threshold!(s, m, Mat3::ones() * 1e-6);
+
+        let m = Mat3::from_rotation(vec3!(1.0, 2.0, -1.0), Angle::from_degrees(40.0))
+            * Mat3::from_diagonal(vec3!(-0.5, 2.0, 3.0));
+        let (r, s) = m.polar_decompose().unwrap();
+        assert_within_threshold!(r, Mat3::from_rotation(vec3!(1.0, 2.0, -1.0),
+            Angle::from_degrees(40.0)), Mat3::ones() * 1e-5);
+        assert_within_threshold!(s, Mat3::from_diagonal(vec3!(-0.5, 2.0, 3.0)),
+            Mat3::ones() * 1e-5);
+
+        let (r, s) = Mat3::from_diagonal(vec3!(1.0, -1.0, 1.0)).polar_decompose().unwrap();
+        assert!(r.is_rotation(1e-6));
+        assert_within_threshold!(s.trace(), 1.0, 1e-6);
+    }
+
+    #[test]
+    fn polar_decompose_singular() {
+        assert_eq!(Mat3::zeros().polar_decompose(), None);
+        let mut m = Mat3::identity();
+        m[2] = Vec3::zeros();
+        assert_eq!(m.polar_decompose(), None);
+    }
+
+    fn check_eigen(m: Mat3, thresh: f32) {
+        let (vals, vecs) = m.symmetric_eigen();
+        assert!(vals[0] >= vals[1] && vals[1] >= vals[2], "vals: {:?}", vals);
+        assert_within_threshold!(vecs.transpose() * vecs, Mat3::identity(),
+            Mat3::ones() * 1e-5, "m: {:?}\nvecs: {:?}", m, vecs);
+
+        let lambda = Mat3::from_diagonal(Vec3::new(vals[0], vals[1], vals[2]));
+        let recon = vecs * lambda * vecs.transpose();
+        assert_within_threshold!(recon, m, Mat3::ones() * thresh, "m: {:?}\nv: {:?}", m, vecs);
+        for i in 0..3 {
+            assert_within_threshold!(m * vecs[i], vecs[i] * vals[i], Vec3::ones() * thresh);
+        }
+    }
+
+    #[test]
+    fn symmetric_eigen() {
+        let mut rng = Lcg::new(0xe1);
+        for _ in 0..200 {
+            let a: Mat3 = rng.mat(-3.0, 3.0);
+            check_eigen(a * a.transpose(), 1e-3);
+            check_eigen(a + a.transpose(), 1e-4);
+        }
+    }
+
+    #[test]
+    fn symmetric_eigen_degenerate() {
+        let (vals, vecs) = Mat3::identity().symmetric_eigen();
+        assert_eq!(vals, [1.0; 3]);
+        assert_eq!(vecs, Mat3::identity());
+
+        let (vals, _) = Mat3::zeros().symmetric_eigen();
+        assert_eq!(vals, [0.0; 3]);
+
+        let u = vec3!(1.0, 2.0, -2.0);
+        let rank1 = u.outer(&u);
+        check_eigen(rank1, 1e-5);
+        let (vals, vecs) = rank1.symmetric_eigen();
+        assert_within_threshold!(vals[0], 9.0, 1e-5);
+        assert_within_threshold!(vals[1].abs() + vals[2].abs(), 0.0, 1e-5);
+        assert_within_threshold!(vecs[0].dot(&u).abs(), 3.0, 1e-5);
+
+        check_eigen(Mat3::from_diagonal(vec3!(2.0, 5.0, 2.0)), 1e-6);
+    }
+
+    #[test]
+    fn symmetric_eigen_symmetrizes() {
+        let mut rng = Lcg::new(0xe2);
+        for _ in 0..20 {
+            let m: Mat3 = rng.mat(-3.0, 3.0);
+            let sym = (m + m.transpose()) * 0.5;
+            assert_eq!(m.symmetric_eigen(), sym.symmetric_eigen());
+        }
+    }
+
+    #[test]
+    fn symmetric_eigen_non_finite() {
+        for &bad in [f32::NAN, f32::INFINITY, f32::NEG_INFINITY].iter() {
+            let mut m = Mat3::from_diagonal(vec3!(3.0, 2.0, 1.0));
+            m[1][0] = bad;
+            let (vals, _) = m.symmetric_eigen();
+            assert!(vals.iter().any(|x| !x.is_finite()), "bad: {}, vals: {:?}", bad, vals);
+
+            let (vals, _) = Mat3::from_diagonal(vec3!(1.0, bad, 2.0)).symmetric_eigen();
+            assert!(vals.iter().any(|x| !x.is_finite()), "bad: {}, vals: {:?}", bad, vals);
+        }
+    }
+
+    #[test]
+    fn orthogonal_rotation_predicates() {
+        let r = Mat3::from_rotation(vec3!(1.0, -2.0, 0.5), Angle::from_degrees(70.0));
+        assert!(r.is_orthogonal(1e-5));
+        assert!(r.is_rotation(1e-5));
+
+        let mut reflection = Mat3::identity();
+        reflection[1][1] = -1.0;
+        assert!(reflection.is_orthogonal(0.0));
+        assert!(!reflection.is_rotation(1e-3));
+        assert!(!(r * reflection).is_rotation(1e-3));
+
+        let scaled = r * 1.001;
+        assert!(!scaled.is_orthogonal(1e-3));
+        assert!(scaled.is_orthogonal(1e-2));
+        assert!(!scaled.is_rotation(1e-3));
+
+        let mut sheared = Mat3::identity();
+        sheared[1][0] = 0.5;
+        assert!(!sheared.is_orthogonal(0.1));
+        assert!(!Mat3::zeros().is_orthogonal(0.5));
+    }
+
+    #[test]
+    fn scale_and_inverse() {
+        for s in GenVec3::new(-2, 2) {
+            let mut m = Mat3::identity();
+            for i in 0..3 {
+                m[i][i] = s[i];
+            }
+
+            for v in GenVec3::new(-2, 2) {
+                assert_eq!(m * v, vec3!(s.x * v.x, s.y * v.y, s.z * v.z));
+            }
+
+            match m.try_inverse() {
+                Some(inv) => {
+                    assert_approx_eq!(inv * m, Mat3::identity());
+                    assert_approx_eq!(m * inv, Mat3::identity());
+                }
+                None => assert_eq!(s.x * s.y * s.z, 0.0),
+            }
+        }
+
+        let mut rng = Lcg::new(0x3);
+        for _ in 0..200 {
+            let m: Mat3 = rng.mat(-3.0, 3.0);
+            if let Some(inv) = m.try_inverse() {
+                if m.determinant().abs() > 0.25 {
+                    assert_within_threshold!(m * inv, Mat3::identity(), Mat3::ones() * 1e-4);
+                }
+            }
+        }
+    }
+
     use std::mem::{size_of, align_of};
 
     #[test]
@@ -756,6 +3111,7 @@ mod test_mat3 {
 #[cfg(test)]
 mod test_mat4 {
     use super::*;
+    use crate::test_util::*;
 
     test_mat!(Mat4, Vec4, x, y, z, w);
 
@@ -858,8 +3214,484 @@ mod test_mat4 {
             expected,
             actual
         );
-        assert_approx_eq!((&MAT * &actual), Mat4::identity());
-        assert_approx_eq!((&actual * &MAT), Mat4::identity());
+        assert_approx_eq!((MAT * actual), Mat4::identity());
+        assert_approx_eq!((actual * MAT), Mat4::identity());
+    }
+
+    #[test]
+    fn known_inverses() {
+        use crate::transform::{rotate, scale, translate};
+
+        let thresh = Mat4::ones() * 1e-5;
+        for v in GenVec3::new(-2, 2) {
+            assert_within_threshold!(translate(v).try_inverse().unwrap(), translate(-v), thresh);
+
+            if v.x != 0.0 && v.y != 0.0 && v.z != 0.0 {
+                let recip = Vec3::new(1.0 / v.x, 1.0 / v.y, 1.0 / v.z);
+                assert_within_threshold!(scale(v).try_inverse().unwrap(), scale(recip), thresh);
+            }
+
+            if v != Vec3::zeros() {
+                for deg in (-180..=180).step_by(30) {
+                    let r = rotate(v, Angle::from_degrees(deg as f32));
+                    assert_within_threshold!(r.try_inverse().unwrap(), r.transpose(), thresh);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn transform_determinants() {
+        use crate::transform::{reflect, rotate, scale};
+
+        assert_eq!(Mat4::identity().determinant(), 1.0);
+        for v in GenVec3::new(-2, 2) {
+            assert_approx_eq!(scale(v).determinant(), v.x * v.y * v.z);
+
+            if v != Vec3::zeros() {
+                assert_within_threshold!(reflect(v).determinant(), -1.0, 1e-5);
+                for deg in (-180..=180).step_by(30) {
+                    let r = rotate(v, Angle::from_degrees(deg as f32));
+                    assert_within_threshold!(r.determinant(), 1.0, 1e-5);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn from_diagonal_scale() {
+        for v in GenVec3::new(-3, 3) {
+            assert_eq!(Mat4::from_diagonal(v.extend(1.0)), crate::transform::scale(v));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "[RowMut::index] Column index 4 out of bounds for 4x4 matrix")]
+    fn row_mut_index_message() {
+        let mut m = Mat4::identity();
+        let _ = m.row_mut(0)[4];
+    }
+
+    #[test]
+    #[should_panic(expected = "[Mat4::index] (row, col) index (1, 4) out of bounds for 4x4 matrix")]
+    fn tuple_index_message() {
+        let _ = MAT[(1, 4)];
+    }
+
+    #[test]
+    fn random_inverses() {
+        let mut rng = Lcg::new(0x5eed);
+        let mut tested = 0;
+        while tested < 200 {
+            let m: Mat4 = rng.mat(-4.0, 4.0);
+            if m.determinant().abs() < 0.5 {
+                continue;
+            }
+            tested += 1;
+
+            let inv = m.try_inverse().unwrap();
+            let thresh = Mat4::ones() * 1e-4;
+            assert_within_threshold!(m * inv, Mat4::identity(), thresh, "m: {:?}", m);
+            assert_within_threshold!(inv * m, Mat4::identity(), thresh, "m: {:?}", m);
+            assert_eq!(m.inverse(), inv);
+        }
+    }
+
+    #[test]
+    fn singular_inverse() {
+        let mut m = MAT;
+        m.set_col(2, m.get_col(0) * 2.0);
+        assert!(m.try_inverse().is_none());
+        assert!(Mat4::zeros().try_inverse().is_none());
+        assert!(crate::transform::scale(vec3!(1.0, 0.0, 1.0)).try_inverse().is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "[Mat4::inverse] Matrix is singular")]
+    fn singular_inverse_panics() {
+        Mat4::zeros().inverse();
+    }
+
+    #[test]
+    fn is_identity_is_affine() {
+        let id = Mat4::identity();
+        assert!(id.is_identity(0.0));
+        assert!(id.is_affine(0.0));
+
+        let mut near = id;
+        near[3][0] = 1e-5;
+        near[2][2] = 1.0 - 1e-5;
+        assert!(near.is_identity(1e-4));
+        assert!(!near.is_identity(1e-6));
+        assert!(near.is_affine(0.0));
+
+        assert!(!MAT.is_identity(0.5));
+        assert!(!MAT.is_affine(0.5));
+
+        let mut projective = id;
+        projective[2][3] = -1.0;
+        projective[3][3] = 0.0;
+        assert!(!projective.is_affine(1e-3));
+
+        let mut near_affine = id;
+        near_affine[0][3] = 1e-5;
+        assert!(near_affine.is_affine(1e-4));
+        assert!(!near_affine.is_affine(1e-6));
+    }
+
+    #[test]
+    fn truncate_extend() {
+        let mut rng = Lcg::new(0x82);
+        for _ in 0..20 {
+            let m: Mat3 = rng.mat(-4.0, 4.0);
+            let t = rng.vec3(-4.0, 4.0);
+
+            assert_eq!(m.extend().truncate(), m);
+            assert_eq!(m.extend().translation(), Vec3::zeros());
+            assert_eq!(m.extend().get_row(3), Vec4::w());
+            assert_eq!(m.extend()[3], Vec4::w());
+
+            let a = Mat4::from_mat3_translation(m, t);
+            assert_eq!(a.truncate(), m);
+            assert_eq!(a.translation(), t);
+            assert_eq!(a.get_row(3), Vec4::w());
+            assert_eq!(a, crate::transform::translate(t) * m.extend());
+        }
+
+        for axis in GenVec3::new(-2, 2) {
+            if axis == Vec3::zeros() {
+                continue;
+            }
+            for deg in (-180..=180).step_by(45) {
+                let a = Angle::from_degrees(deg as f32);
+                let r = crate::transform::rotate(axis, a);
+                assert_eq!(r.truncate(), Mat3::from_rotation(axis, a));
+                assert_eq!(Mat3::from_rotation(axis, a).extend(), r);
+            }
+        }
+    }
+
+    #[test]
+    fn transform_point_vector() {
+        use crate::transform::{perspective, rotate, translate};
+
+        let affine =
+            translate(vec3!(1.0, -2.0, 3.0)) * rotate(Vec3::z(), Angle::from_degrees(30.0));
+        let projective = perspective(Angle::from_degrees(60.0), 1.5, 0.1, 100.0);
+        let translation = translate(vec3!(-0.5, 4.0, 2.0));
+        for p in GenVec3::new(-3, 3) {
+            for m in [affine, projective, translation, MAT].iter() {
+                let manual = (m * p.homogeneous()).homogenize();
+                let w = (m * p.homogeneous()).w;
+                if w != 0.0 {
+                    assert_eq!(m.transform_point3(p), manual);
+                    assert_eq!(m.project_point3(p), manual);
+                }
+                assert_eq!(m.transform_vector3(p), (m * p.extend(0.0)).truncate());
+            }
+
+            assert_eq!(affine.transform_point3(p), (affine * p.homogeneous()).truncate());
+            assert_eq!(translation.transform_point3(p), p + vec3!(-0.5, 4.0, 2.0));
+            assert_eq!(translation.transform_vector3(p), p);
+        }
+
+        // A last row that is affine only up to rounding error still skips the divide.
+        let mut nearly_affine = affine;
+        nearly_affine[0][3] = 1e-7;
+        nearly_affine[3][3] = 1.0 - 2e-7;
+        let p = vec3!(1.0, 2.0, 3.0);
+        let v = nearly_affine * p.homogeneous();
+        assert!(v.w != 1.0);
+        assert_eq!(nearly_affine.transform_point3(p), v.truncate());
+        let mut points = [p];
+        nearly_affine.transform_points3(&mut points);
+        assert_eq!(points[0], v.truncate());
+
+        // Far enough from 1, the divide is performed.
+        let mut scaled_w = affine;
+        scaled_w[3][3] = 1.01;
+        assert_eq!(scaled_w.transform_point3(p), (scaled_w * p.homogeneous()).homogenize());
+
+        // A point behind the camera ends up with negative w, and is still divided through.
+        let behind = vec3!(0.5, 0.5, 2.0);
+        let clip = projective * behind.homogeneous();
+        assert!(clip.w < 0.0);
+        assert_eq!(projective.transform_point3(behind), clip.homogenize());
+        assert_eq!(projective.project_point3(behind), clip.homogenize());
+    }
+
+    #[test]
+    fn transform_points_batch() {
+        use crate::transform::{perspective, rotate, translate};
+
+        let mut rng = Lcg::new(0x5eed_0084);
+        let mats = [
+            translate(vec3!(1.0, -2.0, 3.0)) * rotate(Vec3::y(), Angle::from_degrees(45.0)),
+            perspective(Angle::from_degrees(60.0), 1.5, 0.1, 100.0),
+            rng.mat::<Mat4>(-5.0, 5.0),
+        ];
+
+        for m in mats.iter() {
+            for &len in [0, 1, 2, 17, 100].iter() {
+                let src: Vec<Vec3> = (0..len).map(|_| rng.vec3(-10.0, 10.0)).collect();
+                let expected: Vec<Vec3> = src.iter().map(|&p| m.transform_point3(p)).collect();
+
+                let mut in_place = src.clone();
+                m.transform_points3(&mut in_place);
+                assert_eq!(in_place, expected);
+
+                let mut dst = vec![Vec3::zeros(); len];
+                m.transform_points3_into(&src, &mut dst);
+                assert_eq!(dst, expected);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "[Mat4::transform_points3_into] Source and destination lengths differ")]
+    fn transform_points_into_length_mismatch() {
+        let src = [Vec3::zeros(); 3];
+        let mut dst = [Vec3::zeros(); 2];
+        MAT.transform_points3_into(&src, &mut dst);
+    }
+
+    #[test]
+    fn lu_reconstruction() {
+        let mut rng = Lcg::new(0x104);
+        for _ in 0..200 {
+            let a: Mat4 = rng.mat(-3.0, 3.0);
+            let lu = match a.lu() {
+                Some(lu) => lu,
+                None => continue,
+            };
+
+            let l = lu.l();
+            let u = lu.u();
+            for c in 0..4 {
+                assert_eq!(l[c][c], 1.0);
+                for r in 0..c {
+                    assert_eq!(l[c][r], 0.0);
+                    assert_eq!(u[r][c], 0.0);
+                }
+            }
+
+            assert_within_threshold!(lu.p() * a, l * u, Mat4::ones() * 1e-4, "a: {:?}", a);
+
+            let det = a.determinant();
+            let thresh = 1e-4 * (1.0 + det.abs());
+            assert_within_threshold!(lu.determinant(), det, thresh, "a: {:?}", a);
+        }
+    }
+
+    #[test]
+    fn lu_solve_and_inverse() {
+        let mut rng = Lcg::new(0x105);
+        let mut solved = 0;
+        while solved < 200 {
+            let a: Mat4 = rng.mat(-2.0, 2.0);
+            if a.determinant().abs() < 0.5 {
+                continue;
+            }
+
+            let lu = a.lu().unwrap();
+            for _ in 0..4 {
+                let x = rng.vec4(-5.0, 5.0);
+                assert_within_threshold!(lu.solve(a * x), x, Vec4::ones() * 1e-3, "a: {:?}", a);
+            }
+            assert_within_threshold!(a * lu.inverse(), Mat4::identity(), Mat4::ones() * 1e-4);
+            solved += 1;
+        }
+    }
+
+    #[test]
+    fn lu_ill_conditioned() {
+        // The 4x4 Hilbert matrix has a condition number of roughly 1.5e4.
+        let hilbert = Mat4::from_fn(|c, r| 1.0 / (c + r + 1) as f32);
+        let lu = hilbert.lu().unwrap();
+
+        let x = vec4!(1.0, -1.0, 2.0, 0.5);
+        let b = hilbert * x;
+        let sol = lu.solve(b);
+        assert_within_threshold!(hilbert * sol, b, Vec4::ones() * 1e-5, "sol: {:?}", sol);
+        assert_within_threshold!(sol, x, Vec4::ones() * 5e-2, "sol: {:?}", sol);
+    }
+
+    #[test]
+    fn lu_singular() {
+        assert_eq!(Mat4::zeros().lu(), None);
+        assert_eq!(Mat4::ones().lu(), None);
+
+        let mut m = Mat4::identity();
+        m.set_row(2, Vec4::zeros());
+        assert_eq!(m.lu(), None);
+    }
+
+    #[test]
+    fn lu_non_finite() {
+        let mut m = Mat4::identity();
+        m[0][0] = f32::NAN;
+        assert_eq!(m.lu(), None);
+
+        // A NaN below the diagonal takes part in the pivot search.
+        let mut m = Mat4::identity();
+        m[0][2] = f32::NAN;
+        assert_eq!(m.lu(), None);
+
+        let mut m = Mat4::identity();
+        m[3][1] = f32::NEG_INFINITY;
+        assert_eq!(m.lu(), None);
+    }
+
+    #[test]
+    fn orthonormalize_preserves_translation() {
+        let mut rng = Lcg::new(0x9f);
+        for _ in 0..50 {
+            let linear: Mat3 = rng.mat(-3.0, 3.0);
+            if linear.determinant().abs() < 0.1 {
+                continue;
+            }
+
+            let t = rng.vec3(-10.0, 10.0);
+            let m = Mat4::from_mat3_translation(linear, t);
+            let o = m.orthonormalize();
+            assert_eq!(o.truncate(), linear.orthonormalize());
+            assert_eq!(o.translation(), t);
+            assert_eq!(o.get_row(3), Vec4::w());
+        }
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(
+            format!("{}", Mat4::identity()),
+            "[1, 0, 0, 0]\n[0, 1, 0, 0]\n[0, 0, 1, 0]\n[0, 0, 0, 1]"
+        );
+
+        let t = crate::transform::translate(vec3!(2.5, -10.0, 0.0));
+        assert_eq!(
+            format!("{:.1}", t),
+            "[1.0, 0.0, 0.0,   2.5]\n\
+             [0.0, 1.0, 0.0, -10.0]\n\
+             [0.0, 0.0, 1.0,   0.0]\n\
+             [0.0, 0.0, 0.0,   1.0]"
+        );
+
+        let m = Mat4::from_rows(
+            vec4!(-1.0, 12345.0, 0.5, 0.0),
+            vec4!(100.0, -2.0, -0.25, 3.0),
+            vec4!(0.0, 1.0, -1000.0, 7.0),
+            vec4!(4.0, 0.0, 0.0, 1.0),
+        );
+        assert_eq!(
+            format!("{}", m),
+            "[ -1, 12345,   0.5, 0]\n\
+             [100,    -2, -0.25, 3]\n\
+             [  0,     1, -1000, 7]\n\
+             [  4,     0,     0, 1]"
+        );
+        assert_eq!(
+            format!("{:6.2}", m),
+            "[ -1.00, 12345.00,     0.50,   0.00]\n\
+             [100.00,    -2.00,    -0.25,   3.00]\n\
+             [  0.00,     1.00, -1000.00,   7.00]\n\
+             [  4.00,     0.00,     0.00,   1.00]"
+        );
+    }
+
+    #[test]
+    fn debug() {
+        let m = Mat4::from_rows(
+            vec4!(1.0, 2.0, 3.0, 4.0),
+            vec4!(5.0, 6.0, 7.0, 8.0),
+            vec4!(9.0, 10.0, 11.0, 12.0),
+            vec4!(13.0, 14.0, 15.0, -16.0),
+        );
+        assert_eq!(m[0][1], 5.0);
+        assert_eq!(
+            format!("{:?}", m),
+            "Mat4 { cols: [[1.0, 5.0, 9.0, 13.0], [2.0, 6.0, 10.0, 14.0], \
+             [3.0, 7.0, 11.0, 15.0], [4.0, 8.0, 12.0, -16.0]] }"
+        );
+        assert_eq!(
+            format!("{:#?}", m),
+            "Mat4 {
+    // rows
+    [ 1.0,  2.0,  3.0,   4.0],
+    [ 5.0,  6.0,  7.0,   8.0],
+    [ 9.0, 10.0, 11.0,  12.0],
+    [13.0, 14.0, 15.0, -16.0],
+}"
+        );
+        assert_eq!(
+            format!("{:.2?}", Mat4::identity()),
+            "Mat4 { cols: [[1.00, 0.00, 0.00, 0.00], [0.00, 1.00, 0.00, 0.00], \
+             [0.00, 0.00, 1.00, 0.00], [0.00, 0.00, 0.00, 1.00]] }"
+        );
+    }
+
+    #[test]
+    fn handedness() {
+        use crate::transform::{rotate, scale, translate};
+
+        let eps = 1e-6;
+        for axis in GenVec3::new(-1, 1) {
+            if axis == Vec3::zeros() {
+                continue;
+            }
+
+            let r = translate(axis) * rotate(axis, Angle::from_degrees(70.0));
+            assert_eq!(r.is_right_handed(eps), Some(true));
+            assert_eq!(r.truncate().is_right_handed(eps), Some(true));
+            assert!(!r.winding_flipped());
+        }
+
+        for i in 0..3 {
+            let mut s = Vec3::ones();
+            s[i] = -1.0;
+            let mirror = scale(s);
+            assert_eq!(mirror.is_right_handed(eps), Some(false));
+            assert!(mirror.winding_flipped());
+            assert!(mirror.truncate().winding_flipped());
+
+            let mut s2 = s;
+            s2[(i + 1) % 3] = -2.0;
+            let double = scale(s2);
+            assert_eq!(double.is_right_handed(eps), Some(true));
+            assert!(!double.winding_flipped());
+
+            let mut z = Vec3::ones();
+            z[i] = 0.0;
+            let flat = scale(z);
+            assert_eq!(flat.is_right_handed(eps), None);
+            assert!(!flat.winding_flipped());
+        }
+
+        assert_eq!(scale(Vec3::ones() * 1e-3).is_right_handed(1e-6), None);
+        assert_eq!(scale(Vec3::ones() * 1e-3).is_right_handed(1e-10), Some(true));
+    }
+
+    #[test]
+    fn orthogonal_rotation_predicates() {
+        use crate::transform::{perspective, reflect, rotate, scale, translate};
+
+        let r = translate(vec3!(1.0, 2.0, 3.0)) * rotate(Vec3::y(), Angle::from_degrees(20.0));
+        assert!(r.is_orthogonal(1e-5));
+        assert!(r.is_rotation(1e-5));
+        assert!(r.is_affine(0.0));
+        assert!(!r.is_symmetric(1e-3));
+
+        let m = reflect(vec3!(1.0, 1.0, 0.0));
+        assert!(m.is_orthogonal(1e-5));
+        assert!(!m.is_rotation(1e-3));
+        assert!(m.is_symmetric(1e-6));
+
+        let s = scale(vec3!(1.0, 1.0 + 1e-4, 1.0));
+        assert!(s.is_orthogonal(1e-3));
+        assert!(!s.is_orthogonal(1e-5));
+
+        let p = perspective(Angle::from_degrees(60.0), 1.5, 0.1, 100.0);
+        assert!(!p.is_affine(1e-3));
+        assert!(!p.is_orthogonal(1e-3));
     }
 
     use std::mem::{size_of, align_of};
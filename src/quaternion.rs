@@ -45,6 +45,50 @@ impl Quaternion {
         Quaternion::new(vec.x, vec.y, vec.z, r)
     }
 
+    /// Construct the unit `Quaternion` representing a rotation about the given axis by the given
+    /// angle.
+    ///
+    /// The axis need not be of unit length.
+    pub fn from_axis_angle(axis: Vec3, angle: Angle) -> Quaternion {
+        let (s, c) = (angle / 2.0).sin_cos();
+        Quaternion::real_vector(c, s * axis.unit())
+    }
+
+    /// Spherically interpolate between two unit `Quaternion`s.
+    ///
+    /// At `t = 0` this produces `self`, and at `t = 1` it produces `other` (up to sign, since `q`
+    /// and `-q` represent the same rotation). The interpolation always follows the shorter arc
+    /// between the two rotations. Values of `t` outside of [0, 1] extrapolate along the same arc.
+    ///
+    /// Both quaternions are assumed to be of unit magnitude.
+    pub fn slerp(&self, other: &Quaternion, t: f32) -> Quaternion {
+        let mut other = *other;
+        let mut dot = self.r * other.r + self.i * other.i + self.j * other.j + self.k * other.k;
+        if dot < 0.0 {
+            other = -other;
+            dot = -dot;
+        }
+
+        let (a, b) = if dot > 0.9995 {
+            (1.0 - t, t)
+        } else {
+            let theta = dot.acos();
+            let sin_theta = theta.sin();
+            (
+                ((1.0 - t) * theta).sin() / sin_theta,
+                (t * theta).sin() / sin_theta,
+            )
+        };
+
+        Quaternion {
+            r: a * self.r + b * other.r,
+            i: a * self.i + b * other.i,
+            j: a * self.j + b * other.j,
+            k: a * self.k + b * other.k,
+        }
+        .unit()
+    }
+
     /// Get the imaginary components of this `Quaternion` as a `Vec3`.
     #[inline(always)]
     pub fn vector_part(&self) -> Vec3 {
@@ -269,6 +313,7 @@ quatop_div!(&Quaternion, &Quaternion);
 
 macro_rules! quatop_div_assign {
     ($rhs:ty) => {
+        #[allow(clippy::suspicious_op_assign_impl)]
         impl DivAssign<$rhs> for Quaternion {
             fn div_assign(&mut self, rhs: $rhs) {
                 *self *= rhs.inverse();
@@ -339,6 +384,26 @@ mod test {
         assert_approx_eq!(i * j * k, -r);
     }
 
+    #[test]
+    fn slerp() {
+        let a = Quaternion::from_axis_angle(Vec3::z(), Angle::zero());
+        let b = Quaternion::from_axis_angle(Vec3::z(), Angle::from_degrees(90.0));
+
+        assert_approx_eq!(a.slerp(&b, 0.0), a);
+        assert_approx_eq!(a.slerp(&b, 1.0), b);
+        assert_approx_eq!(
+            a.slerp(&b, 0.5),
+            Quaternion::from_axis_angle(Vec3::z(), Angle::from_degrees(45.0))
+        );
+        assert_approx_eq!(
+            a.slerp(&b, 2.0),
+            Quaternion::from_axis_angle(Vec3::z(), Angle::from_degrees(180.0))
+        );
+
+        // Takes the shorter arc.
+        assert_approx_eq!(a.slerp(&-b, 0.5), a.slerp(&b, 0.5));
+    }
+
     #[test]
     fn mem_layout() {
         assert_eq!(size_of::<Quaternion>(), 16);
@@ -1,8 +1,9 @@
 #![allow(dead_code)]
 
+use crate::mat::SquareMatrix;
 use crate::vec::{Vec2, Vec3, Vec4};
 
-use std::ops::RangeInclusive;
+use std::ops::{IndexMut, RangeInclusive};
 
 type Range = RangeInclusive<i16>;
 
@@ -138,3 +139,53 @@ impl Iterator for GenVec4 {
         Some(v)
     }
 }
+
+/// A small deterministic pseudo-random generator, for tests that want "random" inputs without
+/// pulling in a dependency.
+pub struct Lcg {
+    state: u32,
+}
+
+impl Lcg {
+    pub fn new(seed: u32) -> Lcg {
+        Lcg { state: seed }
+    }
+
+    /// Get the next value, uniformly distributed in `[lower, upper)`.
+    pub fn next_f32(&mut self, lower: f32, upper: f32) -> f32 {
+        self.state = self.state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+        let unit = (self.state >> 8) as f32 / (1u32 << 24) as f32;
+        lower + unit * (upper - lower)
+    }
+
+    pub fn vec2(&mut self, lower: f32, upper: f32) -> Vec2 {
+        Vec2::new(self.next_f32(lower, upper), self.next_f32(lower, upper))
+    }
+
+    pub fn vec3(&mut self, lower: f32, upper: f32) -> Vec3 {
+        Vec3::new(
+            self.next_f32(lower, upper),
+            self.next_f32(lower, upper),
+            self.next_f32(lower, upper),
+        )
+    }
+
+    pub fn vec4(&mut self, lower: f32, upper: f32) -> Vec4 {
+        self.vec3(lower, upper).extend(self.next_f32(lower, upper))
+    }
+
+    /// Get a matrix with every element drawn from `[lower, upper)`.
+    pub fn mat<M>(&mut self, lower: f32, upper: f32) -> M
+    where
+        M: SquareMatrix + IndexMut<usize, Output = <M as SquareMatrix>::VecType>,
+        M::VecType: IndexMut<usize, Output = f32>,
+    {
+        let mut m = M::zeros();
+        for col in 0..M::DIMS {
+            for row in 0..M::DIMS {
+                m[col][row] = self.next_f32(lower, upper);
+            }
+        }
+        m
+    }
+}
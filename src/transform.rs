@@ -1,6 +1,8 @@
 //! Assorted utilities for constructing 3D homogeneous transformation and projection matrices.
 
 use super::*;
+use std::fmt::{self, Display, Formatter};
+use std::ops::Mul;
 
 /// A builder struct for homogeneous transformation matrices.
 ///
@@ -54,6 +56,38 @@ impl Transform {
         }
     }
 
+    /// Create a new [`RecordedTransform`], which remembers each of its builder steps.
+    ///
+    /// [`RecordedTransform`]: struct.RecordedTransform.html
+    #[inline(always)]
+    pub fn recorded() -> RecordedTransform {
+        RecordedTransform::new()
+    }
+
+    /// Create a `Transform` that scales, then rotates, then translates.
+    ///
+    /// This is equivalent to
+    /// `Transform::new().scale(scale).rotate(axis, angle).translate(translation)`, but computes
+    /// the matrix directly. See the free function [`trs`](fn.trs.html).
+    #[inline(always)]
+    pub fn from_trs(translation: Vec3, axis: Vec3, angle: Angle, scale: Vec3) -> Transform {
+        Transform {
+            mat: trs(translation, axis, angle, scale),
+        }
+    }
+
+    /// Create a `Transform` that scales, then rotates by a quaternion, then translates.
+    ///
+    /// This is equivalent to
+    /// `Transform::new().scale(scale).rotate_quat(rotation).translate(translation)`, but computes
+    /// the matrix directly. See the free function [`trs_quat`](fn.trs_quat.html).
+    #[inline(always)]
+    pub fn from_trs_quat(translation: Vec3, rotation: Quaternion, scale: Vec3) -> Transform {
+        Transform {
+            mat: trs_quat(translation, rotation, scale),
+        }
+    }
+
     /// Translate by the given offset.
     #[inline(always)]
     pub fn translate(self, offset: Vec3) -> Transform {
@@ -62,6 +96,66 @@ impl Transform {
         }
     }
 
+    /// Translate by the given per-axis offsets.
+    ///
+    /// This is equivalent to `translate(vec3!(x, y, z))`.
+    #[inline(always)]
+    pub fn translate_xyz(self, x: f32, y: f32, z: f32) -> Transform {
+        self.translate(Vec3::new(x, y, z))
+    }
+
+    /// Translate by the given distance along the _x_ axis.
+    ///
+    /// This is equivalent to `translate(vec3!(distance, 0.0, 0.0))`, but updates the matrix
+    /// directly rather than performing a matrix multiplication.
+    ///
+    /// # Example
+    /// ```rust
+    /// # #[macro_use] extern crate gramit;
+    /// # use gramit::*;
+    /// use gramit::transform::Transform;
+    ///
+    /// # fn main() {
+    /// let t = Transform::new()
+    ///     .translate_x(1.0)
+    ///     .translate(vec3!(0.0, 2.0, 0.0))
+    ///     .translate_z(3.0);
+    ///
+    /// assert_eq!(t.transform_point(Vec3::zeros()), vec3!(1.0, 2.0, 3.0));
+    /// # }
+    /// ```
+    #[inline(always)]
+    pub fn translate_x(self, distance: f32) -> Transform {
+        self.translate_axis(0, distance)
+    }
+
+    /// Translate by the given distance along the _y_ axis.
+    ///
+    /// This is equivalent to `translate(vec3!(0.0, distance, 0.0))`. See
+    /// [`translate_x`](#method.translate_x).
+    #[inline(always)]
+    pub fn translate_y(self, distance: f32) -> Transform {
+        self.translate_axis(1, distance)
+    }
+
+    /// Translate by the given distance along the _z_ axis.
+    ///
+    /// This is equivalent to `translate(vec3!(0.0, 0.0, distance))`. See
+    /// [`translate_x`](#method.translate_x).
+    #[inline(always)]
+    pub fn translate_z(self, distance: f32) -> Transform {
+        self.translate_axis(2, distance)
+    }
+
+    fn translate_axis(mut self, axis: usize, distance: f32) -> Transform {
+        // Left-multiplying by a single-axis translation adds a multiple of the last row to the
+        // row for that axis.
+        for c in 0..4 {
+            self.mat[c][axis] += distance * self.mat[c][3];
+        }
+        self
+    }
+
     /// Scale by the given factors.
     ///
     /// The scaling is performed independently per-axis, using the corresponding factor from the
@@ -73,6 +167,121 @@ impl Transform {
         }
     }
 
+    /// Scale by the given per-axis factors.
+    ///
+    /// This is equivalent to `scale(vec3!(x, y, z))`.
+    #[inline(always)]
+    pub fn scale_xyz(self, x: f32, y: f32, z: f32) -> Transform {
+        self.scale(Vec3::new(x, y, z))
+    }
+
+    /// Scale uniformly by the given factor.
+    ///
+    /// This is equivalent to `scale(vec3!(factor, factor, factor))`.
+    ///
+    /// # Example
+    /// ```rust
+    /// # #[macro_use] extern crate gramit;
+    /// # use gramit::*;
+    /// use gramit::transform::Transform;
+    ///
+    /// # fn main() {
+    /// let mat: Mat4 = Transform::new()
+    ///     .uniform_scale(2.0)
+    ///     .rotate(Vec3::z(), Angle::from_degrees(90.0))
+    ///     .finish();
+    ///
+    /// assert_approx_eq!(
+    ///     (mat * vec3!(1.0, 0.0, 0.0).homogeneous()).homogenize(),
+    ///     vec3!(0.0, 2.0, 0.0));
+    /// # }
+    /// ```
+    #[inline(always)]
+    pub fn uniform_scale(self, factor: f32) -> Transform {
+        Transform {
+            mat: uniform_scale(factor) * self.mat,
+        }
+    }
+
+    /// Scale by the given factors about the given center point.
+    ///
+    /// Unlike `scale`, which scales about the origin, this leaves `center` fixed.
+    #[inline(always)]
+    pub fn scale_about(self, center: Vec3, factor: Vec3) -> Transform {
+        Transform {
+            mat: scale_about(center, factor) * self.mat,
+        }
+    }
+
+    /// Scale uniformly by the given factor about the given center point.
+    ///
+    /// This is equivalent to `scale_about(center, vec3!(factor, factor, factor))`.
+    #[inline(always)]
+    pub fn uniform_scale_about(self, center: Vec3, factor: f32) -> Transform {
+        Transform {
+            mat: uniform_scale_about(center, factor) * self.mat,
+        }
+    }
+
+    /// Reflect across the plane through the origin with the given normal.
+    ///
+    /// The normal need not be of unit length.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `normal` has zero length.
+    #[inline(always)]
+    pub fn reflect(self, normal: Vec3) -> Transform {
+        Transform {
+            mat: reflect(normal) * self.mat,
+        }
+    }
+
+    /// Reflect across the plane with the given normal passing through the given point.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `normal` has zero length.
+    #[inline(always)]
+    pub fn reflect_plane(self, normal: Vec3, point_on_plane: Vec3) -> Transform {
+        Transform {
+            mat: reflect_plane(normal, point_on_plane) * self.mat,
+        }
+    }
+
+    /// Mirror along the _x_ axis, i.e. reflect across the _yz_ plane.
+    ///
+    /// Mirroring flips the sign of the determinant and hence reverses the winding order of
+    /// triangles.
+    #[inline(always)]
+    pub fn mirror_x(self) -> Transform {
+        Transform {
+            mat: mirror_x() * self.mat,
+        }
+    }
+
+    /// Mirror along the _y_ axis, i.e. reflect across the _xz_ plane.
+    ///
+    /// Mirroring flips the sign of the determinant and hence reverses the winding order of
+    /// triangles.
+    #[inline(always)]
+    pub fn mirror_y(self) -> Transform {
+        Transform {
+            mat: mirror_y() * self.mat,
+        }
+    }
+
+    /// Mirror along the _z_ axis, i.e. reflect across the _xy_ plane.
+    ///
+    /// Mirroring flips the sign of the determinant and hence reverses the winding order of
+    /// triangles.
+    #[inline(always)]
+    pub fn mirror_z(self) -> Transform {
+        Transform {
+            mat: mirror_z() * self.mat,
+        }
+    }
+
     /// Shear by the given amount, fixing the _yz_ plane.
     ///
     /// This will shear the _x_ axis by the given amounts along the _y_ and _z_ axes.
@@ -103,6 +312,17 @@ impl Transform {
         }
     }
 
+    /// Shear by the given amount along the given direction, fixing the plane through the origin
+    /// with the given normal.
+    ///
+    /// See the free function [`shear`](fn.shear.html) for details.
+    #[inline(always)]
+    pub fn shear(self, plane_normal: Vec3, direction: Vec3, amount: f32) -> Transform {
+        Transform {
+            mat: shear(plane_normal, direction, amount) * self.mat,
+        }
+    }
+
     /// Rotate about the given axis by the given angle.
     #[inline(always)]
     pub fn rotate(self, axis: Vec3, angle: Angle) -> Transform {
@@ -111,314 +331,6913 @@ impl Transform {
         }
     }
 
-    /// Apply an arbitrary affine transformation, represented by a homogenous matrix.
+    /// Rotate by the rotation represented by the given quaternion.
+    ///
+    /// The quaternion is normalized first; see [`rotation_from_quat`](fn.rotation_from_quat.html).
     #[inline(always)]
-    pub fn arbitrary(self, transform: Mat4) -> Transform {
+    pub fn rotate_quat(self, q: Quaternion) -> Transform {
         Transform {
-            mat: transform * self.mat,
+            mat: rotation_from_quat(q) * self.mat,
         }
     }
 
-    /// Consume the `Transform` and acquire the resulting homogeneous transformation matrix.
+    /// Rotate about the given axis through the given point, by the given angle.
+    ///
+    /// Unlike `rotate`, which always rotates about a line through the origin, the line of rotation
+    /// here passes through `point`. Points on that line are left fixed.
     #[inline(always)]
-    pub fn finish(&self) -> Mat4 {
-        self.mat
+    pub fn rotate_about(self, point: Vec3, axis: Vec3, angle: Angle) -> Transform {
+        Transform {
+            mat: rotate_about(point, axis, angle) * self.mat,
+        }
     }
-}
-
-/// Get the homogeneous transformation matrix of a translation by the given offset.
-pub fn translate(offset: Vec3) -> Mat4 {
-    let offset = offset.extend(1.0);
-    let mut mat = Mat4::identity();
-    mat.set_col(3, offset);
-    mat
-}
 
-/// Get the homogeneous transformation matrix of a scale by the given factors.
-///
-/// Scaling is computed independently per-axis, using the corresponding factors in the given
-/// vector.
-pub fn scale(factor: Vec3) -> Mat4 {
-    let mut mat = Mat4::identity();
-    mat[0][0] = factor[0];
-    mat[1][1] = factor[1];
-    mat[2][2] = factor[2];
-    mat
-}
+    /// Rotate about the line through `point` with direction `dir` by the given angle.
+    ///
+    /// See the free function [`rotate_about_line`](fn.rotate_about_line.html).
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `dir` has zero length.
+    #[inline(always)]
+    pub fn rotate_about_line(self, point: Vec3, dir: Vec3, angle: Angle) -> Transform {
+        Transform {
+            mat: rotate_about_line(point, dir, angle) * self.mat,
+        }
+    }
 
-/// Get the homogeneous transformation matrix of a shear fixing the _yz_ plane by the given amounts
-/// parallel to the _y_ and _z_ axes.
-pub fn shear_x(y_amount: f32, z_amount: f32) -> Mat4 {
-    let mut m = Mat4::identity();
-    m[0][1] = y_amount;
-    m[0][2] = z_amount;
-    m
-}
+    /// Rotate by the minimal rotation taking the direction `from` onto the direction `to`.
+    ///
+    /// See the free function [`rotation_between`](fn.rotation_between.html).
+    ///
+    /// # Example
+    /// ```rust
+    /// # #[macro_use] extern crate gramit;
+    /// # use gramit::*;
+    /// use gramit::transform::Transform;
+    ///
+    /// # fn main() {
+    /// let forward = vec3!(0.0, 0.0, -1.0);
+    /// let target = vec3!(3.0, 0.0, 0.0);
+    /// let t = Transform::new().align(forward, target);
+    ///
+    /// assert_approx_eq!(t.transform_vector(forward), Vec3::x());
+    /// # }
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// This function panics if either `from` or `to` has zero length.
+    #[inline(always)]
+    pub fn align(self, from: Vec3, to: Vec3) -> Transform {
+        Transform {
+            mat: rotation_between(from, to) * self.mat,
+        }
+    }
 
-/// Get the homogeneous transformation matrix of a shear fixing the _xz_ plane by the given amounts
-/// parallel to the _x_ and _z_ axes.
-pub fn shear_y(x_amount: f32, z_amount: f32) -> Mat4 {
-    let mut m = Mat4::identity();
-    m[1][0] = x_amount;
-    m[1][2] = z_amount;
-    m
-}
+    /// Rotate about the _x_ axis by the given angle.
+    #[inline(always)]
+    pub fn rotate_x(self, angle: Angle) -> Transform {
+        Transform {
+            mat: rotate_x(angle) * self.mat,
+        }
+    }
 
-/// Get the homogeneous transformation matrix of a shear fixing the _xy_ plane by the given amounts
-/// parallel to the _x_ and _y_ axes.
-pub fn shear_z(x_amount: f32, y_amount: f32) -> Mat4 {
-    let mut m = Mat4::identity();
-    m[2][0] = x_amount;
-    m[2][1] = y_amount;
-    m
-}
+    /// Rotate about the _y_ axis by the given angle.
+    #[inline(always)]
+    pub fn rotate_y(self, angle: Angle) -> Transform {
+        Transform {
+            mat: rotate_y(angle) * self.mat,
+        }
+    }
 
-/// Get the homogeneous transformation matrix of a rotation about the given axis by the given
-/// angle.
-pub fn rotate(axis: Vec3, angle: Angle) -> Mat4 {
-    let half = angle / 2.0;
-    let w = half.cos();
-    let v = half.sin() * axis.unit();
+    /// Rotate about the _z_ axis by the given angle.
+    #[inline(always)]
+    pub fn rotate_z(self, angle: Angle) -> Transform {
+        Transform {
+            mat: rotate_z(angle) * self.mat,
+        }
+    }
 
-    let xy = v.x * v.y;
-    let xz = v.x * v.z;
-    let xw = v.x * w;
-    let x2 = v.x * v.x;
-    let yz = v.y * v.z;
-    let yw = v.y * w;
-    let y2 = v.y * v.y;
-    let zw = v.z * w;
-    let z2 = v.z * v.z;
+    /// Rotate by the given Euler angles.
+    ///
+    /// See the free function [`rotation_euler`](fn.rotation_euler.html).
+    #[inline(always)]
+    pub fn rotate_euler(self, angles: [Angle; 3], order: EulerOrder) -> Transform {
+        Transform {
+            mat: rotation_euler(angles, order) * self.mat,
+        }
+    }
 
-    Mat4::new(
-        Vec4::new(1.0 - 2.0 * (y2 + z2), 2.0 * (xy + zw), 2.0 * (xz - yw), 0.0),
-        Vec4::new(2.0 * (xy - zw), 1.0 - 2.0 * (x2 + z2), 2.0 * (yz + xw), 0.0),
-        Vec4::new(2.0 * (xz + yw), 2.0 * (yz - xw), 1.0 - 2.0 * (x2 + y2), 0.0),
-        Vec4::w(),
-    )
-}
+    /// Apply a look-at view transformation.
+    ///
+    /// See the free function [`look_at`](fn.look_at.html) for details on the parameters.
+    ///
+    /// # Example
+    /// ```rust
+    /// # #[macro_use] extern crate gramit;
+    /// # use gramit::*;
+    /// use gramit::transform::{self, Transform};
+    ///
+    /// # fn main() {
+    /// let eye = vec3!(3.0, 3.0, 3.0);
+    /// let center = Vec3::zeros();
+    /// let up = Vec3::z();
+    ///
+    /// let model_view = Transform::new()
+    ///     .scale(vec3!(2.0, 2.0, 1.0))
+    ///     .rotate(Vec3::z(), Angle::from_degrees(45.0))
+    ///     .look_at(eye, center, up)
+    ///     .finish();
+    ///
+    /// let model = Transform::new()
+    ///     .scale(vec3!(2.0, 2.0, 1.0))
+    ///     .rotate(Vec3::z(), Angle::from_degrees(45.0))
+    ///     .finish();
+    /// assert_eq!(model_view, transform::look_at(&eye, &center, &up) * model);
+    /// # }
+    /// ```
+    #[inline(always)]
+    pub fn look_at(self, eye: Vec3, center: Vec3, up: Vec3) -> Transform {
+        Transform {
+            mat: look_at(&eye, &center, &up) * self.mat,
+        }
+    }
 
-/// Build a look-at view matrix.
-///
-/// # Parameters
-/// * `eye` The position of the camera.
-/// * `center` The point towards which the camera is facing.
-/// * `up` A vector in the upwards direction, usually `vec3!(0.0, 0.0, 1.0)`.
-///
-/// # Usage Warnings
-/// An `up` vector parallel to the camera's facing direction will result in a singular matrix that
-/// collapses all points onto the _z_ axis. This is probably not what you want. The function does
-/// not check for this condition, so users should check their input to avoid it.
-pub fn look_at(eye: &Vec3, center: &Vec3, up: &Vec3) -> Mat4 {
-    let facing = (center - eye).unit();
-    let horiz = facing.cross(&up.unit());
-    let cam_up = horiz.cross(&facing);
+    /// Apply an arbitrary affine transformation, represented by a homogenous matrix.
+    #[inline(always)]
+    pub fn arbitrary(self, transform: Mat4) -> Transform {
+        Transform {
+            mat: transform * self.mat,
+        }
+    }
 
-    let mut mat = Mat4::identity();
-    mat.set_row(0, horiz.extend(0.0));
-    mat.set_row(1, cam_up.extend(0.0));
-    mat.set_row(2, -facing.extend(0.0));
+    /// Translate by the given offset, _before_ the transformations already applied.
+    ///
+    /// Where the other builder methods multiply their transformation on the left, this multiplies
+    /// on the right.
+    ///
+    /// # Example
+    /// ```rust
+    /// # #[macro_use] extern crate gramit;
+    /// # use gramit::*;
+    /// use gramit::transform::{self, Transform};
+    ///
+    /// # fn main() {
+    /// let t = Transform::new().rotate_z(Angle::from_degrees(90.0));
+    ///
+    /// // The translation happens first, so it is rotated along with everything else.
+    /// let x = Vec3::x();
+    /// assert_eq!(t.pre_translate(x).finish(), t.finish() * transform::translate(x));
+    /// assert_eq!(t.translate(x).finish(), transform::translate(x) * t.finish());
+    /// # }
+    /// ```
+    #[inline(always)]
+    pub fn pre_translate(self, offset: Vec3) -> Transform {
+        Transform {
+            mat: self.mat * translate(offset),
+        }
+    }
 
-    mat[3][0] = -eye.dot(&horiz);
-    mat[3][1] = -eye.dot(&cam_up);
-    mat[3][2] = eye.dot(&facing);
+    /// Scale by the given factors, _before_ the transformations already applied.
+    ///
+    /// Where the other builder methods multiply their transformation on the left, this multiplies
+    /// on the right.
+    ///
+    /// # Example
+    /// ```rust
+    /// # #[macro_use] extern crate gramit;
+    /// # use gramit::*;
+    /// use gramit::transform::{self, Transform};
+    ///
+    /// # fn main() {
+    /// let t = Transform::new().rotate_z(Angle::from_degrees(90.0));
+    ///
+    /// let s = vec3!(2.0, 1.0, 1.0);
+    /// // The scaling stretches along the original x axis, rather than the rotated one.
+    /// assert_eq!(t.pre_scale(s).finish(), t.finish() * transform::scale(s));
+    /// assert_eq!(t.scale(s).finish(), transform::scale(s) * t.finish());
+    /// # }
+    /// ```
+    #[inline(always)]
+    pub fn pre_scale(self, factor: Vec3) -> Transform {
+        Transform {
+            mat: self.mat * scale(factor),
+        }
+    }
 
-    mat
+    /// Shear by the given amount fixing the _yz_ plane, _before_ the transformations already
+    /// applied.
+    ///
+    /// Where the other builder methods multiply their transformation on the left, this multiplies
+    /// on the right.
+    ///
+    /// # Example
+    /// ```rust
+    /// # #[macro_use] extern crate gramit;
+    /// # use gramit::*;
+    /// use gramit::transform::{self, Transform};
+    ///
+    /// # fn main() {
+    /// let t = Transform::new().rotate_z(Angle::from_degrees(90.0));
+    ///
+    /// assert_eq!(t.pre_shear_x(1.0, 0.0).finish(), t.finish() * transform::shear_x(1.0, 0.0));
+    /// assert_eq!(t.shear_x(1.0, 0.0).finish(), transform::shear_x(1.0, 0.0) * t.finish());
+    /// # }
+    /// ```
+    #[inline(always)]
+    pub fn pre_shear_x(self, y_amount: f32, z_amount: f32) -> Transform {
+        Transform {
+            mat: self.mat * shear_x(y_amount, z_amount),
+        }
+    }
+
+    /// Shear by the given amount fixing the _xz_ plane, _before_ the transformations already
+    /// applied.
+    ///
+    /// Where the other builder methods multiply their transformation on the left, this multiplies
+    /// on the right.
+    ///
+    /// # Example
+    /// ```rust
+    /// # #[macro_use] extern crate gramit;
+    /// # use gramit::*;
+    /// use gramit::transform::{self, Transform};
+    ///
+    /// # fn main() {
+    /// let t = Transform::new().rotate_z(Angle::from_degrees(90.0));
+    ///
+    /// assert_eq!(t.pre_shear_y(1.0, 0.0).finish(), t.finish() * transform::shear_y(1.0, 0.0));
+    /// assert_eq!(t.shear_y(1.0, 0.0).finish(), transform::shear_y(1.0, 0.0) * t.finish());
+    /// # }
+    /// ```
+    #[inline(always)]
+    pub fn pre_shear_y(self, x_amount: f32, z_amount: f32) -> Transform {
+        Transform {
+            mat: self.mat * shear_y(x_amount, z_amount),
+        }
+    }
+
+    /// Shear by the given amount fixing the _xy_ plane, _before_ the transformations already
+    /// applied.
+    ///
+    /// Where the other builder methods multiply their transformation on the left, this multiplies
+    /// on the right.
+    ///
+    /// # Example
+    /// ```rust
+    /// # #[macro_use] extern crate gramit;
+    /// # use gramit::*;
+    /// use gramit::transform::{self, Transform};
+    ///
+    /// # fn main() {
+    /// let t = Transform::new().rotate_z(Angle::from_degrees(90.0));
+    ///
+    /// assert_eq!(t.pre_shear_z(1.0, 0.0).finish(), t.finish() * transform::shear_z(1.0, 0.0));
+    /// assert_eq!(t.shear_z(1.0, 0.0).finish(), transform::shear_z(1.0, 0.0) * t.finish());
+    /// # }
+    /// ```
+    #[inline(always)]
+    pub fn pre_shear_z(self, x_amount: f32, y_amount: f32) -> Transform {
+        Transform {
+            mat: self.mat * shear_z(x_amount, y_amount),
+        }
+    }
+
+    /// Rotate about the given axis by the given angle, _before_ the transformations already
+    /// applied.
+    ///
+    /// Where the other builder methods multiply their transformation on the left, this multiplies
+    /// on the right.
+    ///
+    /// # Example
+    /// ```rust
+    /// # #[macro_use] extern crate gramit;
+    /// # use gramit::*;
+    /// use gramit::transform::{self, Transform};
+    ///
+    /// # fn main() {
+    /// let t = Transform::new().rotate_z(Angle::from_degrees(90.0));
+    ///
+    /// let a = Angle::from_degrees(45.0);
+    /// let x = Vec3::x();
+    /// assert_eq!(t.pre_rotate(x, a).finish(), t.finish() * transform::rotate(x, a));
+    /// assert_eq!(t.rotate(x, a).finish(), transform::rotate(x, a) * t.finish());
+    /// # }
+    /// ```
+    #[inline(always)]
+    pub fn pre_rotate(self, axis: Vec3, angle: Angle) -> Transform {
+        Transform {
+            mat: self.mat * rotate(axis, angle),
+        }
+    }
+
+    /// Apply an arbitrary affine transformation, represented by a homogeneous matrix, _before_ the
+    /// transformations already applied.
+    ///
+    /// Where the other builder methods multiply their transformation on the left, this multiplies
+    /// on the right.
+    ///
+    /// # Example
+    /// ```rust
+    /// # #[macro_use] extern crate gramit;
+    /// # use gramit::*;
+    /// use gramit::transform::{self, Transform};
+    ///
+    /// # fn main() {
+    /// let t = Transform::new().rotate_z(Angle::from_degrees(90.0));
+    ///
+    /// let m = transform::scale(vec3!(1.0, 2.0, 3.0));
+    /// assert_eq!(t.pre_arbitrary(m).finish(), t.finish() * m);
+    /// assert_eq!(t.arbitrary(m).finish(), m * t.finish());
+    /// # }
+    /// ```
+    #[inline(always)]
+    pub fn pre_arbitrary(self, transform: Mat4) -> Transform {
+        Transform {
+            mat: self.mat * transform,
+        }
+    }
+
+    /// Apply the given builder steps only if `cond` is true.
+    ///
+    /// If `cond` is false, this `Transform` is returned unchanged.
+    ///
+    /// # Example
+    /// ```rust
+    /// # #[macro_use] extern crate gramit;
+    /// # use gramit::*;
+    /// use gramit::transform::Transform;
+    ///
+    /// # fn main() {
+    /// let sprite_transform = |mirrored: bool| {
+    ///     Transform::new()
+    ///         .apply_if(mirrored, |t| t.scale(vec3!(-1.0, 1.0, 1.0)))
+    ///         .translate(vec3!(10.0, 5.0, 0.0))
+    ///         .finish()
+    /// };
+    ///
+    /// let corner = vec3!(1.0, 1.0, 0.0).homogeneous();
+    /// assert_approx_eq!((sprite_transform(false) * corner).homogenize(), vec3!(11.0, 6.0, 0.0));
+    /// assert_approx_eq!((sprite_transform(true) * corner).homogenize(), vec3!(9.0, 6.0, 0.0));
+    /// # }
+    /// ```
+    #[inline(always)]
+    pub fn apply_if<F>(self, cond: bool, f: F) -> Transform
+    where
+        F: FnOnce(Transform) -> Transform,
+    {
+        if cond {
+            f(self)
+        } else {
+            self
+        }
+    }
+
+    /// Apply the given builder steps only if `opt` holds a value, passing that value along.
+    ///
+    /// If `opt` is `None`, this `Transform` is returned unchanged.
+    ///
+    /// # Example
+    /// ```rust
+    /// # #[macro_use] extern crate gramit;
+    /// # use gramit::*;
+    /// use gramit::transform::Transform;
+    ///
+    /// # fn main() {
+    /// let spin: Option<Angle> = Some(Angle::from_degrees(90.0));
+    /// let mat = Transform::new()
+    ///     .apply_opt(spin, |t, a| t.rotate_z(a))
+    ///     .finish();
+    ///
+    /// assert_approx_eq!((mat * Vec3::x().homogeneous()).homogenize(), Vec3::y());
+    /// # }
+    /// ```
+    #[inline(always)]
+    pub fn apply_opt<T, F>(self, opt: Option<T>, f: F) -> Transform
+    where
+        F: FnOnce(Transform, T) -> Transform,
+    {
+        match opt {
+            Some(val) => f(self, val),
+            None => self,
+        }
+    }
+
+    /// Apply the transformation accumulated so far `n` times in total.
+    ///
+    /// `repeat(0)` produces the identity transformation, and `repeat(1)` leaves this `Transform`
+    /// unchanged. See [`matrix_power`](fn.matrix_power.html).
+    #[inline(always)]
+    pub fn repeat(self, n: u32) -> Transform {
+        Transform {
+            mat: matrix_power(self.mat, n),
+        }
+    }
+
+    /// Orthonormalize the rotation part of the transformation accumulated so far.
+    ///
+    /// See the free function [`orthonormalize`](fn.orthonormalize.html).
+    #[inline(always)]
+    pub fn orthonormalize(self) -> Transform {
+        Transform {
+            mat: orthonormalize(self.mat),
+        }
+    }
+
+    /// Interpolate between this transformation and another.
+    ///
+    /// Both transformations are decomposed into translation, rotation, and scale components (see
+    /// [`decompose`]). The translations and scales are interpolated linearly, and the rotations
+    /// are interpolated spherically (see [`Quaternion::slerp`]). The result is then recomposed.
+    ///
+    /// At `t = 0` and `t = 1`, this returns exactly `self` and `other` respectively. Other values
+    /// of `t` outside of [0, 1] extrapolate.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if either transformation cannot be decomposed (e.g. because it
+    /// contains shear or is not affine). See [`try_lerp`] for a non-panicking version, and
+    /// [`lerp_matrices`] for a naive interpolation that accepts any matrices.
+    ///
+    /// [`decompose`]: #method.decompose
+    /// [`Quaternion::slerp`]: ../quaternion/struct.Quaternion.html#method.slerp
+    /// [`try_lerp`]: #method.try_lerp
+    /// [`lerp_matrices`]: fn.lerp_matrices.html
+    pub fn lerp(&self, other: &Transform, t: f32) -> Transform {
+        self.try_lerp(other, t)
+            .expect("[Transform::lerp] Transformation cannot be decomposed")
+    }
+
+    /// Interpolate between this transformation and another, or return `None` if either cannot be
+    /// decomposed.
+    ///
+    /// See [`lerp`] and the free function [`interpolate_affine`].
+    ///
+    /// [`lerp`]: #method.lerp
+    /// [`interpolate_affine`]: fn.interpolate_affine.html
+    #[inline(always)]
+    pub fn try_lerp(&self, other: &Transform, t: f32) -> Option<Transform> {
+        interpolate_affine(&self.mat, &other.mat, t).map(|mat| Transform { mat })
+    }
+
+    /// Apply the transformation represented by another `Transform` after this one.
+    ///
+    /// In matrix terms, `a.then(b)` computes `B * A`, where `A` and `B` are the matrices of `a`
+    /// and `b` respectively. This matches the convention of the other builder methods, e.g.
+    /// `a.then(Transform::new().translate(offset))` is the same as `a.translate(offset)`.
+    #[inline(always)]
+    pub fn then(self, next: Transform) -> Transform {
+        Transform {
+            mat: next.mat * self.mat,
+        }
+    }
+
+    /// Apply the transformation represented by another `Transform` before this one.
+    ///
+    /// In matrix terms, `a.before(b)` computes `A * B`, where `A` and `B` are the matrices of `a`
+    /// and `b` respectively. This is the same as `b.then(a)`.
+    #[inline(always)]
+    pub fn before(self, prev: Transform) -> Transform {
+        Transform {
+            mat: self.mat * prev.mat,
+        }
+    }
+
+    /// Consume the `Transform` and acquire the resulting homogeneous transformation matrix.
+    #[inline(always)]
+    pub fn finish(&self) -> Mat4 {
+        self.mat
+    }
+
+    /// Apply this transformation to a point.
+    ///
+    /// The point is extended to homogeneous coordinates with _w_ = 1, transformed, and then
+    /// homogenized (i.e. the perspective divide is performed).
+    #[inline(always)]
+    pub fn transform_point(&self, p: Vec3) -> Vec3 {
+        (self.mat * p.homogeneous()).homogenize()
+    }
+
+    /// Apply this transformation to a vector.
+    ///
+    /// The vector is extended to homogeneous coordinates with _w_ = 0, so that any translation in
+    /// this transformation is ignored. No perspective divide is performed.
+    #[inline(always)]
+    pub fn transform_vector(&self, v: Vec3) -> Vec3 {
+        (self.mat * v.extend(0.0)).truncate()
+    }
+
+    /// Apply this transformation in place to each of a slice of points.
+    ///
+    /// Each point is transformed as by [`transform_point`].
+    ///
+    /// [`transform_point`]: #method.transform_point
+    pub fn transform_points(&self, points: &mut [Vec3]) {
+        self.mat.transform_points3(points);
+    }
+
+    /// Decompose this transformation into translation, rotation, and scale components.
+    ///
+    /// Returns `None` if the matrix is not affine, if any axis is scaled to zero, or if the matrix
+    /// contains shear (i.e. its columns are not orthogonal, within the default floating point
+    /// tolerance).
+    ///
+    /// If the transformation includes a reflection (i.e. its determinant is negative), the
+    /// reflection is folded into the _x_ component of the scale, so that the rotation is always
+    /// proper.
+    ///
+    /// See [`Decomposed`](struct.Decomposed.html) for details.
+    pub fn decompose(&self) -> Option<Decomposed> {
+        let m = &self.mat;
+        if !m.get_row(3).approx_eq(Vec4::w()) {
+            return None;
+        }
+
+        let translation = m[3].truncate();
+        let (c1, c2, c3) = (m[0].truncate(), m[1].truncate(), m[2].truncate());
+        let mut scale = Vec3::new(c1.length(), c2.length(), c3.length());
+        if scale.x.approx_eq(0.0) || scale.y.approx_eq(0.0) || scale.z.approx_eq(0.0) {
+            return None;
+        }
+
+        let mut r1 = c1 / scale.x;
+        let r2 = c2 / scale.y;
+        let r3 = c3 / scale.z;
+        if !(r1.dot(&r2).approx_eq(0.0) && r1.dot(&r3).approx_eq(0.0) && r2.dot(&r3).approx_eq(0.0))
+        {
+            return None;
+        }
+
+        if r1.cross(&r2).dot(&r3) < 0.0 {
+            scale.x = -scale.x;
+            r1 = -r1;
+        }
+
+        let r2 = (r2 - r1 * r1.dot(&r2)).unit();
+        let r3 = r1.cross(&r2);
+
+        let rotation = Mat4::new(r1.extend(0.0), r2.extend(0.0), r3.extend(0.0), Vec4::w());
+
+        Some(Decomposed {
+            translation,
+            rotation,
+            scale,
+        })
+    }
+
+    /// Get the determinant of the resulting homogeneous transformation matrix.
+    ///
+    /// If the matrix is affine (i.e. its last row is exactly `(0, 0, 0, 1)`), this is computed as
+    /// the determinant of the upper-left 3x3 block.
+    pub fn determinant(&self) -> f32 {
+        if self.mat.get_row(3) == Vec4::w() {
+            self.mat.truncate().determinant()
+        } else {
+            self.mat.determinant()
+        }
+    }
+
+    /// Whether this transformation is invertible, i.e. whether its determinant is not
+    /// approximately zero.
+    pub fn is_invertible(&self) -> bool {
+        !self.determinant().approx_eq(0.0)
+    }
+
+    /// Whether this transformation is the identity, within the given tolerance.
+    ///
+    /// See [`Mat4::is_identity`](../mat/struct.Mat4.html#method.is_identity).
+    #[inline(always)]
+    pub fn is_identity(&self, eps: f32) -> bool {
+        self.mat.is_identity(eps)
+    }
+
+    /// Whether this transformation is affine, i.e. whether the last row of its matrix is within
+    /// the given tolerance of `(0, 0, 0, 1)`.
+    ///
+    /// See [`Mat4::is_affine`](../mat/struct.Mat4.html#method.is_affine).
+    #[inline(always)]
+    pub fn is_affine(&self, eps: f32) -> bool {
+        self.mat.is_affine(eps)
+    }
+
+    /// Whether this transformation is mirroring, i.e. whether its determinant is negative.
+    ///
+    /// Mirroring transformations reverse the winding order of triangles.
+    pub fn is_mirroring(&self) -> bool {
+        self.determinant() < 0.0
+    }
+
+    /// Check whether this transformation preserves handedness.
+    ///
+    /// See [`Mat4::is_right_handed`](../mat/struct.Mat4.html#method.is_right_handed).
+    #[inline(always)]
+    pub fn is_right_handed(&self, eps: f32) -> Option<bool> {
+        self.mat.is_right_handed(eps)
+    }
+
+    /// Whether this transformation reverses the winding order of triangles.
+    ///
+    /// See [`Mat4::winding_flipped`](../mat/struct.Mat4.html#method.winding_flipped).
+    #[inline(always)]
+    pub fn winding_flipped(&self) -> bool {
+        self.mat.winding_flipped()
+    }
+
+    /// Acquire the inverse of the resulting homogeneous transformation matrix.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the transformation is singular (e.g. it includes a zero scale).
+    /// See [`try_finish_inverse`] for a non-panicking version.
+    ///
+    /// [`try_finish_inverse`]: #method.try_finish_inverse
+    pub fn finish_inverse(&self) -> Mat4 {
+        self.try_finish_inverse()
+            .expect("[Transform::finish_inverse] Transformation is singular")
+    }
+
+    /// Acquire the inverse of the resulting homogeneous transformation matrix, or `None` if the
+    /// transformation is singular.
+    ///
+    /// If the matrix is affine (i.e. its last row is exactly `(0, 0, 0, 1)`), the inverse is
+    /// computed by inverting only the upper-left 3x3 block and correcting the translation column.
+    /// Otherwise, a general 4x4 inverse is computed.
+    ///
+    /// Singularity is decided by [`SquareMatrix::try_inverse`], so transformations that are merely
+    /// close enough to singular that their inverse is not representable (e.g. a subnormal scale
+    /// factor) also produce `None`.
+    ///
+    /// [`SquareMatrix::try_inverse`]: ../mat/trait.SquareMatrix.html#tymethod.try_inverse
+    pub fn try_finish_inverse(&self) -> Option<Mat4> {
+        let m = &self.mat;
+        if m.get_row(3) != Vec4::w() {
+            return m.try_inverse();
+        }
+
+        let linear_inv = m.truncate().try_inverse()?;
+        let offset = -(linear_inv * m[3].truncate());
+
+        Some(Mat4::new(
+            linear_inv[0].extend(0.0),
+            linear_inv[1].extend(0.0),
+            linear_inv[2].extend(0.0),
+            offset.extend(1.0),
+        ))
+    }
+
+    /// Acquire the matrix for transforming surface normals by this transformation, or `None` if
+    /// the transformation is singular.
+    ///
+    /// See the free function [`normal_matrix`](fn.normal_matrix.html).
+    #[inline(always)]
+    pub fn normal_matrix(&self) -> Option<Mat3> {
+        normal_matrix(&self.mat)
+    }
 }
 
-/// Build an orthographic normalization matrix.
+/// The translation, rotation, and scale components of an affine transformation.
 ///
-/// The resulting clipping volume is a right, axis-aligned parallelepiped. The left and right
-/// planes are at the given positions on the _x_ axis, the top and bottom planes at the given
-/// positions on the _y_ axis, and the near and far planes at _z_ = `-near` and `-far`.
+/// This is produced by [`Transform::decompose`]. The original transformation is equivalent to
+/// scaling by `scale`, then rotating by `rotation`, then translating by `translation`; see
+/// [`recompose`].
 ///
-/// This volume is mapped to the canonical viewing volume (the 2x2x2 cube centered at the origin).
-/// The _z_ axis is inverted, so that the near and far planes are mapped to normalized _z_
-/// coordinates -1 and 1 respectively (the OpenGL convention).
-pub fn ortho(left: f32, right: f32, bottom: f32, top: f32, near: f32, far: f32) -> Mat4 {
-    let mut mat = Mat4::identity();
+/// [`Transform::decompose`]: struct.Transform.html#method.decompose
+/// [`recompose`]: #method.recompose
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Decomposed {
+    /// The translation component.
+    pub translation: Vec3,
+
+    /// The rotation component, as a homogeneous rotation matrix.
+    ///
+    /// This is always a proper rotation (i.e. its determinant is 1).
+    pub rotation: Mat4,
+
+    /// The per-axis scale component.
+    ///
+    /// If the decomposed transformation included a reflection, the _x_ component of the scale is
+    /// negative.
+    pub scale: Vec3,
+}
+
+impl Decomposed {
+    /// Recompose these components into a single transformation.
+    pub fn recompose(&self) -> Transform {
+        Transform::new()
+            .scale(self.scale)
+            .arbitrary(self.rotation)
+            .translate(self.translation)
+    }
+}
+
+impl From<Mat4> for Transform {
+    /// Wrap an existing matrix in a `Transform`.
+    ///
+    /// As with [`Transform::arbitrary`], the matrix need not be affine.
+    ///
+    /// [`Transform::arbitrary`]: struct.Transform.html#method.arbitrary
+    #[inline(always)]
+    fn from(mat: Mat4) -> Transform {
+        Transform { mat }
+    }
+}
+
+impl From<Transform> for Mat4 {
+    /// Acquire the homogeneous transformation matrix of a `Transform`.
+    ///
+    /// This is equivalent to [`Transform::finish`](struct.Transform.html#method.finish).
+    #[inline(always)]
+    fn from(t: Transform) -> Mat4 {
+        t.mat
+    }
+}
+
+impl AsRef<Mat4> for Transform {
+    /// View the homogeneous transformation matrix of a `Transform`.
+    #[inline(always)]
+    fn as_ref(&self) -> &Mat4 {
+        &self.mat
+    }
+}
+
+macro_rules! transform_mul {
+    ($lhs:ty, $rhs:ty) => {
+        impl Mul<$rhs> for $lhs {
+            type Output = Transform;
+
+            /// Compose two `Transform`s.
+            ///
+            /// As with matrix multiplication, `a * b` represents the effect of applying `b`
+            /// _first_, then `a`. It is equivalent to `b.then(a)` and to `a.before(b)`.
+            fn mul(self, rhs: $rhs) -> Transform {
+                Transform {
+                    mat: self.mat * rhs.mat,
+                }
+            }
+        }
+    };
+
+    (@VEC4 $lhs:ty, $rhs:ty) => {
+        impl Mul<$rhs> for $lhs {
+            type Output = Vec4;
+
+            /// Multiply a homogeneous vector by this `Transform`'s matrix.
+            fn mul(self, rhs: $rhs) -> Vec4 {
+                self.mat * rhs
+            }
+        }
+    };
+
+    (@VEC3 $lhs:ty, $rhs:ty) => {
+        impl Mul<$rhs> for $lhs {
+            type Output = Vec3;
+
+            /// Transform a point by this `Transform`.
+            ///
+            /// This is equivalent to
+            /// [`Transform::transform_point`](struct.Transform.html#method.transform_point).
+            fn mul(self, rhs: $rhs) -> Vec3 {
+                (self.mat * rhs.homogeneous()).homogenize()
+            }
+        }
+    };
+}
+
+transform_mul!(Transform, Transform);
+transform_mul!(Transform, &Transform);
+transform_mul!(&Transform, Transform);
+transform_mul!(&Transform, &Transform);
+
+transform_mul!(@VEC4 Transform, Vec4);
+transform_mul!(@VEC4 Transform, &Vec4);
+transform_mul!(@VEC4 &Transform, Vec4);
+transform_mul!(@VEC4 &Transform, &Vec4);
+
+transform_mul!(@VEC3 Transform, Vec3);
+transform_mul!(@VEC3 Transform, &Vec3);
+transform_mul!(@VEC3 &Transform, Vec3);
+transform_mul!(@VEC3 &Transform, &Vec3);
+
+/// A single builder step recorded by a [`RecordedTransform`].
+///
+/// [`RecordedTransform`]: struct.RecordedTransform.html
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum TransformStep {
+    /// A translation by the given offset.
+    Translate(Vec3),
+
+    /// A translation along the _x_ axis by the given distance.
+    TranslateX(f32),
+
+    /// A translation along the _y_ axis by the given distance.
+    TranslateY(f32),
+
+    /// A translation along the _z_ axis by the given distance.
+    TranslateZ(f32),
+
+    /// A per-axis scale by the given factors.
+    Scale(Vec3),
+
+    /// A uniform scale by the given factor.
+    UniformScale(f32),
+
+    /// A per-axis scale by the given factors (the second field), about the given center point.
+    ScaleAbout(Vec3, Vec3),
+
+    /// A uniform scale by the given factor, about the given center point.
+    UniformScaleAbout(Vec3, f32),
+
+    /// A shear fixing the _yz_ plane by the given amounts along the _y_ and _z_ axes.
+    ShearX(f32, f32),
+
+    /// A shear fixing the _xz_ plane by the given amounts along the _x_ and _z_ axes.
+    ShearY(f32, f32),
+
+    /// A shear fixing the _xy_ plane by the given amounts along the _x_ and _y_ axes.
+    ShearZ(f32, f32),
+
+    /// A shear fixing the plane through the origin with the given normal, moving points along
+    /// the given direction by the given amount per unit of distance from the plane.
+    Shear(Vec3, Vec3, f32),
+
+    /// A rotation about the given axis by the given angle.
+    Rotate(Vec3, Angle),
+
+    /// A rotation by the given unit quaternion.
+    RotateQuat(Quaternion),
+
+    /// A rotation about the given axis through the given point (the first field), by the given
+    /// angle.
+    RotateAbout(Vec3, Vec3, Angle),
+
+    /// A rotation about the line through the given point (the first field) with the given
+    /// direction, by the given angle.
+    RotateAboutLine(Vec3, Vec3, Angle),
+
+    /// The shortest rotation taking the direction of the first vector to that of the second.
+    Align(Vec3, Vec3),
+
+    /// A rotation about the _x_ axis by the given angle.
+    RotateX(Angle),
+
+    /// A rotation about the _y_ axis by the given angle.
+    RotateY(Angle),
+
+    /// A rotation about the _z_ axis by the given angle.
+    RotateZ(Angle),
+
+    /// A rotation by the given Euler angles, in the given order.
+    RotateEuler([Angle; 3], EulerOrder),
+
+    /// A reflection across the plane through the origin with the given normal.
+    Reflect(Vec3),
+
+    /// A reflection across the plane with the given normal, through the given point.
+    ReflectPlane(Vec3, Vec3),
+
+    /// A reflection across the _yz_ plane.
+    MirrorX,
+
+    /// A reflection across the _xz_ plane.
+    MirrorY,
+
+    /// A reflection across the _xy_ plane.
+    MirrorZ,
+
+    /// A look-at view transformation, from the given eye position towards the given center
+    /// point, with the given up vector.
+    LookAt(Vec3, Vec3, Vec3),
+
+    /// An arbitrary transformation, represented by a homogeneous matrix.
+    Arbitrary(Mat4),
+
+    /// A translation by the given offset, applied before the preceding steps.
+    PreTranslate(Vec3),
+
+    /// A per-axis scale by the given factors, applied before the preceding steps.
+    PreScale(Vec3),
+
+    /// A shear fixing the _yz_ plane, applied before the preceding steps.
+    PreShearX(f32, f32),
+
+    /// A shear fixing the _xz_ plane, applied before the preceding steps.
+    PreShearY(f32, f32),
+
+    /// A shear fixing the _xy_ plane, applied before the preceding steps.
+    PreShearZ(f32, f32),
+
+    /// A rotation about the given axis by the given angle, applied before the preceding steps.
+    PreRotate(Vec3, Angle),
+
+    /// An arbitrary transformation, represented by a homogeneous matrix, applied before the
+    /// preceding steps.
+    PreArbitrary(Mat4),
+
+    /// The transformation accumulated by the preceding steps, repeated the given number of
+    /// times.
+    Repeat(u32),
+
+    /// Orthonormalization of the rotation part of the transformation accumulated so far.
+    Orthonormalize,
+}
+
+impl TransformStep {
+    /// Apply this step to the given `Transform`, via the corresponding builder method.
+    pub fn apply(self, t: Transform) -> Transform {
+        use TransformStep::*;
+        match self {
+            Translate(offset) => t.translate(offset),
+            TranslateX(distance) => t.translate_x(distance),
+            TranslateY(distance) => t.translate_y(distance),
+            TranslateZ(distance) => t.translate_z(distance),
+            Scale(factor) => t.scale(factor),
+            UniformScale(factor) => t.uniform_scale(factor),
+            ScaleAbout(center, factor) => t.scale_about(center, factor),
+            UniformScaleAbout(center, factor) => t.uniform_scale_about(center, factor),
+            ShearX(y, z) => t.shear_x(y, z),
+            ShearY(x, z) => t.shear_y(x, z),
+            ShearZ(x, y) => t.shear_z(x, y),
+            Shear(normal, direction, amount) => t.shear(normal, direction, amount),
+            Rotate(axis, angle) => t.rotate(axis, angle),
+            RotateQuat(q) => t.rotate_quat(q),
+            RotateAbout(point, axis, angle) => t.rotate_about(point, axis, angle),
+            RotateAboutLine(point, dir, angle) => t.rotate_about_line(point, dir, angle),
+            Align(from, to) => t.align(from, to),
+            RotateX(angle) => t.rotate_x(angle),
+            RotateY(angle) => t.rotate_y(angle),
+            RotateZ(angle) => t.rotate_z(angle),
+            RotateEuler(angles, order) => t.rotate_euler(angles, order),
+            Reflect(normal) => t.reflect(normal),
+            ReflectPlane(normal, point) => t.reflect_plane(normal, point),
+            MirrorX => t.mirror_x(),
+            MirrorY => t.mirror_y(),
+            MirrorZ => t.mirror_z(),
+            LookAt(eye, center, up) => t.look_at(eye, center, up),
+            Arbitrary(mat) => t.arbitrary(mat),
+            PreTranslate(offset) => t.pre_translate(offset),
+            PreScale(factor) => t.pre_scale(factor),
+            PreShearX(y, z) => t.pre_shear_x(y, z),
+            PreShearY(x, z) => t.pre_shear_y(x, z),
+            PreShearZ(x, y) => t.pre_shear_z(x, y),
+            PreRotate(axis, angle) => t.pre_rotate(axis, angle),
+            PreArbitrary(mat) => t.pre_arbitrary(mat),
+            Repeat(n) => t.repeat(n),
+            Orthonormalize => t.orthonormalize(),
+        }
+    }
+}
+
+impl Display for TransformStep {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        use TransformStep::*;
+        match self {
+            Translate(offset) => write!(f, "translate by {:?}", offset),
+            TranslateX(distance) => write!(f, "translate along x by {}", distance),
+            TranslateY(distance) => write!(f, "translate along y by {}", distance),
+            TranslateZ(distance) => write!(f, "translate along z by {}", distance),
+            Scale(factor) => write!(f, "scale by {:?}", factor),
+            UniformScale(factor) => write!(f, "scale uniformly by {}", factor),
+            ScaleAbout(center, factor) => write!(f, "scale by {:?} about {:?}", factor, center),
+            UniformScaleAbout(center, factor) => {
+                write!(f, "scale uniformly by {} about {:?}", factor, center)
+            }
+            ShearX(y, z) => write!(f, "shear x by ({}, {})", y, z),
+            ShearY(x, z) => write!(f, "shear y by ({}, {})", x, z),
+            ShearZ(x, y) => write!(f, "shear z by ({}, {})", x, y),
+            Shear(normal, direction, amount) => write!(
+                f,
+                "shear along {:?} by {}, fixing plane with normal {:?}",
+                direction, amount, normal
+            ),
+            Rotate(axis, angle) => {
+                write!(f, "rotate about {:?} by {} degrees", axis, angle.degrees())
+            }
+            RotateQuat(q) => write!(f, "rotate by {:?}", q),
+            RotateAbout(point, axis, angle) => write!(
+                f,
+                "rotate about {:?} through {:?} by {} degrees",
+                axis,
+                point,
+                angle.degrees()
+            ),
+            RotateAboutLine(point, dir, angle) => write!(
+                f,
+                "rotate about line through {:?} along {:?} by {} degrees",
+                point,
+                dir,
+                angle.degrees()
+            ),
+            Align(from, to) => write!(f, "align {:?} with {:?}", from, to),
+            RotateX(angle) => write!(f, "rotate about x by {} degrees", angle.degrees()),
+            RotateY(angle) => write!(f, "rotate about y by {} degrees", angle.degrees()),
+            RotateZ(angle) => write!(f, "rotate about z by {} degrees", angle.degrees()),
+            RotateEuler(angles, order) => write!(
+                f,
+                "rotate by Euler angles ({}, {}, {}) degrees in {:?} order",
+                angles[0].degrees(),
+                angles[1].degrees(),
+                angles[2].degrees(),
+                order
+            ),
+            Reflect(normal) => write!(f, "reflect across plane with normal {:?}", normal),
+            ReflectPlane(normal, point) => write!(
+                f,
+                "reflect across plane with normal {:?} through {:?}",
+                normal, point
+            ),
+            MirrorX => write!(f, "mirror x"),
+            MirrorY => write!(f, "mirror y"),
+            MirrorZ => write!(f, "mirror z"),
+            LookAt(eye, center, up) => {
+                write!(f, "look from {:?} at {:?} with up {:?}", eye, center, up)
+            }
+            Arbitrary(mat) => write!(f, "apply {:?}", mat),
+            PreTranslate(offset) => write!(f, "pre-translate by {:?}", offset),
+            PreScale(factor) => write!(f, "pre-scale by {:?}", factor),
+            PreShearX(y, z) => write!(f, "pre-shear x by ({}, {})", y, z),
+            PreShearY(x, z) => write!(f, "pre-shear y by ({}, {})", x, z),
+            PreShearZ(x, y) => write!(f, "pre-shear z by ({}, {})", x, y),
+            PreRotate(axis, angle) => {
+                write!(f, "pre-rotate about {:?} by {} degrees", axis, angle.degrees())
+            }
+            PreArbitrary(mat) => write!(f, "pre-apply {:?}", mat),
+            Repeat(n) => write!(f, "repeat {} times", n),
+            Orthonormalize => write!(f, "orthonormalize"),
+        }
+    }
+}
+
+/// A [`Transform`] that remembers the builder steps used to construct it.
+///
+/// This is intended as a debugging aid: when a composed transformation turns out to be wrong, the
+/// recorded steps can be inspected via [`steps`] or printed in order via the `Display`
+/// implementation. Unlike `Transform`, this type allocates, and so is not `Copy`.
+///
+/// Every builder method of `Transform` has a counterpart here, which records one step (see
+/// [`TransformStep`]). The exceptions are the conveniences that are defined in terms of another
+/// builder method, which record that method's step instead: `translate_xyz` and `scale_xyz` record
+/// `Translate` and `Scale`, `then` and `before` record `Arbitrary` and `PreArbitrary`, and
+/// `apply_if` and `apply_opt` record whatever steps their closures apply.
+///
+/// # Example
+/// ```rust
+/// # #[macro_use] extern crate gramit;
+/// # use gramit::*;
+/// use gramit::transform::{Transform, TransformStep};
+///
+/// # fn main() {
+/// let t = Transform::recorded()
+///     .scale(vec3!(2.0, 2.0, 2.0))
+///     .rotate_z(Angle::from_degrees(90.0));
+///
+/// assert_eq!(t.steps()[0], TransformStep::Scale(vec3!(2.0, 2.0, 2.0)));
+/// assert_eq!(t.replay(), t.transform());
+/// println!("{}", t);
+/// # }
+/// ```
+///
+/// [`Transform`]: struct.Transform.html
+/// [`TransformStep`]: enum.TransformStep.html
+/// [`steps`]: #method.steps
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct RecordedTransform {
+    transform: Transform,
+    steps: Vec<TransformStep>,
+}
+
+impl RecordedTransform {
+    /// Create a new `RecordedTransform`, initially representing the identity transformation with
+    /// no recorded steps.
+    #[inline(always)]
+    pub fn new() -> RecordedTransform {
+        RecordedTransform {
+            transform: Transform::new(),
+            steps: Vec::new(),
+        }
+    }
+
+    /// Apply and record the given step.
+    pub fn step(mut self, step: TransformStep) -> RecordedTransform {
+        self.transform = step.apply(self.transform);
+        self.steps.push(step);
+        self
+    }
+
+    /// Translate by the given offset.
+    #[inline(always)]
+    pub fn translate(self, offset: Vec3) -> RecordedTransform {
+        self.step(TransformStep::Translate(offset))
+    }
+
+    /// Translate by the given offsets along each axis.
+    ///
+    /// This is recorded as a single `Translate` step.
+    #[inline(always)]
+    pub fn translate_xyz(self, x: f32, y: f32, z: f32) -> RecordedTransform {
+        self.translate(Vec3::new(x, y, z))
+    }
+
+    /// Translate along the _x_ axis by the given distance.
+    #[inline(always)]
+    pub fn translate_x(self, distance: f32) -> RecordedTransform {
+        self.step(TransformStep::TranslateX(distance))
+    }
+
+    /// Translate along the _y_ axis by the given distance.
+    #[inline(always)]
+    pub fn translate_y(self, distance: f32) -> RecordedTransform {
+        self.step(TransformStep::TranslateY(distance))
+    }
+
+    /// Translate along the _z_ axis by the given distance.
+    #[inline(always)]
+    pub fn translate_z(self, distance: f32) -> RecordedTransform {
+        self.step(TransformStep::TranslateZ(distance))
+    }
+
+    /// Scale by the given factors.
+    #[inline(always)]
+    pub fn scale(self, factor: Vec3) -> RecordedTransform {
+        self.step(TransformStep::Scale(factor))
+    }
+
+    /// Scale by the given factors along each axis.
+    ///
+    /// This is recorded as a single `Scale` step.
+    #[inline(always)]
+    pub fn scale_xyz(self, x: f32, y: f32, z: f32) -> RecordedTransform {
+        self.scale(Vec3::new(x, y, z))
+    }
+
+    /// Scale uniformly by the given factor.
+    #[inline(always)]
+    pub fn uniform_scale(self, factor: f32) -> RecordedTransform {
+        self.step(TransformStep::UniformScale(factor))
+    }
+
+    /// Scale by the given factors about the given center point.
+    #[inline(always)]
+    pub fn scale_about(self, center: Vec3, factor: Vec3) -> RecordedTransform {
+        self.step(TransformStep::ScaleAbout(center, factor))
+    }
+
+    /// Scale uniformly by the given factor about the given center point.
+    #[inline(always)]
+    pub fn uniform_scale_about(self, center: Vec3, factor: f32) -> RecordedTransform {
+        self.step(TransformStep::UniformScaleAbout(center, factor))
+    }
+
+    /// Reflect across the plane through the origin with the given normal.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `normal` has zero length.
+    #[inline(always)]
+    pub fn reflect(self, normal: Vec3) -> RecordedTransform {
+        self.step(TransformStep::Reflect(normal))
+    }
+
+    /// Reflect across the plane with the given normal through the given point.
+    #[inline(always)]
+    pub fn reflect_plane(self, normal: Vec3, point_on_plane: Vec3) -> RecordedTransform {
+        self.step(TransformStep::ReflectPlane(normal, point_on_plane))
+    }
+
+    /// Reflect across the _yz_ plane.
+    #[inline(always)]
+    pub fn mirror_x(self) -> RecordedTransform {
+        self.step(TransformStep::MirrorX)
+    }
+
+    /// Reflect across the _xz_ plane.
+    #[inline(always)]
+    pub fn mirror_y(self) -> RecordedTransform {
+        self.step(TransformStep::MirrorY)
+    }
+
+    /// Reflect across the _xy_ plane.
+    #[inline(always)]
+    pub fn mirror_z(self) -> RecordedTransform {
+        self.step(TransformStep::MirrorZ)
+    }
+
+    /// Shear by the given amount, fixing the _yz_ plane.
+    #[inline(always)]
+    pub fn shear_x(self, y_amount: f32, z_amount: f32) -> RecordedTransform {
+        self.step(TransformStep::ShearX(y_amount, z_amount))
+    }
+
+    /// Shear by the given amount, fixing the _xz_ plane.
+    #[inline(always)]
+    pub fn shear_y(self, x_amount: f32, z_amount: f32) -> RecordedTransform {
+        self.step(TransformStep::ShearY(x_amount, z_amount))
+    }
+
+    /// Shear by the given amount, fixing the _xy_ plane.
+    #[inline(always)]
+    pub fn shear_z(self, x_amount: f32, y_amount: f32) -> RecordedTransform {
+        self.step(TransformStep::ShearZ(x_amount, y_amount))
+    }
+
+    /// Shear along `direction`, fixing the plane through the origin with the given normal.
+    #[inline(always)]
+    pub fn shear(self, plane_normal: Vec3, direction: Vec3, amount: f32) -> RecordedTransform {
+        self.step(TransformStep::Shear(plane_normal, direction, amount))
+    }
+
+    /// Rotate about the given axis by the given angle.
+    #[inline(always)]
+    pub fn rotate(self, axis: Vec3, angle: Angle) -> RecordedTransform {
+        self.step(TransformStep::Rotate(axis, angle))
+    }
+
+    /// Rotate by the given unit quaternion.
+    #[inline(always)]
+    pub fn rotate_quat(self, q: Quaternion) -> RecordedTransform {
+        self.step(TransformStep::RotateQuat(q))
+    }
+
+    /// Rotate about the given axis through the given point, by the given angle.
+    #[inline(always)]
+    pub fn rotate_about(self, point: Vec3, axis: Vec3, angle: Angle) -> RecordedTransform {
+        self.step(TransformStep::RotateAbout(point, axis, angle))
+    }
+
+    /// Rotate about the line through `point` with direction `dir`, by the given angle.
+    #[inline(always)]
+    pub fn rotate_about_line(self, point: Vec3, dir: Vec3, angle: Angle) -> RecordedTransform {
+        self.step(TransformStep::RotateAboutLine(point, dir, angle))
+    }
+
+    /// Rotate by the shortest rotation taking the direction of `from` to that of `to`.
+    #[inline(always)]
+    pub fn align(self, from: Vec3, to: Vec3) -> RecordedTransform {
+        self.step(TransformStep::Align(from, to))
+    }
+
+    /// Rotate about the _x_ axis by the given angle.
+    #[inline(always)]
+    pub fn rotate_x(self, angle: Angle) -> RecordedTransform {
+        self.step(TransformStep::RotateX(angle))
+    }
+
+    /// Rotate about the _y_ axis by the given angle.
+    #[inline(always)]
+    pub fn rotate_y(self, angle: Angle) -> RecordedTransform {
+        self.step(TransformStep::RotateY(angle))
+    }
+
+    /// Rotate about the _z_ axis by the given angle.
+    #[inline(always)]
+    pub fn rotate_z(self, angle: Angle) -> RecordedTransform {
+        self.step(TransformStep::RotateZ(angle))
+    }
+
+    /// Rotate by the given Euler angles, in the given order.
+    #[inline(always)]
+    pub fn rotate_euler(self, angles: [Angle; 3], order: EulerOrder) -> RecordedTransform {
+        self.step(TransformStep::RotateEuler(angles, order))
+    }
+
+    /// Apply a look-at view transformation.
+    #[inline(always)]
+    pub fn look_at(self, eye: Vec3, center: Vec3, up: Vec3) -> RecordedTransform {
+        self.step(TransformStep::LookAt(eye, center, up))
+    }
+
+    /// Apply an arbitrary affine transformation, represented by a homogenous matrix.
+    #[inline(always)]
+    pub fn arbitrary(self, transform: Mat4) -> RecordedTransform {
+        self.step(TransformStep::Arbitrary(transform))
+    }
+
+    /// Translate by the given offset, before the steps recorded so far.
+    #[inline(always)]
+    pub fn pre_translate(self, offset: Vec3) -> RecordedTransform {
+        self.step(TransformStep::PreTranslate(offset))
+    }
+
+    /// Scale by the given factors, before the steps recorded so far.
+    #[inline(always)]
+    pub fn pre_scale(self, factor: Vec3) -> RecordedTransform {
+        self.step(TransformStep::PreScale(factor))
+    }
+
+    /// Shear by the given amount, fixing the _yz_ plane, before the steps recorded so far.
+    #[inline(always)]
+    pub fn pre_shear_x(self, y_amount: f32, z_amount: f32) -> RecordedTransform {
+        self.step(TransformStep::PreShearX(y_amount, z_amount))
+    }
+
+    /// Shear by the given amount, fixing the _xz_ plane, before the steps recorded so far.
+    #[inline(always)]
+    pub fn pre_shear_y(self, x_amount: f32, z_amount: f32) -> RecordedTransform {
+        self.step(TransformStep::PreShearY(x_amount, z_amount))
+    }
+
+    /// Shear by the given amount, fixing the _xy_ plane, before the steps recorded so far.
+    #[inline(always)]
+    pub fn pre_shear_z(self, x_amount: f32, y_amount: f32) -> RecordedTransform {
+        self.step(TransformStep::PreShearZ(x_amount, y_amount))
+    }
+
+    /// Rotate about the given axis by the given angle, before the steps recorded so far.
+    #[inline(always)]
+    pub fn pre_rotate(self, axis: Vec3, angle: Angle) -> RecordedTransform {
+        self.step(TransformStep::PreRotate(axis, angle))
+    }
+
+    /// Apply an arbitrary transformation, before the steps recorded so far.
+    #[inline(always)]
+    pub fn pre_arbitrary(self, transform: Mat4) -> RecordedTransform {
+        self.step(TransformStep::PreArbitrary(transform))
+    }
+
+    /// Apply the given builder steps only if `cond` is true.
+    ///
+    /// Only the steps actually applied are recorded.
+    #[inline(always)]
+    pub fn apply_if<F>(self, cond: bool, f: F) -> RecordedTransform
+    where
+        F: FnOnce(RecordedTransform) -> RecordedTransform,
+    {
+        if cond {
+            f(self)
+        } else {
+            self
+        }
+    }
+
+    /// Apply the given builder steps only if `opt` is `Some`, passing them the contained value.
+    ///
+    /// Only the steps actually applied are recorded.
+    #[inline(always)]
+    pub fn apply_opt<T, F>(self, opt: Option<T>, f: F) -> RecordedTransform
+    where
+        F: FnOnce(RecordedTransform, T) -> RecordedTransform,
+    {
+        match opt {
+            Some(val) => f(self, val),
+            None => self,
+        }
+    }
+
+    /// Repeat the transformation recorded so far `n` times.
+    #[inline(always)]
+    pub fn repeat(self, n: u32) -> RecordedTransform {
+        self.step(TransformStep::Repeat(n))
+    }
+
+    /// Orthonormalize the rotation part of the transformation recorded so far.
+    #[inline(always)]
+    pub fn orthonormalize(self) -> RecordedTransform {
+        self.step(TransformStep::Orthonormalize)
+    }
+
+    /// Apply the transformation represented by a `Transform` after the steps recorded so far.
+    ///
+    /// This is recorded as a single `Arbitrary` step.
+    #[inline(always)]
+    pub fn then(self, next: Transform) -> RecordedTransform {
+        self.arbitrary(next.finish())
+    }
+
+    /// Apply the transformation represented by a `Transform` before the steps recorded so far.
+    ///
+    /// This is recorded as a single `PreArbitrary` step.
+    #[inline(always)]
+    pub fn before(self, prev: Transform) -> RecordedTransform {
+        self.pre_arbitrary(prev.finish())
+    }
+
+    /// Get the recorded steps, in the order they were applied.
+    #[inline(always)]
+    pub fn steps(&self) -> &[TransformStep] {
+        &self.steps
+    }
+
+    /// Get the plain `Transform` built so far.
+    #[inline(always)]
+    pub fn transform(&self) -> Transform {
+        self.transform
+    }
+
+    /// Acquire the resulting homogeneous transformation matrix.
+    #[inline(always)]
+    pub fn finish(&self) -> Mat4 {
+        self.transform.finish()
+    }
+
+    /// Rebuild the transformation from scratch by applying the recorded steps in order.
+    pub fn replay(&self) -> Transform {
+        self.steps
+            .iter()
+            .fold(Transform::new(), |t, step| step.apply(t))
+    }
+}
+
+impl From<RecordedTransform> for Transform {
+    #[inline(always)]
+    fn from(t: RecordedTransform) -> Transform {
+        t.transform
+    }
+}
+
+impl Display for RecordedTransform {
+    /// Prints each of the recorded steps in order, one per line.
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        if self.steps.is_empty() {
+            return write!(f, "identity");
+        }
+
+        for (i, step) in self.steps.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{}. {}", i + 1, step)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A variant of [`Transform`] that tracks the inverse of its transformation as it is built.
+///
+/// Each builder step of an `InvertibleTransform` has a simple closed-form inverse (translation by
+/// the negated offset, scaling by the reciprocal factors, rotation by the negated angle, and so
+/// on), which is composed alongside the forward transformation. This avoids a general matrix
+/// inversion when both matrices are needed, such as for view matrices.
+///
+/// Since an arbitrary matrix has no such closed-form inverse, [`arbitrary`] requires the caller to
+/// supply it.
+///
+/// # Example
+/// ```rust
+/// # #[macro_use] extern crate gramit;
+/// # use gramit::*;
+/// use gramit::transform::InvertibleTransform;
+///
+/// # fn main() {
+/// let (fwd, inv) = InvertibleTransform::new()
+///     .scale(vec3!(2.0, 1.0, 0.5))
+///     .rotate(Vec3::z(), Angle::from_degrees(30.0))
+///     .translate(vec3!(1.0, 2.0, 3.0))
+///     .finish_pair();
+///
+/// assert_approx_eq!(fwd * inv, Mat4::identity());
+/// # }
+/// ```
+///
+/// [`Transform`]: struct.Transform.html
+/// [`arbitrary`]: #method.arbitrary
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub struct InvertibleTransform {
+    fwd: Transform,
+    inv: Transform,
+}
+
+impl InvertibleTransform {
+    /// Create a new `InvertibleTransform`, initially representing the identity transformation.
+    #[inline(always)]
+    pub fn new() -> InvertibleTransform {
+        InvertibleTransform {
+            fwd: Transform::new(),
+            inv: Transform::new(),
+        }
+    }
+
+    #[inline(always)]
+    fn step(self, fwd: Mat4, inv: Mat4) -> InvertibleTransform {
+        InvertibleTransform {
+            fwd: self.fwd.arbitrary(fwd),
+            inv: self.inv.pre_arbitrary(inv),
+        }
+    }
+
+    /// Translate by the given offset.
+    #[inline(always)]
+    pub fn translate(self, offset: Vec3) -> InvertibleTransform {
+        self.step(translate(offset), translate(-offset))
+    }
+
+    /// Scale by the given factors.
+    ///
+    /// A zero factor will produce infinite components in the inverse matrix.
+    #[inline(always)]
+    pub fn scale(self, factor: Vec3) -> InvertibleTransform {
+        self.step(scale(factor), scale(Vec3::ones() / factor))
+    }
+
+    /// Scale uniformly by the given factor.
+    #[inline(always)]
+    pub fn uniform_scale(self, factor: f32) -> InvertibleTransform {
+        self.step(uniform_scale(factor), uniform_scale(1.0 / factor))
+    }
+
+    /// Scale by the given factors about the given center point.
+    #[inline(always)]
+    pub fn scale_about(self, center: Vec3, factor: Vec3) -> InvertibleTransform {
+        self.step(
+            scale_about(center, factor),
+            scale_about(center, Vec3::ones() / factor),
+        )
+    }
+
+    /// Reflect across the plane through the origin with the given normal.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `normal` has zero length.
+    #[inline(always)]
+    pub fn reflect(self, normal: Vec3) -> InvertibleTransform {
+        let m = reflect(normal);
+        self.step(m, m)
+    }
+
+    /// Reflect across the plane with the given normal passing through the given point.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `normal` has zero length.
+    #[inline(always)]
+    pub fn reflect_plane(self, normal: Vec3, point_on_plane: Vec3) -> InvertibleTransform {
+        let m = reflect_plane(normal, point_on_plane);
+        self.step(m, m)
+    }
+
+    /// Shear by the given amount, fixing the _yz_ plane.
+    #[inline(always)]
+    pub fn shear_x(self, y_amount: f32, z_amount: f32) -> InvertibleTransform {
+        self.step(shear_x(y_amount, z_amount), shear_x(-y_amount, -z_amount))
+    }
+
+    /// Shear by the given amount, fixing the _xz_ plane.
+    #[inline(always)]
+    pub fn shear_y(self, x_amount: f32, z_amount: f32) -> InvertibleTransform {
+        self.step(shear_y(x_amount, z_amount), shear_y(-x_amount, -z_amount))
+    }
+
+    /// Shear by the given amount, fixing the _xy_ plane.
+    #[inline(always)]
+    pub fn shear_z(self, x_amount: f32, y_amount: f32) -> InvertibleTransform {
+        self.step(shear_z(x_amount, y_amount), shear_z(-x_amount, -y_amount))
+    }
+
+    /// Rotate about the given axis by the given angle.
+    #[inline(always)]
+    pub fn rotate(self, axis: Vec3, angle: Angle) -> InvertibleTransform {
+        self.step(rotate(axis, angle), rotate(axis, -angle))
+    }
+
+    /// Rotate about the given axis through the given point, by the given angle.
+    #[inline(always)]
+    pub fn rotate_about(self, point: Vec3, axis: Vec3, angle: Angle) -> InvertibleTransform {
+        self.step(
+            rotate_about(point, axis, angle),
+            rotate_about(point, axis, -angle),
+        )
+    }
+
+    /// Rotate about the _x_ axis by the given angle.
+    #[inline(always)]
+    pub fn rotate_x(self, angle: Angle) -> InvertibleTransform {
+        self.step(rotate_x(angle), rotate_x(-angle))
+    }
+
+    /// Rotate about the _y_ axis by the given angle.
+    #[inline(always)]
+    pub fn rotate_y(self, angle: Angle) -> InvertibleTransform {
+        self.step(rotate_y(angle), rotate_y(-angle))
+    }
+
+    /// Rotate about the _z_ axis by the given angle.
+    #[inline(always)]
+    pub fn rotate_z(self, angle: Angle) -> InvertibleTransform {
+        self.step(rotate_z(angle), rotate_z(-angle))
+    }
+
+    /// Apply an arbitrary affine transformation, represented by a homogeneous matrix, along with
+    /// its inverse.
+    ///
+    /// # Usage Warning
+    /// This function _does not check_ that `inverse` is actually the inverse of `transform`. If it
+    /// is not, the inverse reported by this `InvertibleTransform` will be incorrect.
+    #[inline(always)]
+    pub fn arbitrary(self, transform: Mat4, inverse: Mat4) -> InvertibleTransform {
+        self.step(transform, inverse)
+    }
+
+    /// Acquire the forward transformation matrix.
+    #[inline(always)]
+    pub fn finish(&self) -> Mat4 {
+        self.fwd.finish()
+    }
+
+    /// Acquire the inverse transformation matrix.
+    #[inline(always)]
+    pub fn finish_inverse(&self) -> Mat4 {
+        self.inv.finish()
+    }
+
+    /// Acquire both the forward and inverse transformation matrices, in that order.
+    #[inline(always)]
+    pub fn finish_pair(&self) -> (Mat4, Mat4) {
+        (self.fwd.finish(), self.inv.finish())
+    }
+}
+
+/// Raise a matrix to the `n`th power.
+///
+/// This is computed by exponentiation by squaring, requiring _O(log n)_ matrix multiplications.
+/// The zeroth power of any matrix is the identity.
+pub fn matrix_power(m: Mat4, n: u32) -> Mat4 {
+    let mut result = Mat4::identity();
+    let mut base = m;
+    let mut n = n;
+
+    while n > 0 {
+        if n & 1 == 1 {
+            result *= base;
+        }
+        base = base * base;
+        n >>= 1;
+    }
+
+    result
+}
+
+/// Orthonormalize the upper-left 3x3 block of a homogeneous matrix, leaving its translation
+/// column untouched.
+///
+/// The first three columns are orthonormalized in order via the (modified) Gram–Schmidt process.
+/// This is useful for correcting floating point drift in a matrix that should be a rotation, but
+/// has accumulated small errors (e.g. after composing many incremental rotations). Scaling and
+/// shear are removed. The orientation is preserved, so a mirroring matrix stays mirroring.
+///
+/// If the first three columns are linearly dependent, the result will contain NaNs.
+pub fn orthonormalize(m: Mat4) -> Mat4 {
+    m.orthonormalize()
+}
+
+/// Compute the matrix for transforming surface normals by the given model matrix.
+///
+/// Normals do not transform like ordinary vectors under non-uniform scaling or shear; instead they
+/// must be transformed by the inverse transpose of the upper-left 3x3 block of the model matrix,
+/// which is what this function computes. For rigid transformations, this is just the rotation
+/// block itself. The transformed normals are generally not unit vectors, and must be renormalized.
+///
+/// Returns `None` if the 3x3 block is singular, since such a transformation collapses surfaces
+/// and has no well-defined normals. This includes blocks so close to singular that their inverse
+/// is not representable (see [`SquareMatrix::try_inverse`]).
+///
+/// [`SquareMatrix::try_inverse`]: ../mat/trait.SquareMatrix.html#tymethod.try_inverse
+///
+/// # Example
+/// ```rust
+/// # #[macro_use] extern crate gramit;
+/// # use gramit::*;
+/// use gramit::transform;
+///
+/// # fn main() {
+/// // Squash along x. The normal of the plane x = y should tilt towards x, not away from it.
+/// let model = transform::scale(vec3!(0.5, 1.0, 1.0));
+/// let normal = transform::normal_matrix(&model).unwrap() * vec3!(1.0, -1.0, 0.0);
+/// assert_approx_eq!(normal, vec3!(2.0, -1.0, 0.0));
+/// # }
+/// ```
+pub fn normal_matrix(model: &Mat4) -> Option<Mat3> {
+    Some(model.truncate().try_inverse()?.transpose())
+}
+
+/// Get the homogeneous transformation matrix of a translation by the given offset.
+pub fn translate(offset: Vec3) -> Mat4 {
+    let offset = offset.extend(1.0);
+    let mut mat = Mat4::identity();
+    mat.set_col(3, offset);
+    mat
+}
+
+/// Get the homogeneous transformation matrix of a translation by the given per-axis offsets.
+///
+/// This is equivalent to `translate(vec3!(x, y, z))`.
+pub fn translate_xyz(x: f32, y: f32, z: f32) -> Mat4 {
+    translate(Vec3::new(x, y, z))
+}
+
+/// Get the homogeneous transformation matrix of a translation along the _x_ axis.
+pub fn translate_x(distance: f32) -> Mat4 {
+    let mut mat = Mat4::identity();
+    mat[3][0] = distance;
+    mat
+}
+
+/// Get the homogeneous transformation matrix of a translation along the _y_ axis.
+pub fn translate_y(distance: f32) -> Mat4 {
+    let mut mat = Mat4::identity();
+    mat[3][1] = distance;
+    mat
+}
+
+/// Get the homogeneous transformation matrix of a translation along the _z_ axis.
+pub fn translate_z(distance: f32) -> Mat4 {
+    let mut mat = Mat4::identity();
+    mat[3][2] = distance;
+    mat
+}
+
+/// Get the homogeneous transformation matrix of a scale by the given factors.
+///
+/// Scaling is computed independently per-axis, using the corresponding factors in the given
+/// vector.
+pub fn scale(factor: Vec3) -> Mat4 {
+    Mat4::from_diagonal(factor.extend(1.0))
+}
+
+/// Get the homogeneous transformation matrix of a scale by the given per-axis factors.
+///
+/// This is equivalent to `scale(vec3!(x, y, z))`.
+pub fn scale_xyz(x: f32, y: f32, z: f32) -> Mat4 {
+    scale(Vec3::new(x, y, z))
+}
+
+/// Get the homogeneous transformation matrix of a uniform scale by the given factor.
+///
+/// This is equivalent to `scale(vec3!(factor, factor, factor))`.
+pub fn uniform_scale(factor: f32) -> Mat4 {
+    scale(Vec3::new(factor, factor, factor))
+}
+
+/// Get the homogeneous transformation matrix of a scale by the given factors about the given
+/// center point.
+///
+/// This is equivalent to `translate(center) * scale(factor) * translate(-center)`.
+pub fn scale_about(center: Vec3, factor: Vec3) -> Mat4 {
+    translate(center) * scale(factor) * translate(-center)
+}
+
+/// Get the homogeneous transformation matrix of a uniform scale by the given factor about the
+/// given center point.
+///
+/// This is equivalent to `scale_about(center, vec3!(factor, factor, factor))`.
+pub fn uniform_scale_about(center: Vec3, factor: f32) -> Mat4 {
+    scale_about(center, Vec3::new(factor, factor, factor))
+}
+
+/// Get the homogeneous transformation matrix of a reflection across the plane through the origin
+/// with the given normal.
+///
+/// The normal need not be of unit length; it is normalized before computing the Householder
+/// matrix `I - 2 n nᵀ`.
+///
+/// # Panics
+///
+/// This function panics if `normal` has zero length, since such a vector does not define a plane.
+pub fn reflect(normal: Vec3) -> Mat4 {
+    let len = normal.length();
+    assert!(len > 0.0, "[transform::reflect] Plane normal must have nonzero length");
+    let n = normal / len;
+
+    let mut mat = Mat4::identity();
+    for col in 0..3 {
+        for row in 0..3 {
+            mat[col][row] -= 2.0 * n[col] * n[row];
+        }
+    }
+    mat
+}
+
+/// Get the homogeneous transformation matrix of a reflection across the plane with the given
+/// normal passing through the given point.
+///
+/// This is equivalent to translating `point_on_plane` to the origin, reflecting with
+/// `reflect(normal)`, and translating back.
+///
+/// # Panics
+///
+/// This function panics if `normal` has zero length.
+pub fn reflect_plane(normal: Vec3, point_on_plane: Vec3) -> Mat4 {
+    translate(point_on_plane) * reflect(normal) * translate(-point_on_plane)
+}
+
+/// Get the homogeneous transformation matrix of a reflection across the plane given in Hessian
+/// normal form.
+///
+/// The plane consists of the points `x` with `normal.dot(&x) == origin_distance`. The normal is
+/// assumed to be of unit length and is used as-is. The resulting matrix has linear part
+/// `I - 2 n nᵀ` and translation `2 d n`.
+///
+/// # Example
+/// ```rust
+/// # #[macro_use] extern crate gramit;
+/// # use gramit::*;
+/// use gramit::transform;
+///
+/// # fn main() {
+/// let m = transform::reflect_about(Vec3::y(), 2.0);
+/// let p = (m * vec4!(1.0, 5.0, -1.0, 1.0)).homogenize();
+/// assert_approx_eq!(p, vec3!(1.0, -1.0, -1.0));
+/// # }
+/// ```
+pub fn reflect_about(normal: Vec3, origin_distance: f32) -> Mat4 {
+    let mut mat = Mat4::identity();
+    for col in 0..3 {
+        for row in 0..3 {
+            mat[col][row] -= 2.0 * normal[col] * normal[row];
+        }
+        mat[3][col] = 2.0 * origin_distance * normal[col];
+    }
+    mat
+}
+
+/// Get the homogeneous transformation matrix of a reflection across the plane `(a, b, c, d)`.
+///
+/// The plane consists of the points with `ax + by + cz + d == 0`. The plane is normalized
+/// internally, so `(a, b, c)` need not be of unit length. See
+/// [`reflect_about`](fn.reflect_about.html).
+///
+/// # Panics
+///
+/// This function panics if `(a, b, c)` has zero length.
+pub fn reflect_about_plane(plane: Vec4) -> Mat4 {
+    let normal = plane.truncate();
+    let len = normal.length();
+    assert!(len > 0.0, "[transform::reflect_about_plane] Plane normal must have nonzero length");
+
+    reflect_about(normal / len, -plane.w / len)
+}
+
+/// Get the homogeneous transformation matrix of a mirroring along the _x_ axis, i.e. a
+/// reflection across the _yz_ plane.
+///
+/// This is equivalent to `scale(vec3!(-1.0, 1.0, 1.0))`.
+///
+/// Mirroring flips the sign of the determinant and hence reverses the winding order of triangles.
+pub fn mirror_x() -> Mat4 {
+    let mut mat = Mat4::identity();
+    mat[0][0] = -1.0;
+    mat
+}
+
+/// Get the homogeneous transformation matrix of a mirroring along the _y_ axis, i.e. a
+/// reflection across the _xz_ plane.
+///
+/// This is equivalent to `scale(vec3!(1.0, -1.0, 1.0))`.
+///
+/// Mirroring flips the sign of the determinant and hence reverses the winding order of triangles.
+pub fn mirror_y() -> Mat4 {
+    let mut mat = Mat4::identity();
+    mat[1][1] = -1.0;
+    mat
+}
+
+/// Get the homogeneous transformation matrix of a mirroring along the _z_ axis, i.e. a
+/// reflection across the _xy_ plane.
+///
+/// This is equivalent to `scale(vec3!(1.0, 1.0, -1.0))`.
+///
+/// Mirroring flips the sign of the determinant and hence reverses the winding order of triangles.
+pub fn mirror_z() -> Mat4 {
+    let mut mat = Mat4::identity();
+    mat[2][2] = -1.0;
+    mat
+}
+
+/// Get the homogeneous transformation matrix of a shear fixing the _yz_ plane by the given amounts
+/// parallel to the _y_ and _z_ axes.
+pub fn shear_x(y_amount: f32, z_amount: f32) -> Mat4 {
+    let mut m = Mat4::identity();
+    m[0][1] = y_amount;
+    m[0][2] = z_amount;
+    m
+}
+
+/// Get the homogeneous transformation matrix of a shear fixing the _xz_ plane by the given amounts
+/// parallel to the _x_ and _z_ axes.
+pub fn shear_y(x_amount: f32, z_amount: f32) -> Mat4 {
+    let mut m = Mat4::identity();
+    m[1][0] = x_amount;
+    m[1][2] = z_amount;
+    m
+}
+
+/// Get the homogeneous transformation matrix of a shear fixing the _xy_ plane by the given amounts
+/// parallel to the _x_ and _y_ axes.
+pub fn shear_z(x_amount: f32, y_amount: f32) -> Mat4 {
+    let mut m = Mat4::identity();
+    m[2][0] = x_amount;
+    m[2][1] = y_amount;
+    m
+}
+
+/// Get the homogeneous transformation matrix of a general shear.
+///
+/// The shear fixes the plane through the origin with the given normal, and displaces every other
+/// point along `direction` proportionally to its signed distance from that plane. That is, a point
+/// `p` is mapped to `p + amount * n.dot(p) * direction`, where `n` is the normalized plane normal.
+/// In matrix terms, this is the rank-one update `I + amount * direction * nᵀ`.
+///
+/// The normal need not be of unit length, but the direction is _not_ normalized, and its length
+/// scales the shear. For the result to be a true shear, the direction should lie in the plane
+/// (i.e. be perpendicular to the normal); this is not checked.
+///
+/// # Panics
+///
+/// This function panics if `plane_normal` has zero length.
+pub fn shear(plane_normal: Vec3, direction: Vec3, amount: f32) -> Mat4 {
+    let len = plane_normal.length();
+    assert!(len > 0.0, "[transform::shear] Plane normal must have nonzero length");
+    let n = plane_normal / len;
+
+    let mut mat = Mat4::identity();
+    for col in 0..3 {
+        for row in 0..3 {
+            mat[col][row] += amount * direction[row] * n[col];
+        }
+    }
+    mat
+}
+
+/// Get the homogeneous transformation matrix of a rotation about the given axis by the given
+/// angle.
+///
+/// The axis is normalized internally, so it need not be a unit vector. The axis is not checked for
+/// zero length; a zero axis produces a matrix of NaNs. Use
+/// [`Vec3::try_unit`](../vec/struct.Vec3.html#method.try_unit) to check untrusted axes first.
+pub fn rotate(axis: Vec3, angle: Angle) -> Mat4 {
+    let (c1, c2, c3) = rotation_columns(axis, angle);
+
+    Mat4::new(c1.extend(0.0), c2.extend(0.0), c3.extend(0.0), Vec4::w())
+}
+
+/// Rotate a single vector about the given axis by the given angle.
+///
+/// This applies the Rodrigues rotation formula directly, and gives the same result as
+/// `(rotate(axis, angle) * v.homogeneous()).homogenize()` without constructing a matrix. As with
+/// [`rotate`](fn.rotate.html), the axis is normalized internally, and a zero axis produces NaNs.
+///
+/// # Example
+/// ```rust
+/// # #[macro_use] extern crate gramit;
+/// # use gramit::*;
+/// use gramit::transform;
+///
+/// # fn main() {
+/// let v = transform::rotate_vec3(Vec3::z(), Angle::from_degrees(90.0), Vec3::x());
+/// assert_approx_eq!(v, Vec3::y());
+/// # }
+/// ```
+pub fn rotate_vec3(axis: Vec3, angle: Angle, v: Vec3) -> Vec3 {
+    let k = axis.unit();
+    let (s, c) = angle.sin_cos();
+
+    v * c + k.cross(&v) * s + k * (k.dot(&v) * (1.0 - c))
+}
+
+/// Get the skew-symmetric cross-product matrix `[v]×` of a vector.
+///
+/// This is the matrix such that `cross_matrix(v) * w == v.cross(&w)` for every `w`. It appears
+/// in Rodrigues' rotation formula and when integrating angular velocities.
+///
+/// # Example
+/// ```rust
+/// # #[macro_use] extern crate gramit;
+/// # use gramit::*;
+/// use gramit::transform;
+///
+/// # fn main() {
+/// let v = vec3!(1.0, 2.0, 3.0);
+/// let w = vec3!(-4.0, 0.5, 2.0);
+/// assert_eq!(transform::cross_matrix(v) * w, v.cross(&w));
+/// # }
+/// ```
+pub fn cross_matrix(v: Vec3) -> Mat3 {
+    Mat3::new(
+        vec3!(0.0, v.z, -v.y),
+        vec3!(-v.z, 0.0, v.x),
+        vec3!(v.y, -v.x, 0.0),
+    )
+}
+
+/// Get the square `[v]ײ` of the cross-product matrix of a vector.
+///
+/// This is computed directly as `v vᵀ - |v|² I`, and maps `w` to `v.cross(&v.cross(&w))`. See
+/// [`cross_matrix`](fn.cross_matrix.html).
+pub fn cross_matrix_squared(v: Vec3) -> Mat3 {
+    let len2 = v.dot(&v);
+    let mut mat = Mat3::zeros();
+    for col in 0..3 {
+        for row in 0..3 {
+            mat[col][row] = v[row] * v[col];
+        }
+        mat[col][col] -= len2;
+    }
+    mat
+}
+
+/// Compute the columns of the 3x3 rotation matrix about the given axis by the given angle.
+pub(crate) fn rotation_columns(axis: Vec3, angle: Angle) -> (Vec3, Vec3, Vec3) {
+    let half = angle / 2.0;
+    let w = half.cos();
+    let v = half.sin() * axis.unit();
+
+    quaternion_columns(w, v)
+}
+
+/// Compute the columns of the 3x3 rotation matrix corresponding to the unit quaternion with real
+/// part `w` and vector part `v`.
+fn quaternion_columns(w: f32, v: Vec3) -> (Vec3, Vec3, Vec3) {
+    let xy = v.x * v.y;
+    let xz = v.x * v.z;
+    let xw = v.x * w;
+    let x2 = v.x * v.x;
+    let yz = v.y * v.z;
+    let yw = v.y * w;
+    let y2 = v.y * v.y;
+    let zw = v.z * w;
+    let z2 = v.z * v.z;
+
+    (
+        Vec3::new(1.0 - 2.0 * (y2 + z2), 2.0 * (xy + zw), 2.0 * (xz - yw)),
+        Vec3::new(2.0 * (xy - zw), 1.0 - 2.0 * (x2 + z2), 2.0 * (yz + xw)),
+        Vec3::new(2.0 * (xz + yw), 2.0 * (yz - xw), 1.0 - 2.0 * (x2 + y2)),
+    )
+}
+
+/// Get the homogeneous transformation matrix of the rotation represented by the given quaternion.
+///
+/// The quaternion is normalized before the matrix is computed, so non-unit quaternions produce
+/// the same rotation as their normalized counterparts. See
+/// [`Quaternion::from_axis_angle`](../quaternion/struct.Quaternion.html#method.from_axis_angle).
+pub fn rotation_from_quat(q: Quaternion) -> Mat4 {
+    let q = q.unit();
+    let (c1, c2, c3) = quaternion_columns(q.r, q.vector_part());
+
+    Mat4::new(c1.extend(0.0), c2.extend(0.0), c3.extend(0.0), Vec4::w())
+}
+
+/// Get the unit quaternion corresponding to a homogeneous rotation matrix.
+///
+/// The upper-left 3x3 block of `m` is assumed to be a proper rotation.
+fn quat_from_rotation(m: &Mat4) -> Quaternion {
+    let e = |row: usize, col: usize| m[col][row];
+    let trace = e(0, 0) + e(1, 1) + e(2, 2);
+
+    if trace > 0.0 {
+        let s = 2.0 * (trace + 1.0).sqrt();
+        Quaternion::new(
+            (e(2, 1) - e(1, 2)) / s,
+            (e(0, 2) - e(2, 0)) / s,
+            (e(1, 0) - e(0, 1)) / s,
+            s / 4.0,
+        )
+    } else if e(0, 0) > e(1, 1) && e(0, 0) > e(2, 2) {
+        let s = 2.0 * (1.0 + e(0, 0) - e(1, 1) - e(2, 2)).sqrt();
+        Quaternion::new(
+            s / 4.0,
+            (e(0, 1) + e(1, 0)) / s,
+            (e(0, 2) + e(2, 0)) / s,
+            (e(2, 1) - e(1, 2)) / s,
+        )
+    } else if e(1, 1) > e(2, 2) {
+        let s = 2.0 * (1.0 + e(1, 1) - e(0, 0) - e(2, 2)).sqrt();
+        Quaternion::new(
+            (e(0, 1) + e(1, 0)) / s,
+            s / 4.0,
+            (e(1, 2) + e(2, 1)) / s,
+            (e(0, 2) - e(2, 0)) / s,
+        )
+    } else {
+        let s = 2.0 * (1.0 + e(2, 2) - e(0, 0) - e(1, 1)).sqrt();
+        Quaternion::new(
+            (e(0, 2) + e(2, 0)) / s,
+            (e(1, 2) + e(2, 1)) / s,
+            s / 4.0,
+            (e(1, 0) - e(0, 1)) / s,
+        )
+    }
+}
+
+/// Naively interpolate between two matrices, element by element.
+///
+/// This computes `a * (1 - t) + b * t`. Note that this is generally _not_ a sensible way to
+/// interpolate between transformations that include rotation, as the intermediate matrices will
+/// not be rotations; see [`Transform::lerp`](struct.Transform.html#method.lerp) instead.
+pub fn lerp_matrices(a: &Mat4, b: &Mat4, t: f32) -> Mat4 {
+    a * (1.0 - t) + b * t
+}
+
+/// Interpolate between two affine transformation matrices, component by component.
+///
+/// Both matrices are decomposed into translation, rotation, and scale components (see
+/// [`Transform::decompose`]). The translations and scales are interpolated linearly, and the
+/// rotations are interpolated spherically (see [`Quaternion::slerp`]). The result is then
+/// recomposed. Unlike [`lerp_matrices`], this does not shrink objects midway through a rotation.
+///
+/// At `t = 0` and `t = 1`, this returns exactly `a` and `b` respectively. Other values of `t`
+/// outside of [0, 1] extrapolate.
+///
+/// Returns `None` if either matrix cannot be decomposed, e.g. because it is not affine or
+/// contains shear; no fallback interpolation is attempted. [`Transform::lerp`] is the panicking
+/// equivalent for `Transform`s.
+///
+/// [`Transform::lerp`]: struct.Transform.html#method.lerp
+/// [`Transform::decompose`]: struct.Transform.html#method.decompose
+/// [`Quaternion::slerp`]: ../quaternion/struct.Quaternion.html#method.slerp
+/// [`lerp_matrices`]: fn.lerp_matrices.html
+pub fn interpolate_affine(a: &Mat4, b: &Mat4, t: f32) -> Option<Mat4> {
+    let da = Transform::from(*a).decompose()?;
+    let db = Transform::from(*b).decompose()?;
+
+    if t == 0.0 {
+        return Some(*a);
+    } else if t == 1.0 {
+        return Some(*b);
+    }
+
+    let qa = quat_from_rotation(&da.rotation);
+    let qb = quat_from_rotation(&db.rotation);
+
+    let mat = Decomposed {
+        translation: da.translation + (db.translation - da.translation) * t,
+        rotation: rotation_from_quat(qa.slerp(&qb, t)),
+        scale: da.scale + (db.scale - da.scale) * t,
+    }
+    .recompose()
+    .finish();
+    Some(mat)
+}
+
+/// Get the homogeneous transformation matrix that scales, then rotates, then translates.
+///
+/// The result is equal to `translate(translation) * rotate(axis, angle) * scale(scale)`, but is
+/// computed directly, without the intermediate matrix products.
+pub fn trs(translation: Vec3, axis: Vec3, angle: Angle, scale: Vec3) -> Mat4 {
+    let (c1, c2, c3) = rotation_columns(axis, angle);
+
+    Mat4::new(
+        (c1 * scale.x).extend(0.0),
+        (c2 * scale.y).extend(0.0),
+        (c3 * scale.z).extend(0.0),
+        translation.extend(1.0),
+    )
+}
+
+/// Get the homogeneous transformation matrix that scales, then rotates by a quaternion, then
+/// translates.
+///
+/// The result is equal to `translate(translation) * rotation_from_quat(rotation) * scale(scale)`,
+/// but is computed directly, without the intermediate matrix products. As with
+/// [`rotation_from_quat`](fn.rotation_from_quat.html), the quaternion is normalized first.
+pub fn trs_quat(translation: Vec3, rotation: Quaternion, scale: Vec3) -> Mat4 {
+    let q = rotation.unit();
+    let (c1, c2, c3) = quaternion_columns(q.r, q.vector_part());
+
+    Mat4::new(
+        (c1 * scale.x).extend(0.0),
+        (c2 * scale.y).extend(0.0),
+        (c3 * scale.z).extend(0.0),
+        translation.extend(1.0),
+    )
+}
+
+/// Get the homogeneous transformation matrix of a rotation about the given axis through the given
+/// point, by the given angle.
+///
+/// This is equivalent to `translate(point) * rotate(axis, angle) * translate(-point)`.
+pub fn rotate_about(point: Vec3, axis: Vec3, angle: Angle) -> Mat4 {
+    translate(point) * rotate(axis, angle) * translate(-point)
+}
+
+/// Get the homogeneous transformation matrix of a rotation about the line through `point` with
+/// direction `dir`, by the given angle.
+///
+/// Every point on the line is fixed, and every other point moves on a circle about the line. This
+/// produces the same transformation as [`rotate_about`](fn.rotate_about.html), but computes the
+/// translation part directly as `point - R * point` (where `R` is the rotation about `dir`)
+/// rather than composing three matrices.
+///
+/// # Example
+/// ```rust
+/// # #[macro_use] extern crate gramit;
+/// # use gramit::*;
+/// use gramit::transform;
+///
+/// # fn main() {
+/// // A hinge along the z axis direction, through (1, 0, 0).
+/// let m = transform::rotate_about_line(Vec3::x(), Vec3::z(), Angle::from_degrees(180.0));
+///
+/// assert_approx_eq!((m * vec3!(1.0, 0.0, 5.0).homogeneous()).homogenize(), vec3!(1.0, 0.0, 5.0));
+/// assert_approx_eq!((m * Vec3::zeros().homogeneous()).homogenize(), vec3!(2.0, 0.0, 0.0));
+/// # }
+/// ```
+///
+/// # Panics
+///
+/// This function panics if `dir` has zero length.
+pub fn rotate_about_line(point: Vec3, dir: Vec3, angle: Angle) -> Mat4 {
+    assert!(
+        dir.length() != 0.0,
+        "[transform::rotate_about_line] Line direction must have nonzero length"
+    );
+
+    let (c1, c2, c3) = rotation_columns(dir, angle);
+    let offset = point - (c1 * point.x + c2 * point.y + c3 * point.z);
+
+    Mat4::new(c1.extend(0.0), c2.extend(0.0), c3.extend(0.0), offset.extend(1.0))
+}
+
+/// Get the homogeneous transformation matrix of the minimal rotation taking the direction `from`
+/// onto the direction `to`.
+///
+/// Both vectors are normalized first. The rotation is about the axis perpendicular to both of
+/// them. If they point in the same direction, the result is the identity; if they point in
+/// opposite directions, the result is a half turn about an arbitrary (but deterministic) axis
+/// perpendicular to `from`.
+///
+/// # Panics
+///
+/// This function panics if either `from` or `to` has zero length.
+pub fn rotation_between(from: Vec3, to: Vec3) -> Mat4 {
+    assert!(
+        from.length() != 0.0,
+        "[transform::rotation_between] Source direction must have nonzero length"
+    );
+    assert!(
+        to.length() != 0.0,
+        "[transform::rotation_between] Target direction must have nonzero length"
+    );
+
+    let from = from.unit();
+    let to = to.unit();
+
+    // The unnormalized quaternion (1 + cos θ, sin θ · axis) has half the angle between the two
+    // directions. It degenerates to zero when they are anti-parallel.
+    let w = 1.0 + from.dot(&to);
+    let q = if w < 1e-6 {
+        Quaternion::vector(perpendicular(from))
+    } else {
+        Quaternion::real_vector(w, from.cross(&to))
+    };
+
+    rotation_from_quat(q)
+}
+
+/// Get an arbitrary (but deterministic) nonzero vector perpendicular to the given nonzero vector.
+///
+/// The result is the cross product of `v` with the coordinate axis least aligned with it, so it
+/// is never degenerate.
+fn perpendicular(v: Vec3) -> Vec3 {
+    let (x, y, z) = (v.x.abs(), v.y.abs(), v.z.abs());
+    let basis = if x <= y && x <= z {
+        Vec3::x()
+    } else if y <= z {
+        Vec3::y()
+    } else {
+        Vec3::z()
+    };
+
+    v.cross(&basis)
+}
+
+/// Get the homogeneous model matrix of a billboard at `object_pos` that fully faces the camera.
+///
+/// The billboard's local _z_ axis points from `object_pos` towards `camera_pos`, its local _y_
+/// axis is as close to `camera_up` as possible, and its local origin is translated to
+/// `object_pos`. The result is a rigid transformation, so it can be used directly as a model
+/// matrix for a quad lying in the local _xy_ plane.
+///
+/// # Usage Warnings
+/// As with [`look_at`](fn.look_at.html), a `camera_up` parallel to the direction from the object
+/// to the camera results in a degenerate matrix. This is not checked.
+pub fn billboard_spherical(object_pos: Vec3, camera_pos: Vec3, camera_up: Vec3) -> Mat4 {
+    let z = (camera_pos - object_pos).unit();
+    let x = camera_up.cross(&z).unit();
+    let y = z.cross(&x);
+
+    Mat4::new(x.extend(0.0), y.extend(0.0), z.extend(0.0), object_pos.extend(1.0))
+}
+
+/// Get the homogeneous model matrix of a billboard at `object_pos` that faces the camera while
+/// rotating only about the given axis.
+///
+/// The billboard's local _y_ axis is exactly `axis.unit()`, and its local _z_ axis points towards
+/// the projection of `camera_pos` onto the plane through `object_pos` perpendicular to the axis.
+/// This is the usual orientation for trees, grass, and similar upright sprites. Its local origin is
+/// translated to `object_pos`.
+///
+/// If the camera lies on the axis through the object (to within floating point precision), the
+/// facing direction is undefined, and an arbitrary (but deterministic) direction perpendicular to
+/// the axis is used instead.
+///
+/// # Example
+/// ```rust
+/// # #[macro_use] extern crate gramit;
+/// # use gramit::*;
+/// use gramit::transform;
+///
+/// # fn main() {
+/// let m = transform::billboard_cylindrical(Vec3::zeros(), vec3!(5.0, 0.0, 3.0), Vec3::z());
+///
+/// assert_eq!(m[1].truncate(), Vec3::z());
+/// assert_approx_eq!(m[2].truncate(), Vec3::x());
+/// # }
+/// ```
+///
+/// # Panics
+///
+/// This function panics if `axis` has zero length.
+pub fn billboard_cylindrical(object_pos: Vec3, camera_pos: Vec3, axis: Vec3) -> Mat4 {
+    assert!(
+        axis.length() != 0.0,
+        "[transform::billboard_cylindrical] Axis must have nonzero length"
+    );
+
+    let y = axis.unit();
+    let to_camera = camera_pos - object_pos;
+    let z = to_camera - y * y.dot(&to_camera);
+    let z = if z.length() <= 1e-6 * to_camera.length() {
+        perpendicular(y)
+    } else {
+        z
+    }
+    .unit();
+    let x = y.cross(&z);
+
+    Mat4::new(x.extend(0.0), y.extend(0.0), z.extend(0.0), object_pos.extend(1.0))
+}
+
+/// Get the homogeneous transformation matrix of a rotation about the _x_ axis by the given angle.
+///
+/// This is equivalent to `rotate(Vec3::x(), angle)`, but computes the matrix directly from the
+/// sine and cosine of the angle.
+pub fn rotate_x(angle: Angle) -> Mat4 {
+    let (s, c) = angle.sin_cos();
+    let mut mat = Mat4::identity();
+    mat[1][1] = c;
+    mat[1][2] = s;
+    mat[2][1] = -s;
+    mat[2][2] = c;
+    mat
+}
+
+/// Get the homogeneous transformation matrix of a rotation about the _y_ axis by the given angle.
+///
+/// This is equivalent to `rotate(Vec3::y(), angle)`, but computes the matrix directly from the
+/// sine and cosine of the angle.
+pub fn rotate_y(angle: Angle) -> Mat4 {
+    let (s, c) = angle.sin_cos();
+    let mut mat = Mat4::identity();
+    mat[0][0] = c;
+    mat[0][2] = -s;
+    mat[2][0] = s;
+    mat[2][2] = c;
+    mat
+}
+
+/// Get the homogeneous transformation matrix of a rotation about the _z_ axis by the given angle.
+///
+/// This is equivalent to `rotate(Vec3::z(), angle)`, but computes the matrix directly from the
+/// sine and cosine of the angle.
+pub fn rotate_z(angle: Angle) -> Mat4 {
+    let (s, c) = angle.sin_cos();
+    let mut mat = Mat4::identity();
+    mat[0][0] = c;
+    mat[0][1] = s;
+    mat[1][0] = -s;
+    mat[1][1] = c;
+    mat
+}
+
+/// The order in which the three angles of an Euler-angle rotation are applied.
+///
+/// Each variant names a sequence of _intrinsic_ rotations: for `XYZ`, the rotation is about _x_,
+/// then about the rotated _y_ axis, then about the twice-rotated _z_ axis. The matrix produced
+/// by [`rotation_euler`] for the order `ABC` and angles `[a, b, c]` is `RA(a) * RB(b) * RC(c)`.
+///
+/// In terms of this crate's "applied after" builder convention (fixed axes, each call applied
+/// after those before it), intrinsic rotations are applied in the _reverse_ of the named order.
+/// The documentation of each variant states which axis that is applied first.
+///
+/// [`rotation_euler`]: fn.rotation_euler.html
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum EulerOrder {
+    /// Intrinsic _x_, _y_, _z_. The _z_ rotation is applied first, then _y_, then _x_.
+    XYZ,
+
+    /// Intrinsic _x_, _z_, _y_. The _y_ rotation is applied first, then _z_, then _x_.
+    XZY,
+
+    /// Intrinsic _y_, _x_, _z_. The _z_ rotation is applied first, then _x_, then _y_.
+    YXZ,
+
+    /// Intrinsic _y_, _z_, _x_. The _x_ rotation is applied first, then _z_, then _y_.
+    YZX,
+
+    /// Intrinsic _z_, _x_, _y_. The _y_ rotation is applied first, then _x_, then _z_.
+    ZXY,
+
+    /// Intrinsic _z_, _y_, _x_ (yaw, pitch, roll). The _x_ rotation is applied first, then _y_,
+    /// then _z_.
+    ZYX,
+}
+
+impl EulerOrder {
+    /// The axis indices, in the named (intrinsic) order.
+    fn axes(self) -> [usize; 3] {
+        match self {
+            EulerOrder::XYZ => [0, 1, 2],
+            EulerOrder::XZY => [0, 2, 1],
+            EulerOrder::YXZ => [1, 0, 2],
+            EulerOrder::YZX => [1, 2, 0],
+            EulerOrder::ZXY => [2, 0, 1],
+            EulerOrder::ZYX => [2, 1, 0],
+        }
+    }
+}
+
+fn rotate_axis(axis: usize, angle: Angle) -> Mat4 {
+    match axis {
+        0 => rotate_x(angle),
+        1 => rotate_y(angle),
+        _ => rotate_z(angle),
+    }
+}
+
+/// Get the homogeneous transformation matrix of a rotation given by Euler angles.
+///
+/// `angles[i]` is the angle about the `i`th axis named by `order`; for instance, with
+/// `EulerOrder::ZYX` the angles are `[yaw, pitch, roll]` about _z_, _y_ and _x_ respectively.
+/// See [`EulerOrder`] for how the order maps onto sequences of single-axis rotations.
+///
+/// ```
+/// # #[macro_use] extern crate gramit;
+/// # use gramit::*;
+/// use gramit::transform::{self, EulerOrder, Transform};
+/// # fn main() {
+/// let (yaw, pitch, roll) = (
+///     Angle::from_degrees(30.0),
+///     Angle::from_degrees(-45.0),
+///     Angle::from_degrees(10.0),
+/// );
+///
+/// let m = transform::rotation_euler([yaw, pitch, roll], EulerOrder::ZYX);
+/// let chain = Transform::new().rotate_x(roll).rotate_y(pitch).rotate_z(yaw).finish();
+/// assert_approx_eq!(m, chain);
+/// # }
+/// ```
+///
+/// [`EulerOrder`]: enum.EulerOrder.html
+pub fn rotation_euler(angles: [Angle; 3], order: EulerOrder) -> Mat4 {
+    let axes = order.axes();
+    rotate_axis(axes[0], angles[0])
+        * rotate_axis(axes[1], angles[1])
+        * rotate_axis(axes[2], angles[2])
+}
+
+/// Below this value of the middle angle's cosine, `euler_from_matrix` treats the rotation as
+/// gimbal-locked.
+const EULER_LOCK_THRESHOLD: f32 = 1e-6;
+
+/// Recover the Euler angles of a rotation matrix for the given order.
+///
+/// This is the inverse of [`rotation_euler`]: for a rotation matrix `m`, the returned angles `a`
+/// satisfy `rotation_euler(a, order) ≈ m`. Only the upper-left 3×3 block of `m` is inspected,
+/// and it is assumed to be a proper rotation; the result is meaningless otherwise.
+///
+/// The returned angles are canonical: the middle angle is in [-90°, 90°], and the first and last
+/// angles are in [-180°, 180°].
+///
+/// When the middle angle is ±90° (gimbal lock), the first and last rotations are about the same
+/// axis and only their combination is determined. In that case the last angle is set to zero
+/// and the whole rotation is attributed to the first angle.
+///
+/// ```
+/// # #[macro_use] extern crate gramit;
+/// # use gramit::*;
+/// use gramit::transform::{self, EulerOrder};
+/// # fn main() {
+/// let angles = [
+///     Angle::from_degrees(30.0),
+///     Angle::from_degrees(-45.0),
+///     Angle::from_degrees(10.0),
+/// ];
+///
+/// let m = transform::rotation_euler(angles, EulerOrder::ZYX);
+/// let recovered = transform::euler_from_matrix(&m, EulerOrder::ZYX);
+/// for (a, b) in angles.iter().zip(recovered.iter()) {
+///     assert_within_threshold!(a.degrees(), b.degrees(), 1e-3);
+/// }
+/// # }
+/// ```
+///
+/// [`rotation_euler`]: fn.rotation_euler.html
+pub fn euler_from_matrix(m: &Mat4, order: EulerOrder) -> [Angle; 3] {
+    let [i, j, k] = order.axes();
+    let s = if j == (i + 1) % 3 { 1.0 } else { -1.0 };
+
+    // m[col][row]; with M = Ri(a) Rj(b) Rk(c), row i of M is row i of Rj(b) Rk(c).
+    let cos_b = m[i][i].hypot(m[j][i]);
+    let b = Angle::atan2(s * m[k][i], cos_b);
+
+    if cos_b < EULER_LOCK_THRESHOLD {
+        let a = Angle::atan2(s * m[j][k], m[j][j]);
+        return [a, b, Angle::zero()];
+    }
+
+    let a = Angle::atan2(-s * m[k][j], m[k][k]);
+
+    // Solve c from row j of Ri(a)ᵀ M = Rj(b) Rk(c), which keeps it consistent with a.
+    let (sa, ca) = a.sin_cos();
+    let c = Angle::atan2(
+        s * ca * m[i][j] + sa * m[i][k],
+        ca * m[j][j] + s * sa * m[j][k],
+    );
+
+    [a, b, c]
+}
+
+/// Recover the rotation axis and angle of a rotation matrix.
+///
+/// This is the inverse of [`rotate`]: for a rotation matrix `m`, the returned `(axis, angle)`
+/// satisfies `rotate(axis, angle) ≈ m`. The axis is a unit vector and the angle is in
+/// [0°, 180°]. Only the upper-left 3×3 block of `m` is inspected; `None` is returned if that
+/// block is not orthogonal with determinant 1, within a small tolerance.
+///
+/// For the identity rotation the axis is undefined; in this case `(Vec3::x(), Angle::zero())` is
+/// returned.
+///
+/// ```
+/// # #[macro_use] extern crate gramit;
+/// # use gramit::*;
+/// use gramit::transform;
+/// # fn main() {
+/// let m = transform::rotate(vec3!(0.0, 0.0, 2.0), Angle::from_degrees(60.0));
+/// let (axis, angle) = transform::axis_angle_from_matrix(&m).unwrap();
+///
+/// assert_approx_eq!(axis, Vec3::z());
+/// assert_within_threshold!(angle.degrees(), 60.0, 1e-3);
+///
+/// assert!(transform::axis_angle_from_matrix(&transform::scale(Vec3::ones() * 2.0)).is_none());
+/// # }
+/// ```
+///
+/// [`rotate`]: fn.rotate.html
+pub fn axis_angle_from_matrix(m: &Mat4) -> Option<(Vec3, Angle)> {
+    let r = m.truncate();
+    let angle = r.rotation_angle()?;
+
+    // r[col][row]; the skew-symmetric part of R is sin(θ) [k]×.
+    let skew = vec3!(r[1][2] - r[2][1], r[2][0] - r[0][2], r[0][1] - r[1][0]);
+    let sin = skew.length() / 2.0;
+    let cos = (r.trace() - 1.0) / 2.0;
+
+    if cos >= 0.0 {
+        if sin <= f32::EPSILON {
+            return Some((Vec3::x(), Angle::zero()));
+        }
+        return Some((skew / (2.0 * sin), angle));
+    }
+
+    // Near 180° the skew part vanishes, so recover the axis from the symmetric part
+    // cos(θ) I + (1 - cos(θ)) k kᵀ, starting from its largest diagonal entry.
+    let one_minus_cos = 1.0 - cos;
+    let i = (0..3)
+        .max_by(|&a, &b| r[a][a].partial_cmp(&r[b][b]).unwrap())
+        .unwrap();
+    let mut axis = Vec3::zeros();
+    axis[i] = ((r[i][i] - cos) / one_minus_cos).max(0.0).sqrt();
+    for j in (0..3).filter(|&j| j != i) {
+        axis[j] = (r[i][j] + r[j][i]) / (2.0 * one_minus_cos * axis[i]);
+    }
+
+    if axis.dot(&skew) < 0.0 {
+        axis = -axis;
+    }
+
+    Some((axis.unit(), angle))
+}
+
+/// Below this rotation angle (in radians), the SE(3) exponential and logarithm use series
+/// expansions of their coefficients, rather than the closed forms, to avoid cancellation.
+const SE3_SERIES_THRESHOLD: f32 = 0.1;
+
+/// Tolerance used by `log_se3` when checking that a matrix is affine.
+const RIGID_CHECK_THRESHOLD: f32 = 1e-4;
+
+/// Get the rigid transformation matrix given by the exponential of a twist.
+///
+/// The twist is given by its rotational part `omega`, a rotation vector (i.e. the rotation axis
+/// scaled by the rotation angle in radians), and its translational part `v`. The result rotates
+/// about `omega` by `|omega|` radians, while translating by `V v`, where `V` accounts for the
+/// motion along the screw.
+///
+/// This is the inverse of [`log_se3`]. It uses the closed-form Rodrigues formulas, switching to
+/// series expansions of the coefficients for small angles.
+///
+/// [`log_se3`]: fn.log_se3.html
+pub fn exp_se3(omega: Vec3, v: Vec3) -> Mat4 {
+    let theta_sq = omega.dot(&omega);
+    let theta = theta_sq.sqrt();
+
+    let (a, b, c) = if theta < SE3_SERIES_THRESHOLD {
+        let theta_4 = theta_sq * theta_sq;
+        (
+            1.0 - theta_sq / 6.0 + theta_4 / 120.0,
+            0.5 - theta_sq / 24.0 + theta_4 / 720.0,
+            1.0 / 6.0 - theta_sq / 120.0 + theta_4 / 5040.0,
+        )
+    } else {
+        let (sin, cos) = theta.sin_cos();
+        (
+            sin / theta,
+            (1.0 - cos) / theta_sq,
+            (theta - sin) / (theta_sq * theta),
+        )
+    };
+
+    let k = cross_matrix(omega);
+    let k_sq = cross_matrix_squared(omega);
+    let rotation = Mat3::identity() + a * k + b * k_sq;
+    let vel = Mat3::identity() + b * k + c * k_sq;
+
+    Mat4::from_mat3_translation(rotation, vel * v)
+}
+
+/// Get the twist whose exponential is the given rigid transformation matrix.
+///
+/// Returns `(omega, v)`, the rotational and translational parts of the twist, such that
+/// `exp_se3(omega, v) ≈ m`. The rotation angle `|omega|` is in [0, π].
+///
+/// Returns `None` if `m` is not a rigid transformation, i.e. if it is not affine or its upper-left
+/// 3×3 block is not a rotation, within a small tolerance. For rotations close to 180°, the
+/// direction of `omega` is recovered from the symmetric part of the rotation (see
+/// [`axis_angle_from_matrix`]); for small rotations, series expansions are used.
+///
+/// [`axis_angle_from_matrix`]: fn.axis_angle_from_matrix.html
+pub fn log_se3(m: &Mat4) -> Option<(Vec3, Vec3)> {
+    if !m.is_affine(RIGID_CHECK_THRESHOLD) {
+        return None;
+    }
+
+    let (axis, angle) = axis_angle_from_matrix(m)?;
+    let theta = angle.radians();
+    let theta_sq = theta * theta;
+    let omega = axis * theta;
+
+    // V⁻¹ = I - K / 2 + d K², with d = (1 - (θ / 2) cot(θ / 2)) / θ².
+    let d = if theta < SE3_SERIES_THRESHOLD {
+        1.0 / 12.0 + theta_sq / 720.0 + theta_sq * theta_sq / 30240.0
+    } else {
+        let half = theta / 2.0;
+        (1.0 - half * half.cos() / half.sin()) / theta_sq
+    };
+
+    let vel_inv = Mat3::identity() - cross_matrix(omega) * 0.5 + d * cross_matrix_squared(omega);
+    Some((omega, vel_inv * m.translation()))
+}
+
+/// Interpolate between two rigid transformation matrices along a screw motion.
+///
+/// The relative motion `a⁻¹ b` is converted to a twist via [`log_se3`], scaled by `t`, and
+/// applied after `a` via [`exp_se3`]. The rotation and translation thus progress together at a
+/// constant rate, as a rigid body moving along a helix would; this is the natural interpolation
+/// for rigid motions. At `t = 0` and `t = 1`, this returns exactly `a` and `b` respectively.
+///
+/// Returns `None` if either matrix is not a rigid transformation.
+///
+/// # Example
+/// ```rust
+/// # #[macro_use] extern crate gramit;
+/// # use gramit::*;
+/// use gramit::transform;
+///
+/// # fn main() {
+/// // Turn a quarter circle about z while moving up by 2.
+/// let a = Mat4::identity();
+/// let b = transform::translate(vec3!(0.0, 0.0, 2.0))
+///     * transform::rotate(Vec3::z(), Angle::from_degrees(90.0));
+///
+/// let mid = transform::screw_lerp(&a, &b, 0.5).unwrap();
+/// let expected = transform::translate(vec3!(0.0, 0.0, 1.0))
+///     * transform::rotate(Vec3::z(), Angle::from_degrees(45.0));
+/// assert_within_threshold!(mid, expected, Mat4::ones() * 1e-5);
+/// # }
+/// ```
+///
+/// [`log_se3`]: fn.log_se3.html
+/// [`exp_se3`]: fn.exp_se3.html
+pub fn screw_lerp(a: &Mat4, b: &Mat4, t: f32) -> Option<Mat4> {
+    let ra = a.truncate();
+    if !a.is_affine(RIGID_CHECK_THRESHOLD) || !ra.is_rotation(RIGID_CHECK_THRESHOLD) {
+        return None;
+    }
+
+    let ra_inv = ra.transpose();
+    let a_inv = Mat4::from_mat3_translation(ra_inv, -(ra_inv * a.translation()));
+    let (omega, v) = log_se3(&(a_inv * b))?;
+
+    if t == 0.0 {
+        return Some(*a);
+    } else if t == 1.0 {
+        return Some(*b);
+    }
+
+    Some(a * exp_se3(omega * t, v * t))
+}
+
+/// Get the homogeneous transformation matrix projecting geometry onto a plane, as seen from a
+/// light source.
+///
+/// The plane passes through `plane_point` with normal `plane_normal`. The light is given in
+/// homogeneous coordinates: a positional light at point `p` is given as `(p, 1)`, while a
+/// directional light shining *from* direction `d` (i.e. towards `-d`) is given as `(d, 0)`.
+///
+/// Using the plane's `(n, d)` form `P`, the matrix is the classic `(P · L) I - L Pᵀ`. Points on
+/// the plane are fixed, and every other point is mapped to the intersection of the plane with the
+/// line through it and the light (or through it in the light's direction).
+///
+/// # Usage Warnings
+/// If the light lies in the plane (or a directional light is parallel to it), `P · L` is zero
+/// and the result is a singular matrix that sends every point to the light itself. This is not
+/// checked.
+///
+/// # Example
+/// ```rust
+/// # #[macro_use] extern crate gramit;
+/// # use gramit::*;
+/// use gramit::transform;
+///
+/// # fn main() {
+/// // A light directly overhead casts shadows straight down onto the ground plane z = 0.
+/// let m = transform::shadow_projection(Vec3::z(), Vec3::zeros(), vec4!(0.0, 0.0, 10.0, 1.0));
+///
+/// let shadow = (m * vec3!(0.0, 0.0, 5.0).homogeneous()).homogenize();
+/// assert_approx_eq!(shadow, Vec3::zeros());
+/// # }
+/// ```
+///
+/// # Panics
+///
+/// This function panics if `plane_normal` has zero length.
+pub fn shadow_projection(plane_normal: Vec3, plane_point: Vec3, light: Vec4) -> Mat4 {
+    assert!(
+        plane_normal.length() != 0.0,
+        "[transform::shadow_projection] Plane normal must have nonzero length"
+    );
+
+    let plane = plane_normal.extend(-plane_normal.dot(&plane_point));
+    let dot = plane.dot(&light);
+
+    let mut mat = Mat4::identity() * dot;
+    for c in 0..4 {
+        for r in 0..4 {
+            mat[c][r] -= light[r] * plane[c];
+        }
+    }
+
+    mat
+}
+
+/// Build a look-at view matrix.
+///
+/// # Parameters
+/// * `eye` The position of the camera.
+/// * `center` The point towards which the camera is facing.
+/// * `up` A vector in the upwards direction, usually `vec3!(0.0, 0.0, 1.0)`.
+///
+/// # Usage Warnings
+/// An `up` vector parallel to the camera's facing direction will result in a singular matrix that
+/// collapses all points onto the _z_ axis. This is probably not what you want. The function only
+/// checks for this condition (and for `eye == center`) in debug builds; use [`try_look_at`] to
+/// check for it unconditionally.
+///
+/// [`try_look_at`]: fn.try_look_at.html
+pub fn look_at(eye: &Vec3, center: &Vec3, up: &Vec3) -> Mat4 {
+    if cfg!(debug_assertions) {
+        if let Err(err) = check_look_to(center - eye, *up) {
+            panic!("[transform::look_at] {}", err);
+        }
+    }
+
+    view_matrix(*eye, (center - eye).unit(), *up)
+}
+
+/// Build a look-at view matrix, taking its parameters by value.
+///
+/// This is identical to [`look_at`], but takes its parameters by value like the rest of this
+/// module. `up` need not be a unit vector or perpendicular to the facing direction; only its
+/// direction within the plane spanned by it and the facing direction matters. As with `look_at`,
+/// an `up` vector parallel to the facing direction results in a singular matrix, which is only
+/// checked in debug builds; see [`try_look_at`].
+///
+/// # Example
+/// ```rust
+/// # #[macro_use] extern crate gramit;
+/// # use gramit::*;
+/// use gramit::transform;
+///
+/// # fn main() {
+/// let eye = vec3!(3.0, 4.0, 5.0);
+/// let view = transform::look_at_v(eye, Vec3::zeros(), Vec3::z());
+/// assert_eq!(view, transform::look_at(&eye, &Vec3::zeros(), &Vec3::z()));
+/// # }
+/// ```
+///
+/// [`look_at`]: fn.look_at.html
+/// [`try_look_at`]: fn.try_look_at.html
+pub fn look_at_v(eye: Vec3, center: Vec3, up: Vec3) -> Mat4 {
+    if cfg!(debug_assertions) {
+        if let Err(err) = check_look_to(center - eye, up) {
+            panic!("[transform::look_at_v] {}", err);
+        }
+    }
+
+    view_matrix(eye, (center - eye).unit(), up)
+}
+
+/// Build a view matrix from the position of the camera and the direction it is facing.
+///
+/// This produces the same matrix as [`look_at`] with `center = eye + direction`, but avoids
+/// computing the target point only to subtract `eye` from it again. The direction is normalized
+/// internally, so it need not be a unit vector.
+///
+/// # Parameters
+/// * `eye` The position of the camera.
+/// * `direction` The direction in which the camera is facing.
+/// * `up` A vector in the upwards direction, usually `vec3!(0.0, 0.0, 1.0)`.
+///
+/// # Usage Warnings
+/// As with [`look_at`], an `up` vector parallel to `direction` results in a singular matrix. This
+/// is only checked in debug builds; use [`try_look_to`] to check for it unconditionally.
+///
+/// # Example
+/// ```rust
+/// # #[macro_use] extern crate gramit;
+/// # use gramit::*;
+/// use gramit::transform;
+///
+/// # fn main() {
+/// let eye = vec3!(1.0, 2.0, 3.0);
+/// let view = transform::look_to(eye, Vec3::x(), Vec3::z());
+///
+/// // The point one unit in front of the camera lies on the negative view-space z axis.
+/// let ahead = (view * (eye + Vec3::x()).homogeneous()).homogenize();
+/// assert_approx_eq!(ahead, vec3!(0.0, 0.0, -1.0));
+/// # }
+/// ```
+///
+/// [`look_at`]: fn.look_at.html
+/// [`try_look_to`]: fn.try_look_to.html
+pub fn look_to(eye: Vec3, direction: Vec3, up: Vec3) -> Mat4 {
+    if cfg!(debug_assertions) {
+        if let Err(err) = check_look_to(direction, up) {
+            panic!("[transform::look_to] {}", err);
+        }
+    }
+
+    view_matrix(eye, direction.unit(), up)
+}
+
+/// Build a view matrix from the position of the camera and the direction it is facing, checking
+/// for degenerate inputs.
+///
+/// This is equivalent to [`look_to`](fn.look_to.html), but returns an error in the same cases as
+/// [`try_look_at`](fn.try_look_at.html). A zero `direction` is reported as
+/// `LookAtError::EyeAtCenter`.
+pub fn try_look_to(eye: Vec3, direction: Vec3, up: Vec3) -> Result<Mat4, LookAtError> {
+    check_look_to(direction, up)?;
+    Ok(view_matrix(eye, direction.unit(), up))
+}
+
+/// Build a view matrix from the camera position, unit facing direction, and up vector.
+fn view_matrix(eye: Vec3, facing: Vec3, up: Vec3) -> Mat4 {
+    let horiz = facing.cross(&up).unit();
+    let cam_up = horiz.cross(&facing);
+
+    let mut mat = Mat4::identity();
+    mat.set_row(0, horiz.extend(0.0));
+    mat.set_row(1, cam_up.extend(0.0));
+    mat.set_row(2, -facing.extend(0.0));
+
+    mat[3][0] = -eye.dot(&horiz);
+    mat[3][1] = -eye.dot(&cam_up);
+    mat[3][2] = eye.dot(&facing);
+
+    mat
+}
+
+/// Build a look-at view matrix, checking for degenerate inputs.
+///
+/// This is equivalent to [`look_at`], but returns an error instead of a singular matrix when
+/// `eye` and `center` coincide, when `up` has zero length, or when `up` is (nearly) parallel to
+/// the facing direction `center - eye`.
+///
+/// # Example
+/// ```rust
+/// # #[macro_use] extern crate gramit;
+/// # use gramit::*;
+/// use gramit::transform::{self, LookAtError};
+///
+/// # fn main() {
+/// let eye = vec3!(0.0, 0.0, 5.0);
+/// let up = Vec3::z();
+///
+/// // Looking straight down with a z-up vector is degenerate.
+/// assert_eq!(
+///     transform::try_look_at(eye, Vec3::zeros(), up),
+///     Err(LookAtError::UpParallelToFacing)
+/// );
+///
+/// let view = transform::try_look_at(eye, Vec3::zeros(), Vec3::y()).unwrap();
+/// assert_eq!(view, transform::look_at(&eye, &Vec3::zeros(), &Vec3::y()));
+/// # }
+/// ```
+///
+/// [`look_at`]: fn.look_at.html
+pub fn try_look_at(eye: Vec3, center: Vec3, up: Vec3) -> Result<Mat4, LookAtError> {
+    check_look_to(center - eye, up)?;
+    Ok(look_at(&eye, &center, &up))
+}
+
+/// An error produced when building a view matrix from degenerate inputs.
+///
+/// See [`try_look_at`](fn.try_look_at.html).
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum LookAtError {
+    /// The eye position and the target point coincide, so there is no facing direction.
+    EyeAtCenter,
+
+    /// The up vector has zero length.
+    ZeroUpVector,
+
+    /// The up vector is parallel (or nearly so) to the facing direction.
+    UpParallelToFacing,
+}
+
+impl Display for LookAtError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            LookAtError::EyeAtCenter => write!(f, "eye position coincides with target point"),
+            LookAtError::ZeroUpVector => write!(f, "up vector has zero length"),
+            LookAtError::UpParallelToFacing => {
+                write!(f, "up vector is parallel to the facing direction")
+            }
+        }
+    }
+}
+
+impl std::error::Error for LookAtError {}
+
+/// The sine of the smallest angle between the facing direction and up vector accepted by
+/// [`try_look_at`](fn.try_look_at.html).
+const LOOK_AT_PARALLEL_THRESHOLD: f32 = 1e-4;
+
+/// Check a facing direction and up vector for degeneracies that would make a view matrix
+/// singular.
+fn check_look_to(facing: Vec3, up: Vec3) -> Result<(), LookAtError> {
+    let facing = facing.try_unit().ok_or(LookAtError::EyeAtCenter)?;
+    let up = up.try_unit().ok_or(LookAtError::ZeroUpVector)?;
+
+    if facing.cross(&up).length() < LOOK_AT_PARALLEL_THRESHOLD {
+        return Err(LookAtError::UpParallelToFacing);
+    }
+
+    Ok(())
+}
+
+/// Build an orthographic normalization matrix.
+///
+/// The resulting clipping volume is a right, axis-aligned parallelepiped. The left and right
+/// planes are at the given positions on the _x_ axis, the top and bottom planes at the given
+/// positions on the _y_ axis, and the near and far planes at _z_ = `-near` and `-far`.
+///
+/// This volume is mapped to the canonical viewing volume (the 2x2x2 cube centered at the origin).
+/// The _z_ axis is inverted, so that the near and far planes are mapped to normalized _z_
+/// coordinates -1 and 1 respectively (the OpenGL convention).
+///
+/// The function does not check for a degenerate volume outside of debug builds; see
+/// [`try_ortho`](fn.try_ortho.html).
+pub fn ortho(left: f32, right: f32, bottom: f32, top: f32, near: f32, far: f32) -> Mat4 {
+    if cfg!(debug_assertions) {
+        if let Err(err) = check_ortho(left, right, bottom, top, near, far) {
+            panic!("[transform::ortho] {}", err);
+        }
+    }
+
+    let mut mat = Mat4::identity();
+
+    mat[0][0] = 2.0 / (right - left);
+    mat[1][1] = 2.0 / (top - bottom);
+    mat[2][2] = -2.0 / (far - near);
+
+    mat[3][0] = -(right + left) / (right - left);
+    mat[3][1] = -(top + bottom) / (top - bottom);
+    mat[3][2] = -(far + near) / (far - near);
+
+    mat
+}
+
+/// Build an orthographic normalization matrix for 2D rendering.
+///
+/// This is equivalent to `ortho(left, right, bottom, top, -1.0, 1.0)`, so that geometry at
+/// _z_ = 0 lies at normalized _z_ coordinate 0. See [`ortho`](fn.ortho.html).
+#[inline(always)]
+pub fn ortho2d(left: f32, right: f32, bottom: f32, top: f32) -> Mat4 {
+    ortho(left, right, bottom, top, -1.0, 1.0)
+}
+
+/// Build an orthographic normalization matrix mapping pixel coordinates to normalized device
+/// coordinates.
+///
+/// Pixel coordinates range from 0 to `width` along the _x_ axis and from 0 to `height` along the
+/// _y_ axis. If `y_down` is `true`, pixel _y_ coordinates increase downwards (the usual screen
+/// convention), so that (0, 0) maps to the top left corner of normalized device coordinates;
+/// otherwise they increase upwards, and (0, 0) maps to the bottom left corner.
+///
+/// # Example
+/// ```rust
+/// # #[macro_use] extern crate gramit;
+/// # use gramit::*;
+/// use gramit::transform;
+///
+/// # fn main() {
+/// let m = transform::ortho2d_pixel(800.0, 600.0, true);
+///
+/// let top_left = (m * vec3!(0.0, 0.0, 0.0).homogeneous()).homogenize();
+/// assert_eq!(top_left, vec3!(-1.0, 1.0, 0.0));
+/// # }
+/// ```
+pub fn ortho2d_pixel(width: f32, height: f32, y_down: bool) -> Mat4 {
+    if y_down {
+        ortho2d(0.0, width, height, 0.0)
+    } else {
+        ortho2d(0.0, width, 0.0, height)
+    }
+}
+
+/// Build an orthographic normalization matrix whose view volume is the given axis-aligned
+/// bounding box.
+///
+/// The box is given in view space by its minimum and maximum corners. Since the view looks along
+/// the negative _z_ axis, this is equivalent to
+/// `ortho(min.x, max.x, min.y, max.y, -max.z, -min.z)`, so that `max.z` (the face nearest the
+/// viewer) maps to the near plane. If the box has zero extent along any axis, it is padded
+/// slightly along that axis, so that the result is never singular.
+pub fn ortho_from_aabb(min: Vec3, max: Vec3) -> Mat4 {
+    let (left, right) = pad_extent(min.x, max.x);
+    let (bottom, top) = pad_extent(min.y, max.y);
+    let (back, front) = pad_extent(min.z, max.z);
+
+    ortho(left, right, bottom, top, -front, -back)
+}
+
+/// Build an orthographic normalization matrix that tightly encloses the given points, as seen
+/// through the given view matrix.
+///
+/// The points are transformed into view space by `light_view` (typically the view matrix of a
+/// directional light), and the projection is built from their bounding box as per
+/// [`ortho_from_aabb`]. Every one of the points then lies within the canonical view volume after
+/// transformation by `proj * light_view`.
+///
+/// Returns `None` if `points` is empty.
+///
+/// # Example
+/// ```rust
+/// # #[macro_use] extern crate gramit;
+/// # use gramit::*;
+/// use gramit::transform;
+///
+/// # fn main() {
+/// let light_view = transform::look_to(Vec3::zeros(), vec3!(1.0, 1.0, -1.0), Vec3::z());
+/// let casters = [vec3!(0.0, 0.0, 0.0), vec3!(5.0, 2.0, 1.0), vec3!(-3.0, 4.0, 0.5)];
+/// let proj = transform::fit_ortho(&casters, &light_view).unwrap();
+///
+/// for &p in casters.iter() {
+///     let ndc = (proj * light_view * p.homogeneous()).homogenize();
+///     assert!(ndc.x.abs() <= 1.0 + 1e-5 && ndc.y.abs() <= 1.0 + 1e-5);
+/// }
+/// # }
+/// ```
+///
+/// [`ortho_from_aabb`]: fn.ortho_from_aabb.html
+pub fn fit_ortho(points: &[Vec3], light_view: &Mat4) -> Option<Mat4> {
+    let mut iter = points
+        .iter()
+        .map(|p| (light_view * p.homogeneous()).homogenize());
+    let first = iter.next()?;
+
+    let (min, max) = iter.fold((first, first), |(min, max), p| {
+        (
+            vec3!(min.x.min(p.x), min.y.min(p.y), min.z.min(p.z)),
+            vec3!(max.x.max(p.x), max.y.max(p.y), max.z.max(p.z)),
+        )
+    });
+
+    Some(ortho_from_aabb(min, max))
+}
+
+/// Pad a degenerate (zero-length) interval so that it has nonzero length.
+fn pad_extent(lo: f32, hi: f32) -> (f32, f32) {
+    if lo == hi {
+        let pad = 1e-4 * lo.abs().max(1.0);
+        (lo - pad, hi + pad)
+    } else {
+        (lo, hi)
+    }
+}
+
+/// Construct a frustum normalization matrix.
+///
+/// The resulting frustum has its apex at the origin, and its near and far faces centered on and
+/// perpendicular to the negative _z_ axis at the specified distances. The near face has the given
+/// width and height. This volume is then mapped to the canonical viewing volume (the 2x2x2 cube
+/// centered at the origin).
+///
+/// The near and far planes are mapped to normalized _z_ coordinates -1 and 1 respectively (the
+/// OpenGL convention).
+///
+/// The function does not check for a degenerate frustum outside of debug builds; see
+/// [`try_frustum`](fn.try_frustum.html).
+pub fn frustum(left: f32, right: f32, bottom: f32, top: f32, near: f32, far: f32) -> Mat4 {
+    if cfg!(debug_assertions) {
+        if let Err(err) = check_frustum(left, right, bottom, top, near, far) {
+            panic!("[transform::frustum] {}", err);
+        }
+    }
+
+    let mut mat = Mat4::zeros();
+
+    mat[0][0] = (2.0 * near) / (right - left);
+    mat[1][1] = (2.0 * near) / (top - bottom);
+
+    mat[2][0] = (right + left) / (right - left);
+    mat[2][1] = (top + bottom) / (top - bottom);
+    mat[2][2] = -(far + near) / (far - near);
+    mat[2][3] = -1.0;
+
+    mat[3][2] = -(2.0 * far * near) / (far - near);
+
+    mat
+}
+
+/// Build an orthographic normalization matrix with a zero-to-one depth range.
+///
+/// This is the same as [`ortho`](fn.ortho.html), except that the near and far planes are mapped
+/// to normalized _z_ coordinates 0 and 1 respectively (the Vulkan, Direct3D, and Metal
+/// convention).
+pub fn ortho_zo(left: f32, right: f32, bottom: f32, top: f32, near: f32, far: f32) -> Mat4 {
+    if cfg!(debug_assertions) {
+        if let Err(err) = check_ortho(left, right, bottom, top, near, far) {
+            panic!("[transform::ortho_zo] {}", err);
+        }
+    }
+
+    let mut mat = Mat4::identity();
+
+    mat[0][0] = 2.0 / (right - left);
+    mat[1][1] = 2.0 / (top - bottom);
+    mat[2][2] = -1.0 / (far - near);
+
+    mat[3][0] = -(right + left) / (right - left);
+    mat[3][1] = -(top + bottom) / (top - bottom);
+    mat[3][2] = -near / (far - near);
+
+    mat
+}
+
+/// Construct a frustum normalization matrix with a zero-to-one depth range.
+///
+/// This is the same as [`frustum`](fn.frustum.html), except that the near and far planes are
+/// mapped to normalized _z_ coordinates 0 and 1 respectively (the Vulkan, Direct3D, and Metal
+/// convention).
+pub fn frustum_zo(left: f32, right: f32, bottom: f32, top: f32, near: f32, far: f32) -> Mat4 {
+    if cfg!(debug_assertions) {
+        if let Err(err) = check_frustum(left, right, bottom, top, near, far) {
+            panic!("[transform::frustum_zo] {}", err);
+        }
+    }
+
+    let mut mat = Mat4::zeros();
+
+    mat[0][0] = (2.0 * near) / (right - left);
+    mat[1][1] = (2.0 * near) / (top - bottom);
+
+    mat[2][0] = (right + left) / (right - left);
+    mat[2][1] = (top + bottom) / (top - bottom);
+    mat[2][2] = -far / (far - near);
+    mat[2][3] = -1.0;
+
+    mat[3][2] = -(far * near) / (far - near);
+
+    mat
+}
+
+/// A matrix remapping OpenGL clip space depth (-1 to 1) to the zero-to-one depth range.
+///
+/// Left-multiplying a projection that uses the OpenGL depth range by this matrix produces the
+/// corresponding projection for the zero-to-one depth range used by Vulkan, Direct3D, Metal, and
+/// wgpu. See [`convert_depth_range`](fn.convert_depth_range.html).
+pub const GL_TO_ZO_DEPTH: Mat4 = Mat4::new(
+    Vec4::new(1.0, 0.0, 0.0, 0.0),
+    Vec4::new(0.0, 1.0, 0.0, 0.0),
+    Vec4::new(0.0, 0.0, 0.5, 0.0),
+    Vec4::new(0.0, 0.0, 0.5, 1.0),
+);
+
+/// Convert a projection matrix from the OpenGL depth range (-1 to 1) to the zero-to-one depth
+/// range.
+///
+/// This is equivalent to `GL_TO_ZO_DEPTH * proj`. It is useful for projection matrices from other
+/// sources; projections built by this module have `_zo` variants that compute the converted matrix
+/// directly.
+///
+/// # Example
+/// ```rust
+/// # #[macro_use] extern crate gramit;
+/// # use gramit::*;
+/// use gramit::transform;
+///
+/// # fn main() {
+/// let fovy = Angle::from_degrees(60.0);
+/// let proj = transform::convert_depth_range(transform::perspective(fovy, 1.5, 0.1, 100.0));
+///
+/// let expected = transform::perspective_zo(fovy, 1.5, 0.1, 100.0);
+/// assert_within_threshold!(proj, expected, Mat4::ones() * 1e-5);
+/// # }
+/// ```
+#[inline(always)]
+pub fn convert_depth_range(proj: Mat4) -> Mat4 {
+    GL_TO_ZO_DEPTH * proj
+}
+
+/// Build an orthographic normalization matrix, checking for a degenerate volume.
+///
+/// This is equivalent to [`ortho`](fn.ortho.html), but returns an error instead of dividing by
+/// zero when the volume has zero width, height, or depth.
+pub fn try_ortho(
+    left: f32,
+    right: f32,
+    bottom: f32,
+    top: f32,
+    near: f32,
+    far: f32,
+) -> Result<Mat4, ProjectionError> {
+    check_ortho(left, right, bottom, top, near, far)?;
+    Ok(ortho(left, right, bottom, top, near, far))
+}
+
+/// Construct a frustum normalization matrix, checking for a degenerate frustum.
+///
+/// This is equivalent to [`frustum`](fn.frustum.html), but returns an error instead of dividing
+/// by zero when the frustum has zero width, height, or depth, or when the near plane distance is
+/// not positive.
+///
+/// # Example
+/// ```rust
+/// # extern crate gramit;
+/// use gramit::transform::{self, ProjectionError};
+///
+/// assert_eq!(
+///     transform::try_frustum(-1.0, 1.0, -1.0, 1.0, 0.0, 10.0),
+///     Err(ProjectionError::NonPositiveNear)
+/// );
+/// assert!(transform::try_frustum(-1.0, 1.0, -1.0, 1.0, 0.1, 10.0).is_ok());
+/// ```
+pub fn try_frustum(
+    left: f32,
+    right: f32,
+    bottom: f32,
+    top: f32,
+    near: f32,
+    far: f32,
+) -> Result<Mat4, ProjectionError> {
+    check_frustum(left, right, bottom, top, near, far)?;
+    Ok(frustum(left, right, bottom, top, near, far))
+}
+
+/// An error produced when building a projection matrix from a degenerate view volume.
+///
+/// See [`try_ortho`](fn.try_ortho.html) and [`try_frustum`](fn.try_frustum.html).
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ProjectionError {
+    /// The left and right planes coincide.
+    ZeroWidth,
+
+    /// The bottom and top planes coincide.
+    ZeroHeight,
+
+    /// The near and far planes coincide.
+    ZeroDepth,
+
+    /// The near plane distance of a perspective projection is zero or negative.
+    NonPositiveNear,
+}
+
+impl Display for ProjectionError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            ProjectionError::ZeroWidth => write!(f, "view volume has zero width"),
+            ProjectionError::ZeroHeight => write!(f, "view volume has zero height"),
+            ProjectionError::ZeroDepth => write!(f, "view volume has zero depth"),
+            ProjectionError::NonPositiveNear => write!(f, "near plane distance is not positive"),
+        }
+    }
+}
+
+impl std::error::Error for ProjectionError {}
+
+/// Check the bounds of an orthographic view volume for degeneracies.
+fn check_ortho(
+    left: f32,
+    right: f32,
+    bottom: f32,
+    top: f32,
+    near: f32,
+    far: f32,
+) -> Result<(), ProjectionError> {
+    if left == right {
+        Err(ProjectionError::ZeroWidth)
+    } else if bottom == top {
+        Err(ProjectionError::ZeroHeight)
+    } else if near == far {
+        Err(ProjectionError::ZeroDepth)
+    } else {
+        Ok(())
+    }
+}
+
+/// Check the bounds of a perspective frustum for degeneracies.
+fn check_frustum(
+    left: f32,
+    right: f32,
+    bottom: f32,
+    top: f32,
+    near: f32,
+    far: f32,
+) -> Result<(), ProjectionError> {
+    check_ortho(left, right, bottom, top, near, far)?;
+
+    if near <= 0.0 {
+        Err(ProjectionError::NonPositiveNear)
+    } else {
+        Ok(())
+    }
+}
+
+/// Build a perspective normalization matrix.
+///
+/// The resulting view volume is a symmetric frustum centered on the _z_ axis with its apex at the
+/// origin, near plane at _z_ = `-near`, and far plane at _z_ = `-far`. `fovy` gives the vertical
+/// field of view, with the horizontal field of view determined from this by `aspect_xy`, which is
+/// the ratio width / height of the viewport dimensions.
+///
+/// This volume is mapped to the canonical viewing volume (the 2x2x2 cube centered at the origin).
+/// The near and far planes are mapped to normalized _z_ coordinates -1 and 1 respectively.
+pub fn perspective(fovy: Angle, aspect_xy: f32, near: f32, far: f32) -> Mat4 {
+    let tan_half_fov = (fovy / 2.0).tan();
+    let mut mat = Mat4::zeros();
+
+    mat[0][0] = 1.0 / (aspect_xy * tan_half_fov);
+    mat[1][1] = 1.0 / tan_half_fov;
+    mat[2][2] = -(far + near) / (far - near);
+
+    mat[2][3] = -1.0;
+    mat[3][2] = -(2.0 * far * near) / (far - near);
+
+    mat
+}
+
+/// Build a perspective normalization matrix with a zero-to-one depth range.
+///
+/// This is the same as [`perspective`](fn.perspective.html), except that the near and far planes
+/// are mapped to normalized _z_ coordinates 0 and 1 respectively (the Vulkan, Direct3D, and Metal
+/// convention).
+///
+/// # Example
+/// ```rust
+/// # #[macro_use] extern crate gramit;
+/// # use gramit::*;
+/// use gramit::transform;
+///
+/// # fn main() {
+/// let proj = transform::perspective_zo(Angle::from_degrees(60.0), 1.5, 0.1, 100.0);
+///
+/// let near = (proj * vec3!(0.0, 0.0, -0.1).homogeneous()).homogenize();
+/// let far = (proj * vec3!(0.0, 0.0, -100.0).homogeneous()).homogenize();
+/// assert_approx_eq!(near.z, 0.0);
+/// assert_approx_eq!(far.z, 1.0);
+/// # }
+/// ```
+pub fn perspective_zo(fovy: Angle, aspect_xy: f32, near: f32, far: f32) -> Mat4 {
+    let tan_half_fov = (fovy / 2.0).tan();
+    let mut mat = Mat4::zeros();
+
+    mat[0][0] = 1.0 / (aspect_xy * tan_half_fov);
+    mat[1][1] = 1.0 / tan_half_fov;
+    mat[2][2] = -far / (far - near);
+
+    mat[2][3] = -1.0;
+    mat[3][2] = -(far * near) / (far - near);
+
+    mat
+}
+
+/// Build a reversed-Z orthographic normalization matrix with a zero-to-one depth range.
+///
+/// This is the same as [`ortho_zo`](fn.ortho_zo.html), except that the near and far planes are
+/// mapped to normalized _z_ coordinates 1 and 0 respectively. It is intended for use with a
+/// "greater" depth test.
+pub fn ortho_reversed_zo(
+    left: f32,
+    right: f32,
+    bottom: f32,
+    top: f32,
+    near: f32,
+    far: f32,
+) -> Mat4 {
+    if cfg!(debug_assertions) {
+        if let Err(err) = check_ortho(left, right, bottom, top, near, far) {
+            panic!("[transform::ortho_reversed_zo] {}", err);
+        }
+    }
+
+    let mut mat = Mat4::identity();
+
+    mat[0][0] = 2.0 / (right - left);
+    mat[1][1] = 2.0 / (top - bottom);
+    mat[2][2] = 1.0 / (far - near);
+
+    mat[3][0] = -(right + left) / (right - left);
+    mat[3][1] = -(top + bottom) / (top - bottom);
+    mat[3][2] = far / (far - near);
+
+    mat
+}
+
+/// Build a reversed-Z perspective normalization matrix with a zero-to-one depth range.
+///
+/// This is the same as [`perspective_zo`](fn.perspective_zo.html), except that the near and far
+/// planes are mapped to normalized _z_ coordinates 1 and 0 respectively. Combined with a floating
+/// point depth buffer and a "greater" depth test, this distributes depth precision much more
+/// evenly over the view volume.
+///
+/// # Example
+/// ```rust
+/// # #[macro_use] extern crate gramit;
+/// # use gramit::*;
+/// use gramit::transform;
+///
+/// # fn main() {
+/// let proj = transform::perspective_reversed_zo(Angle::from_degrees(60.0), 1.5, 0.1, 100.0);
+///
+/// let near = (proj * vec3!(0.0, 0.0, -0.1).homogeneous()).homogenize();
+/// let far = (proj * vec3!(0.0, 0.0, -100.0).homogeneous()).homogenize();
+/// assert_approx_eq!(near.z, 1.0);
+/// assert_approx_eq!(far.z, 0.0);
+/// # }
+/// ```
+pub fn perspective_reversed_zo(fovy: Angle, aspect_xy: f32, near: f32, far: f32) -> Mat4 {
+    let tan_half_fov = (fovy / 2.0).tan();
+    let mut mat = Mat4::zeros();
+
+    mat[0][0] = 1.0 / (aspect_xy * tan_half_fov);
+    mat[1][1] = 1.0 / tan_half_fov;
+    mat[2][2] = near / (far - near);
+
+    mat[2][3] = -1.0;
+    mat[3][2] = (far * near) / (far - near);
+
+    mat
+}
+
+/// Build a (generally asymmetric) perspective normalization matrix from the tangents of the
+/// angles between the view direction and each edge of the field of view.
+///
+/// This is the form in which VR runtimes such as OpenXR report per-eye projections. The angles are
+/// signed, measured from the negative _z_ axis: `tan_left` and `tan_down` are typically negative,
+/// while `tan_right` and `tan_up` are typically positive. The resulting matrix is the same as
+/// [`frustum`] with `left = near * tan_left`, `right = near * tan_right`,
+/// `bottom = near * tan_down`, and `top = near * tan_up`, and uses the same (OpenGL) depth range.
+///
+/// # Example
+/// ```rust
+/// # #[macro_use] extern crate gramit;
+/// # use gramit::*;
+/// use gramit::transform;
+///
+/// # fn main() {
+/// // A slightly asymmetric field of view, wider towards the nose (right, for a left eye).
+/// let proj = transform::perspective_from_tangents(-1.0, 1.2, 1.1, -1.1, 0.1, 100.0);
+///
+/// let right_edge = (proj * vec3!(1.2, 0.0, -1.0).homogeneous()).homogenize();
+/// assert_approx_eq!(right_edge.x, 1.0);
+/// # }
+/// ```
+///
+/// [`frustum`]: fn.frustum.html
+pub fn perspective_from_tangents(
+    tan_left: f32,
+    tan_right: f32,
+    tan_up: f32,
+    tan_down: f32,
+    near: f32,
+    far: f32,
+) -> Mat4 {
+    if cfg!(debug_assertions) {
+        if let Err(err) = check_frustum(tan_left, tan_right, tan_down, tan_up, near, far) {
+            panic!("[transform::perspective_from_tangents] {}", err);
+        }
+    }
+
+    let mut mat = Mat4::zeros();
+
+    mat[0][0] = 2.0 / (tan_right - tan_left);
+    mat[1][1] = 2.0 / (tan_up - tan_down);
+
+    mat[2][0] = (tan_right + tan_left) / (tan_right - tan_left);
+    mat[2][1] = (tan_up + tan_down) / (tan_up - tan_down);
+    mat[2][2] = -(far + near) / (far - near);
+    mat[2][3] = -1.0;
+
+    mat[3][2] = -(2.0 * far * near) / (far - near);
+
+    mat
+}
+
+/// Replace the near clipping plane of a projection matrix with an arbitrary plane.
+///
+/// This implements Eric Lengyel's oblique near-plane clipping technique, as used for planar
+/// reflections and portals. `clip_plane` is given in view space as the coefficients `(a, b, c, d)`
+/// of the plane `ax + by + cz + d = 0`, with the visible half-space on its positive side. The
+/// camera (the view-space origin) must be on the negative side, i.e. `d` must be negative.
+///
+/// `proj` must use the OpenGL depth range, e.g. a matrix produced by [`perspective`],
+/// [`frustum`], or [`ortho`]; see [`oblique_near_plane_zo`] for the zero-to-one depth range.
+/// Points on `clip_plane` are mapped to normalized _z_ coordinate -1. The far plane is tilted as
+/// a consequence, so that it passes through the far corner of the original view volume opposite
+/// the new near plane; this sacrifices some depth precision, increasingly so as the clip plane
+/// becomes more oblique.
+///
+/// # Panics
+///
+/// This function panics if `proj` is singular, or so close to singular that its inverse is not
+/// representable. Every matrix produced by this module's projection functions is invertible.
+///
+/// [`perspective`]: fn.perspective.html
+/// [`frustum`]: fn.frustum.html
+/// [`ortho`]: fn.ortho.html
+/// [`oblique_near_plane_zo`]: fn.oblique_near_plane_zo.html
+pub fn oblique_near_plane(proj: Mat4, clip_plane: Vec4) -> Mat4 {
+    let (scale, q_w) = oblique_plane_scale(&proj, clip_plane, "oblique_near_plane");
+
+    let mut mat = proj;
+    mat.set_row(2, clip_plane * (2.0 * q_w) / scale - proj.get_row(3));
+    mat
+}
+
+/// Replace the near clipping plane of a zero-to-one depth range projection matrix with an
+/// arbitrary plane.
+///
+/// This is the same as [`oblique_near_plane`](fn.oblique_near_plane.html), except that `proj`
+/// must use the zero-to-one depth range (e.g. a matrix produced by
+/// [`perspective_zo`](fn.perspective_zo.html)), and points on `clip_plane` are mapped to
+/// normalized _z_ coordinate 0.
+///
+/// # Panics
+///
+/// This function panics if `proj` is singular, as for `oblique_near_plane`.
+pub fn oblique_near_plane_zo(proj: Mat4, clip_plane: Vec4) -> Mat4 {
+    let (scale, q_w) = oblique_plane_scale(&proj, clip_plane, "oblique_near_plane_zo");
+
+    let mut mat = proj;
+    mat.set_row(2, clip_plane * q_w / scale);
+    mat
+}
+
+/// Compute the dot product of a clip plane with the view-space point mapped to the far corner of
+/// the canonical view volume opposite that plane, along with that point's clip-space _w_.
+fn oblique_plane_scale(proj: &Mat4, clip_plane: Vec4, caller: &str) -> (f32, f32) {
+    let corner = vec4!(clip_plane.x.signum(), clip_plane.y.signum(), 1.0, 1.0);
+    let inv = match proj.try_inverse() {
+        Some(inv) => inv,
+        None => panic!("[transform::{}] Projection matrix is singular", caller),
+    };
+    let q = inv * corner;
+
+    (clip_plane.dot(&q), proj.get_row(3).dot(&q))
+}
+
+/// Build a perspective normalization matrix from separate horizontal and vertical fields of view.
+///
+/// This is equivalent to [`perspective`] with the given `fovy` and
+/// `aspect_xy = tan(fovx / 2) / tan(fovy / 2)`. Note that the aspect ratio relates the _tangents_
+/// of the half-angles, not the angles themselves; `fovx / fovy` is only an approximation of it for
+/// narrow fields of view.
+///
+/// [`perspective`]: fn.perspective.html
+pub fn perspective_fov(fovx: Angle, fovy: Angle, near: f32, far: f32) -> Mat4 {
+    let mut mat = Mat4::zeros();
+
+    mat[0][0] = 1.0 / (fovx / 2.0).tan();
+    mat[1][1] = 1.0 / (fovy / 2.0).tan();
+    mat[2][2] = -(far + near) / (far - near);
+
+    mat[2][3] = -1.0;
+    mat[3][2] = -(2.0 * far * near) / (far - near);
+
+    mat
+}
+
+/// Build a perspective normalization matrix from a horizontal field of view and an aspect ratio.
+///
+/// This is the counterpart of [`perspective`] for a horizontal rather than vertical field of view.
+/// The vertical field of view is determined by `tan(fovy / 2) = tan(fovx / 2) / aspect_xy`, where
+/// `aspect_xy` is the ratio width / height of the viewport dimensions.
+///
+/// # Example
+/// ```rust
+/// # #[macro_use] extern crate gramit;
+/// # use gramit::*;
+/// use gramit::transform;
+///
+/// # fn main() {
+/// let fovx = Angle::from_degrees(90.0);
+/// let proj = transform::perspective_fovx(fovx, 2.0, 0.1, 100.0);
+///
+/// // The right edge of the view is at 45 degrees from the view direction.
+/// let edge = (proj * vec3!(1.0, 0.0, -1.0).homogeneous()).homogenize();
+/// assert_approx_eq!(edge.x, 1.0);
+/// # }
+/// ```
+///
+/// [`perspective`]: fn.perspective.html
+pub fn perspective_fovx(fovx: Angle, aspect_xy: f32, near: f32, far: f32) -> Mat4 {
+    let tan_half_fov = (fovx / 2.0).tan();
+    let mut mat = Mat4::zeros();
+
+    mat[0][0] = 1.0 / tan_half_fov;
+    mat[1][1] = aspect_xy / tan_half_fov;
+    mat[2][2] = -(far + near) / (far - near);
+
+    mat[2][3] = -1.0;
+    mat[3][2] = -(2.0 * far * near) / (far - near);
+
+    mat
+}
+
+/// Build a perspective normalization matrix from physical camera parameters.
+///
+/// The camera is specified by its lens focal length and the dimensions of its sensor (or film
+/// back), all in the same units (typically millimeters). This is equivalent to [`perspective`]
+/// with vertical field of view `fovy = 2 * atan(sensor_height_mm / (2 * focal_length_mm))` and
+/// aspect ratio `sensor_width_mm / sensor_height_mm`. `near` and `far` are given in scene units,
+/// as usual.
+///
+/// # Example
+/// ```rust
+/// # #[macro_use] extern crate gramit;
+/// # use gramit::*;
+/// use gramit::transform;
+///
+/// # fn main() {
+/// // A 50mm lens on a full-frame (36mm x 24mm) sensor.
+/// let proj = transform::perspective_from_focal(50.0, 36.0, 24.0, 0.1, 100.0);
+///
+/// let fovy = Angle::from_radians(2.0 * (24.0_f32 / 100.0).atan());
+/// let expected = transform::perspective(fovy, 1.5, 0.1, 100.0);
+/// assert_within_threshold!(proj, expected, Mat4::ones() * 1e-5);
+/// # }
+/// ```
+///
+/// [`perspective`]: fn.perspective.html
+pub fn perspective_from_focal(
+    focal_length_mm: f32,
+    sensor_width_mm: f32,
+    sensor_height_mm: f32,
+    near: f32,
+    far: f32,
+) -> Mat4 {
+    perspective_from_focal_shifted(
+        focal_length_mm,
+        sensor_width_mm,
+        sensor_height_mm,
+        0.0,
+        0.0,
+        near,
+        far,
+    )
+}
+
+/// Build a perspective normalization matrix from physical camera parameters, with a lens shift.
+///
+/// This is the same as [`perspective_from_focal`], except that the lens is shifted relative to
+/// the center of the sensor by `shift_x_mm` horizontally and `shift_y_mm` vertically (in the same
+/// units as the focal length), producing an asymmetric frustum. Positive shifts move the field of
+/// view right and up, respectively. The result is the [`frustum`] whose near plane edges are at
+/// `near * (±sensor_width_mm / 2 + shift_x_mm) / focal_length_mm` and
+/// `near * (±sensor_height_mm / 2 + shift_y_mm) / focal_length_mm`.
+///
+/// [`perspective_from_focal`]: fn.perspective_from_focal.html
+/// [`frustum`]: fn.frustum.html
+pub fn perspective_from_focal_shifted(
+    focal_length_mm: f32,
+    sensor_width_mm: f32,
+    sensor_height_mm: f32,
+    shift_x_mm: f32,
+    shift_y_mm: f32,
+    near: f32,
+    far: f32,
+) -> Mat4 {
+    let scale = near / focal_length_mm;
+    let (half_w, half_h) = (sensor_width_mm / 2.0, sensor_height_mm / 2.0);
+
+    frustum(
+        scale * (shift_x_mm - half_w),
+        scale * (shift_x_mm + half_w),
+        scale * (shift_y_mm - half_h),
+        scale * (shift_y_mm + half_h),
+        near,
+        far,
+    )
+}
+
+/// Build a perspective normalization matrix with the far plane at infinity.
+///
+/// This is the limit of [`perspective`](fn.perspective.html) as `far` goes to infinity. The view
+/// volume is a symmetric, semi-infinite frustum with its near plane at _z_ = `-near`, so distant
+/// geometry is never clipped. The near plane is mapped to normalized _z_ coordinate -1, and points
+/// approach normalized _z_ coordinate 1 as their distance goes to infinity (the OpenGL
+/// convention).
+pub fn perspective_infinite(fovy: Angle, aspect_xy: f32, near: f32) -> Mat4 {
+    let tan_half_fov = (fovy / 2.0).tan();
+    let mut mat = Mat4::zeros();
+
+    mat[0][0] = 1.0 / (aspect_xy * tan_half_fov);
+    mat[1][1] = 1.0 / tan_half_fov;
+    mat[2][2] = -1.0;
+
+    mat[2][3] = -1.0;
+    mat[3][2] = -2.0 * near;
+
+    mat
+}
+
+/// Build a reversed-Z perspective normalization matrix with the far plane at infinity and a
+/// zero-to-one depth range.
+///
+/// This is the limit of [`perspective_reversed_zo`](fn.perspective_reversed_zo.html) as `far`
+/// goes to infinity. The near plane is mapped to normalized _z_ coordinate 1, and points approach
+/// normalized _z_ coordinate 0 as their distance goes to infinity. It is intended for use with a
+/// floating point depth buffer and a "greater" depth test.
+///
+/// # Example
+/// ```rust
+/// # #[macro_use] extern crate gramit;
+/// # use gramit::*;
+/// use gramit::transform;
+///
+/// # fn main() {
+/// let proj = transform::perspective_infinite_reversed_zo(Angle::from_degrees(60.0), 1.5, 0.1);
+///
+/// let near = (proj * vec3!(0.0, 0.0, -0.1).homogeneous()).homogenize();
+/// let distant = (proj * vec3!(0.0, 0.0, -1.0e6).homogeneous()).homogenize();
+/// assert_approx_eq!(near.z, 1.0);
+/// assert!(distant.z > 0.0 && distant.z < 1.0e-6);
+/// # }
+/// ```
+pub fn perspective_infinite_reversed_zo(fovy: Angle, aspect_xy: f32, near: f32) -> Mat4 {
+    let tan_half_fov = (fovy / 2.0).tan();
+    let mut mat = Mat4::zeros();
+
+    mat[0][0] = 1.0 / (aspect_xy * tan_half_fov);
+    mat[1][1] = 1.0 / tan_half_fov;
+
+    mat[2][3] = -1.0;
+    mat[3][2] = near;
+
+    mat
+}
+
+/// Build a viewport transformation matrix, mapping normalized device coordinates to window
+/// coordinates.
+///
+/// Normalized _x_ and _y_ coordinates in the range -1 to 1 are mapped to the rectangle with its
+/// lower left corner at (`x`, `y`) and the given width and height, with the _y_ axis pointing up
+/// (the OpenGL window convention). Normalized _z_ coordinates in the range -1 to 1 (the OpenGL
+/// depth convention, as produced by e.g. [`perspective`]) are mapped to the range `near_depth` to
+/// `far_depth`.
+///
+/// Composing this with a projection, view, and model matrix (as in
+/// `viewport * proj * view * model`) produces a matrix that maps points to window coordinates
+/// after homogenization.
+///
+/// # Example
+/// ```rust
+/// # #[macro_use] extern crate gramit;
+/// # use gramit::*;
+/// use gramit::transform;
+///
+/// # fn main() {
+/// let vp = transform::viewport(0.0, 0.0, 800.0, 600.0, 0.0, 1.0);
+///
+/// let center = (vp * vec3!(0.0, 0.0, 0.0).homogeneous()).homogenize();
+/// assert_approx_eq!(center, vec3!(400.0, 300.0, 0.5));
+/// # }
+/// ```
+///
+/// [`perspective`]: fn.perspective.html
+pub fn viewport(x: f32, y: f32, width: f32, height: f32, near_depth: f32, far_depth: f32) -> Mat4 {
+    let mut mat = Mat4::identity();
+
+    mat[0][0] = width / 2.0;
+    mat[1][1] = height / 2.0;
+    mat[2][2] = (far_depth - near_depth) / 2.0;
+
+    mat[3][0] = x + width / 2.0;
+    mat[3][1] = y + height / 2.0;
+    mat[3][2] = (far_depth + near_depth) / 2.0;
+
+    mat
+}
+
+/// Build a viewport transformation matrix with the default depth range 0 to 1.
+///
+/// This is equivalent to `viewport(x, y, width, height, 0.0, 1.0)`. See
+/// [`viewport`](fn.viewport.html).
+#[inline(always)]
+pub fn viewport_rect(x: f32, y: f32, width: f32, height: f32) -> Mat4 {
+    viewport(x, y, width, height, 0.0, 1.0)
+}
+
+/// Map a point from world (or object) space to window coordinates.
+///
+/// This mirrors GLU's `gluProject`. The point is transformed by `modelview` and then `proj`,
+/// homogenized, and mapped to the viewport given as `(x, y, width, height)` as per
+/// [`viewport_rect`]. The resulting _z_ coordinate is the window depth in the range 0 to 1.
+///
+/// Returns `None` if the transformed point has a homogeneous _w_ coordinate of (approximately)
+/// zero, i.e. if it lies in the plane of the camera.
+///
+/// [`viewport_rect`]: fn.viewport_rect.html
+pub fn project(
+    world: Vec3,
+    modelview: &Mat4,
+    proj: &Mat4,
+    viewport: (f32, f32, f32, f32),
+) -> Option<Vec3> {
+    let clip = proj * (modelview * world.homogeneous());
+    if clip.w.approx_eq(0.0) {
+        return None;
+    }
+
+    let (x, y, width, height) = viewport;
+    Some((viewport_rect(x, y, width, height) * clip).homogenize())
+}
+
+/// Map a point from window coordinates back to world (or object) space.
+///
+/// This mirrors GLU's `gluUnProject`, and is the inverse of [`project`]. `window` gives the window
+/// coordinates and depth (in the range 0 to 1) of the point.
+///
+/// Returns `None` if `proj * modelview` is singular (including when its inverse is not
+/// representable; see [`SquareMatrix::try_inverse`]), or if the unprojected point has a
+/// homogeneous _w_ coordinate of (approximately) zero.
+///
+/// # Example
+/// ```rust
+/// # #[macro_use] extern crate gramit;
+/// # use gramit::*;
+/// use gramit::transform;
+///
+/// # fn main() {
+/// let view = transform::look_at(&vec3!(0.0, -5.0, 0.0), &Vec3::zeros(), &Vec3::z());
+/// let proj = transform::perspective(Angle::from_degrees(60.0), 4.0 / 3.0, 0.1, 100.0);
+/// let vp = (0.0, 0.0, 800.0, 600.0);
+///
+/// let point = vec3!(1.0, 2.0, -0.5);
+/// let window = transform::project(point, &view, &proj, vp).unwrap();
+/// let back = transform::unproject(window, &view, &proj, vp).unwrap();
+/// assert_within_threshold!(back, point, Vec3::ones() * 1e-4);
+/// # }
+/// ```
+///
+/// [`project`]: fn.project.html
+/// [`SquareMatrix::try_inverse`]: ../mat/trait.SquareMatrix.html#tymethod.try_inverse
+pub fn unproject(
+    window: Vec3,
+    modelview: &Mat4,
+    proj: &Mat4,
+    viewport: (f32, f32, f32, f32),
+) -> Option<Vec3> {
+    let inv = (proj * modelview).try_inverse()?;
+
+    let (x, y, width, height) = viewport;
+    let ndc = vec3!(
+        2.0 * (window.x - x) / width - 1.0,
+        2.0 * (window.y - y) / height - 1.0,
+        2.0 * window.z - 1.0
+    );
+
+    let world = inv * ndc.homogeneous();
+    if world.w.approx_eq(0.0) {
+        return None;
+    }
+
+    Some(world.homogenize())
+}
+
+/// Construct the world-space ray through the given screen position, for mouse picking.
+///
+/// `screen` is given in window coordinates, relative to the viewport `(x, y, width, height)` as
+/// per [`project`]. The ray's origin is the point on the near plane under the cursor, and its
+/// direction is the unit vector from there to the corresponding point on the far plane. For
+/// perspective projections, all such rays pass through the camera position; for orthographic
+/// projections, they are all parallel to the view direction, with varying origins.
+///
+/// Returns `None` under the same conditions as [`unproject`].
+///
+/// # Example
+/// ```rust
+/// # #[macro_use] extern crate gramit;
+/// # use gramit::*;
+/// use gramit::transform;
+///
+/// # fn main() {
+/// let view = transform::look_at(&vec3!(0.0, -5.0, 0.0), &Vec3::zeros(), &Vec3::z());
+/// let proj = transform::perspective(Angle::from_degrees(60.0), 4.0 / 3.0, 0.1, 100.0);
+/// let vp = (0.0, 0.0, 800.0, 600.0);
+///
+/// let (_origin, dir) = transform::pick_ray(vec2!(400.0, 300.0), vp, &view, &proj).unwrap();
+/// assert_within_threshold!(dir, Vec3::y(), Vec3::ones() * 1e-4);
+/// # }
+/// ```
+///
+/// [`project`]: fn.project.html
+/// [`unproject`]: fn.unproject.html
+pub fn pick_ray(
+    screen: Vec2,
+    viewport: (f32, f32, f32, f32),
+    view: &Mat4,
+    proj: &Mat4,
+) -> Option<(Vec3, Vec3)> {
+    let near = unproject(screen.extend(0.0), view, proj, viewport)?;
+    let far = unproject(screen.extend(1.0), view, proj, viewport)?;
+
+    Some((near, (far - near).unit()))
+}
+
+/// Extract the six clipping planes of the view frustum from a combined projection-view matrix.
+///
+/// This uses the Gribb–Hartmann method. `proj_view` must use the OpenGL depth range (see
+/// [`frustum_planes_zo`] for the zero-to-one depth range), and may be a perspective or
+/// orthographic projection. The planes are returned in the order left, right, bottom, top, near,
+/// far, each as coefficients `(a, b, c, d)` of the plane `ax + by + cz + d = 0` in the space
+/// that `proj_view` maps from (typically world space).
+///
+/// Each plane is normalized so that `(a, b, c)` is a unit vector pointing into the frustum. The
+/// signed distance of a point `p` from a plane is thus `plane.dot(&p.homogeneous())`, which is
+/// positive for points on the inside.
+///
+/// # Example
+/// ```rust
+/// # #[macro_use] extern crate gramit;
+/// # use gramit::*;
+/// use gramit::transform;
+///
+/// # fn main() {
+/// let proj = transform::perspective(Angle::from_degrees(90.0), 1.0, 1.0, 10.0);
+/// let planes = transform::frustum_planes(&proj);
+///
+/// let inside = vec3!(0.0, 0.0, -5.0).homogeneous();
+/// assert!(planes.iter().all(|plane| plane.dot(&inside) > 0.0));
+/// # }
+/// ```
+///
+/// [`frustum_planes_zo`]: fn.frustum_planes_zo.html
+pub fn frustum_planes(proj_view: &Mat4) -> [Vec4; 6] {
+    let r2 = proj_view.get_row(2);
+    let r3 = proj_view.get_row(3);
+
+    frustum_planes_with_depth(proj_view, r3 + r2, r3 - r2)
+}
+
+/// Extract the six clipping planes of the view frustum from a combined projection-view matrix
+/// with a zero-to-one depth range.
+///
+/// This is the same as [`frustum_planes`](fn.frustum_planes.html), except that `proj_view` must
+/// use the zero-to-one depth range, e.g. a projection produced by
+/// [`perspective_zo`](fn.perspective_zo.html).
+pub fn frustum_planes_zo(proj_view: &Mat4) -> [Vec4; 6] {
+    let r2 = proj_view.get_row(2);
+    let r3 = proj_view.get_row(3);
+
+    frustum_planes_with_depth(proj_view, r2, r3 - r2)
+}
+
+/// Build the normalized frustum planes from the given (unnormalized) near and far planes.
+fn frustum_planes_with_depth(proj_view: &Mat4, near: Vec4, far: Vec4) -> [Vec4; 6] {
+    let r0 = proj_view.get_row(0);
+    let r1 = proj_view.get_row(1);
+    let r3 = proj_view.get_row(3);
+    let normalize = |plane: Vec4| plane / plane.truncate().length();
+
+    [
+        normalize(r3 + r0),
+        normalize(r3 - r0),
+        normalize(r3 + r1),
+        normalize(r3 - r1),
+        normalize(near),
+        normalize(far),
+    ]
+}
+
+/// Convert a normalized depth value produced by [`perspective`] back to the distance from the
+/// camera.
+///
+/// `depth` is the normalized _z_ coordinate in the OpenGL range -1 to 1, and `near` and `far` are
+/// the distances to the near and far planes, as passed to `perspective`. The result is the
+/// (positive) distance along the view direction, i.e. the negated view-space _z_ coordinate.
+/// Note that a value read back from a depth buffer is typically in the range 0 to 1 instead, and
+/// must be remapped to -1 to 1 first (or passed through [`linearize_depth_zo`] if the projection
+/// used that range in the first place).
+///
+/// # Example
+/// ```rust
+/// # #[macro_use] extern crate gramit;
+/// # use gramit::*;
+/// use gramit::transform;
+///
+/// # fn main() {
+/// let proj = transform::perspective(Angle::from_degrees(60.0), 1.0, 0.1, 100.0);
+/// let ndc = (proj * vec3!(0.0, 0.0, -25.0).homogeneous()).homogenize();
+/// assert_within_threshold!(transform::linearize_depth(ndc.z, 0.1, 100.0), 25.0, 1e-3);
+/// # }
+/// ```
+///
+/// [`perspective`]: fn.perspective.html
+/// [`linearize_depth_zo`]: fn.linearize_depth_zo.html
+pub fn linearize_depth(depth: f32, near: f32, far: f32) -> f32 {
+    (2.0 * far * near) / ((far + near) - depth * (far - near))
+}
+
+/// Convert a normalized depth value produced by [`perspective_zo`] back to the distance from the
+/// camera.
+///
+/// This is the same as [`linearize_depth`](fn.linearize_depth.html), except that `depth` is in
+/// the range 0 to 1.
+///
+/// [`perspective_zo`]: fn.perspective_zo.html
+pub fn linearize_depth_zo(depth: f32, near: f32, far: f32) -> f32 {
+    (far * near) / (far - depth * (far - near))
+}
+
+/// Convert a distance from the camera to the normalized depth value produced by [`perspective`].
+///
+/// This is the inverse of [`linearize_depth`](fn.linearize_depth.html).
+///
+/// [`perspective`]: fn.perspective.html
+pub fn delinearize_depth(distance: f32, near: f32, far: f32) -> f32 {
+    ((far + near) - (2.0 * far * near) / distance) / (far - near)
+}
+
+/// Convert a distance from the camera to the normalized depth value produced by
+/// [`perspective_zo`].
+///
+/// This is the inverse of [`linearize_depth_zo`](fn.linearize_depth_zo.html).
+///
+/// [`perspective_zo`]: fn.perspective_zo.html
+pub fn delinearize_depth_zo(distance: f32, near: f32, far: f32) -> f32 {
+    far * (distance - near) / ((far - near) * distance)
+}
+
+/// Recover the view-space _z_ coordinate of a point from its normalized _z_ coordinate, using the
+/// depth coefficients of the given perspective projection matrix.
+///
+/// This works for any projection whose third and fourth rows have the form `(0, 0, A, B)` and
+/// `(0, 0, -1, 0)`, which includes every perspective projection in this module regardless of its
+/// depth convention (including reversed-Z and infinite-far projections). The result is negative
+/// for points in front of the camera.
+pub fn eye_z_from_ndc(ndc_z: f32, proj: &Mat4) -> f32 {
+    -proj[3][2] / (ndc_z + proj[2][2])
+}
+
+/// The parameters of a symmetric perspective projection, as passed to [`perspective`].
+///
+/// See [`decompose_perspective`](fn.decompose_perspective.html).
+///
+/// [`perspective`]: fn.perspective.html
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct PerspectiveParams {
+    /// The vertical field of view.
+    pub fovy: Angle,
+
+    /// The ratio width / height of the viewport dimensions.
+    pub aspect_xy: f32,
+
+    /// The distance to the near plane.
+    pub near: f32,
+
+    /// The distance to the far plane.
+    pub far: f32,
+}
+
+/// The parameters of an orthographic projection, as passed to [`ortho`].
+///
+/// See [`decompose_ortho`](fn.decompose_ortho.html).
+///
+/// [`ortho`]: fn.ortho.html
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct OrthoParams {
+    /// The position of the left plane on the _x_ axis.
+    pub left: f32,
+
+    /// The position of the right plane on the _x_ axis.
+    pub right: f32,
+
+    /// The position of the bottom plane on the _y_ axis.
+    pub bottom: f32,
+
+    /// The position of the top plane on the _y_ axis.
+    pub top: f32,
+
+    /// The distance to the near plane.
+    pub near: f32,
+
+    /// The distance to the far plane.
+    pub far: f32,
+}
+
+/// Recover the parameters of a symmetric perspective projection matrix.
+///
+/// This is the inverse of [`perspective`]. Returns `None` if `proj` does not have the structure
+/// of such a matrix (to within the default threshold), e.g. if it is orthographic or asymmetric,
+/// or if its depth coefficients do not correspond to `0 < near < far`. In particular, reversed-Z
+/// matrices (see [`perspective_reversed_zo`]) and infinite projections are rejected.
+///
+/// The depth range convention cannot be determined from the matrix alone: a matrix built by
+/// [`perspective_zo`] has a valid OpenGL-style structure, but decoding it with this function
+/// gives the wrong near distance. Use [`decompose_perspective_zo`] for such matrices.
+///
+/// The far distance is recovered from the difference between the depth coefficient and 1, so its
+/// relative precision degrades as `near / far` approaches the floating point epsilon.
+///
+/// # Example
+/// ```rust
+/// # #[macro_use] extern crate gramit;
+/// # use gramit::*;
+/// use gramit::transform;
+///
+/// # fn main() {
+/// let proj = transform::perspective(Angle::from_degrees(60.0), 1.5, 0.1, 100.0);
+/// let params = transform::decompose_perspective(&proj).unwrap();
+///
+/// assert_approx_eq!(params.fovy, Angle::from_degrees(60.0));
+/// assert_approx_eq!(params.aspect_xy, 1.5);
+/// assert_within_threshold!(params.near, 0.1, 1e-6);
+/// assert_within_threshold!(params.far, 100.0, 1e-2);
+/// # }
+/// ```
+///
+/// [`perspective`]: fn.perspective.html
+/// [`perspective_zo`]: fn.perspective_zo.html
+/// [`perspective_reversed_zo`]: fn.perspective_reversed_zo.html
+/// [`decompose_perspective_zo`]: fn.decompose_perspective_zo.html
+pub fn decompose_perspective(proj: &Mat4) -> Option<PerspectiveParams> {
+    let (a, b) = perspective_depth_coefficients(proj)?;
+    if a >= -1.0 || b >= 0.0 {
+        return None;
+    }
+
+    Some(perspective_params(proj, b / (a - 1.0), b / (a + 1.0)))
+}
+
+/// Recover the parameters of a symmetric perspective projection matrix with a zero-to-one depth
+/// range.
+///
+/// This is the inverse of [`perspective_zo`], and is otherwise the same as
+/// [`decompose_perspective`], including its caveats about the depth range convention and the
+/// precision of the far distance.
+///
+/// [`perspective_zo`]: fn.perspective_zo.html
+/// [`decompose_perspective`]: fn.decompose_perspective.html
+pub fn decompose_perspective_zo(proj: &Mat4) -> Option<PerspectiveParams> {
+    let (a, b) = perspective_depth_coefficients(proj)?;
+    if a >= -1.0 || b >= 0.0 {
+        return None;
+    }
+
+    Some(perspective_params(proj, b / a, b / (a + 1.0)))
+}
+
+/// Recover the parameters of a symmetric reversed-Z perspective projection matrix with a
+/// zero-to-one depth range.
+///
+/// This is the inverse of [`perspective_reversed_zo`]. Returns `None` if `proj` does not have the
+/// structure of such a matrix (to within the default threshold), including if it is a
+/// non-reversed projection or an infinite one (see [`perspective_infinite_reversed_zo`]).
+///
+/// Unlike the non-reversed forms, both distances are recovered without cancellation, to within a
+/// small multiple of the floating point epsilon.
+///
+/// [`perspective_reversed_zo`]: fn.perspective_reversed_zo.html
+/// [`perspective_infinite_reversed_zo`]: fn.perspective_infinite_reversed_zo.html
+pub fn decompose_perspective_reversed_zo(proj: &Mat4) -> Option<PerspectiveParams> {
+    let (a, b) = perspective_depth_coefficients(proj)?;
+    if a <= 0.0 || b <= 0.0 {
+        return None;
+    }
+
+    Some(perspective_params(proj, b / (a + 1.0), b / a))
+}
+
+// Check that `proj` has the structure shared by the symmetric perspective matrices, and get its
+// depth coefficients `(proj[2][2], proj[3][2])`.
+fn perspective_depth_coefficients(proj: &Mat4) -> Option<(f32, f32)> {
+    let zero = |c: usize, r: usize| proj[c][r].approx_eq(0.0);
+    let structured = [(0, 1), (0, 2), (0, 3), (1, 0), (1, 2), (1, 3), (2, 0), (2, 1)]
+        .iter()
+        .chain([(3, 0), (3, 1), (3, 3)].iter())
+        .all(|&(c, r)| zero(c, r))
+        && proj[2][3].approx_eq(-1.0);
+
+    if !structured || proj[0][0] == 0.0 || proj[1][1] == 0.0 {
+        return None;
+    }
+
+    Some((proj[2][2], proj[3][2]))
+}
+
+fn perspective_params(proj: &Mat4, near: f32, far: f32) -> PerspectiveParams {
+    PerspectiveParams {
+        fovy: Angle::from_radians(2.0 * (1.0 / proj[1][1]).atan()),
+        aspect_xy: proj[1][1] / proj[0][0],
+        near,
+        far,
+    }
+}
+
+/// Recover the parameters of an orthographic projection matrix.
+///
+/// This is the inverse of [`ortho`]. Returns `None` if `proj` does not have the structure of such
+/// a matrix (to within the default threshold), e.g. if it is a perspective projection.
+///
+/// Each plane position is recovered to within a small multiple of the floating point epsilon times
+/// the magnitude of the bounds along its axis, so e.g. a near distance much smaller than the far
+/// distance has reduced relative precision.
+///
+/// [`ortho`]: fn.ortho.html
+pub fn decompose_ortho(proj: &Mat4) -> Option<OrthoParams> {
+    let zero = |c: usize, r: usize| proj[c][r].approx_eq(0.0);
+    let structured = [(0, 1), (0, 2), (0, 3), (1, 0), (1, 2), (1, 3), (2, 0), (2, 1), (2, 3)]
+        .iter()
+        .all(|&(c, r)| zero(c, r))
+        && proj[3][3].approx_eq(1.0);
+
+    if !structured || proj[0][0] == 0.0 || proj[1][1] == 0.0 || proj[2][2] == 0.0 {
+        return None;
+    }
+
+    Some(OrthoParams {
+        left: (-proj[3][0] - 1.0) / proj[0][0],
+        right: (-proj[3][0] + 1.0) / proj[0][0],
+        bottom: (-proj[3][1] - 1.0) / proj[1][1],
+        top: (-proj[3][1] + 1.0) / proj[1][1],
+        near: (proj[3][2] + 1.0) / proj[2][2],
+        far: (proj[3][2] - 1.0) / proj[2][2],
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_util::*;
+
+    #[test]
+    fn test_translate() {
+        let test_func = |v: Vec3, offset| {
+            let expected = v + offset;
+            let v = v.homogeneous();
+
+            let m = translate(offset);
+            let vt = m * v;
+            let vt = vt.homogenize();
+            assert_approx_eq!(
+                vt,
+                expected,
+                "Failure with v = {:?}, offset = {:?}. Expected {:?}, got {:?}.",
+                v,
+                offset,
+                expected,
+                vt
+            );
+        };
+
+        for v in GenVec3::new(-4, 4) {
+            for o in GenVec3::new(-4, 4) {
+                test_func(v, o);
+            }
+        }
+    }
+
+    #[test]
+    fn test_scale() {
+        let test_func = |v: Vec3, s: Vec3| {
+            let expected = vec3!(v[0] * s[0], v[1] * s[1], v[2] * s[2]);
+            let v = v.homogeneous();
+
+            let m = scale(s);
+            let vt = m * v;
+            let vt = vt.homogenize();
+            assert_approx_eq!(
+                vt,
+                expected,
+                "Failure with v = {:?}, scale = {:?}. Expected {:?}, got {:?}.",
+                v,
+                s,
+                expected,
+                vt
+            );
+        };
+
+        for v in GenVec3::new(-4, 4) {
+            for s in GenVec3::new(-4, 4) {
+                test_func(v, s);
+            }
+        }
+    }
+
+    #[test]
+    fn test_translate_scale_xyz() {
+        let start = Transform::new().rotate(vec3!(1.0, 2.0, 3.0), Angle::from_degrees(40.0));
+        for v in GenVec3::new(-4, 4) {
+            let (x, y, z) = (v.x, v.y, v.z);
+            assert_eq!(translate_xyz(x, y, z), translate(v));
+            assert_eq!(scale_xyz(x, y, z), scale(v));
+            assert_eq!(start.translate_xyz(x, y, z), start.translate(v));
+            assert_eq!(start.scale_xyz(x, y, z), start.scale(v));
+        }
+    }
+
+    #[test]
+    fn test_uniform_scale() {
+        for f in -8..=8 {
+            let f = f as f32 * 0.5;
+            assert_eq!(uniform_scale(f), scale(vec3!(f, f, f)));
+            assert_eq!(
+                Transform::new().uniform_scale(f).finish(),
+                Transform::new().scale(vec3!(f, f, f)).finish()
+            );
+        }
+    }
+
+    #[test]
+    fn test_scale_about() {
+        let test_func = |center: Vec3, s: Vec3, v: Vec3| {
+            let m = scale_about(center, s);
+
+            let c = (m * center.homogeneous()).homogenize();
+            assert_approx_eq!(
+                c,
+                center,
+                "Failure with center = {:?}, scale = {:?}. Center moved to {:?}.",
+                center,
+                s,
+                c
+            );
+
+            let expected = center + s * v;
+            let vt = (m * (center + v).homogeneous()).homogenize();
+            assert_approx_eq!(
+                vt,
+                expected,
+                "Failure with center = {:?}, scale = {:?}, v = {:?}. Expected {:?}, got {:?}.",
+                center,
+                s,
+                v,
+                expected,
+                vt
+            );
+        };
+
+        for center in GenVec3::new(-2, 2) {
+            for s in GenVec3::new(-2, 2) {
+                for v in GenVec3::new(-1, 1) {
+                    test_func(center, s, v);
+                }
+            }
+        }
+
+        let center = vec3!(1.0, -2.0, 3.0);
+        assert_eq!(
+            uniform_scale_about(center, -1.5),
+            scale_about(center, vec3!(-1.5, -1.5, -1.5))
+        );
+        assert_eq!(
+            Transform::new().uniform_scale_about(center, 0.5).finish(),
+            Transform::new().scale_about(center, vec3!(0.5, 0.5, 0.5)).finish()
+        );
+    }
+
+    #[test]
+    fn test_reflect() {
+        for n in GenVec3::new(-2, 2) {
+            if n.approx_eq(Vec3::zeros()) {
+                continue;
+            }
+
+            let m = reflect(n);
+            assert_within_threshold!(
+                m * m,
+                Mat4::identity(),
+                Mat4::ones() * 1e-5,
+                "Failure with normal = {:?}. Double reflection gave {:?}.",
+                n,
+                m * m
+            );
+            assert_approx_eq!(
+                m.determinant(),
+                -1.0,
+                "Failure with normal = {:?}. Determinant was {}.",
+                n,
+                m.determinant()
+            );
+
+            for v in GenVec3::new(-2, 2) {
+                let in_plane = v - n * (v.dot(&n) / n.dot(&n));
+                let vt = (m * in_plane.homogeneous()).homogenize();
+                assert_within_threshold!(
+                    vt,
+                    in_plane,
+                    Vec3::ones() * 1e-5,
+                    "Failure with normal = {:?}. Expected {:?} to be fixed, got {:?}.",
+                    n,
+                    in_plane,
+                    vt
+                );
+            }
+        }
+
+        assert_eq!(
+            Transform::new().reflect(Vec3::y()).finish(),
+            scale(vec3!(1.0, -1.0, 1.0))
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_reflect_zero_normal() {
+        reflect(Vec3::zeros());
+    }
+
+    #[test]
+    fn test_reflect_about() {
+        let thresh = Vec3::ones() * 1e-4;
+        for n in GenVec3::new(-2, 2) {
+            if n == Vec3::zeros() {
+                continue;
+            }
+
+            let unit = n.unit();
+            for d in -3..=3 {
+                let d = d as f32 * 0.75;
+                let m = reflect_about(unit, d);
+                assert_within_threshold!(m * m, Mat4::identity(), Mat4::ones() * 1e-5);
+                assert_within_threshold!(
+                    reflect_about_plane(n.extend(-d * n.length())),
+                    m,
+                    Mat4::ones() * 1e-5
+                );
+
+                for v in GenVec3::new(-2, 2) {
+                    let in_plane = v - unit * (v.dot(&unit) - d);
+                    let vt = (m * in_plane.homogeneous()).homogenize();
+                    assert_within_threshold!(
+                        vt,
+                        in_plane,
+                        thresh,
+                        "Failure with n = {:?}, d = {}. Expected {:?} to be fixed, got {:?}.",
+                        unit,
+                        d,
+                        in_plane,
+                        vt
+                    );
+
+                    let expected = v - unit * (2.0 * (v.dot(&unit) - d));
+                    let vt = (m * v.homogeneous()).homogenize();
+                    assert_within_threshold!(vt, expected, thresh);
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_reflect_about_plane_zero_normal() {
+        reflect_about_plane(vec4!(0.0, 0.0, 0.0, 1.0));
+    }
+
+    #[test]
+    fn test_reflect_plane_axis_aligned() {
+        let mx = reflect_plane(Vec3::x(), vec3!(2.0, 5.0, -1.0));
+        let my = reflect_plane(-Vec3::y(), vec3!(0.0, -1.0, 0.0));
+        let mz = reflect_plane(3.0 * Vec3::z(), vec3!(1.0, 1.0, 0.5));
+
+        for v in GenVec3::new(-4, 4) {
+            let cases = [
+                (mx, vec3!(4.0 - v.x, v.y, v.z)),
+                (my, vec3!(v.x, -2.0 - v.y, v.z)),
+                (mz, vec3!(v.x, v.y, 1.0 - v.z)),
+            ];
+
+            for (m, expected) in cases.iter() {
+                let vt = (m * v.homogeneous()).homogenize();
+                assert_approx_eq!(
+                    vt,
+                    *expected,
+                    "Failure with v = {:?}. Expected {:?}, got {:?}.",
+                    v,
+                    expected,
+                    vt
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_reflect_plane_oblique() {
+        let planes = [
+            (vec3!(1.0, 1.0, 0.0), vec3!(1.0, 0.0, 0.0)),
+            (vec3!(1.0, -2.0, 3.0), vec3!(-1.0, 2.0, 0.5)),
+            (vec3!(0.0, 4.0, -1.0), vec3!(3.0, 3.0, 3.0)),
+        ];
+
+        for (n, p) in planes.iter() {
+            let m = reflect_plane(*n, *p);
+            assert_within_threshold!(
+                m * m,
+                Mat4::identity(),
+                Mat4::ones() * 1e-5,
+                "Failure with normal = {:?}, point = {:?}. Double reflection gave {:?}.",
+                n,
+                p,
+                m * m
+            );
+
+            for v in GenVec3::new(-2, 2) {
+                let on_plane = v - *n * ((v - p).dot(n) / n.dot(n));
+                let vt = (m * on_plane.homogeneous()).homogenize();
+                assert_within_threshold!(
+                    vt,
+                    on_plane,
+                    Vec3::ones() * 1e-5,
+                    "Failure with normal = {:?}, point = {:?}. Expected {:?} to be fixed, got {:?}.",
+                    n,
+                    p,
+                    on_plane,
+                    vt
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_mirror() {
+        assert_eq!(mirror_x(), scale(vec3!(-1.0, 1.0, 1.0)));
+        assert_eq!(mirror_y(), scale(vec3!(1.0, -1.0, 1.0)));
+        assert_eq!(mirror_z(), scale(vec3!(1.0, 1.0, -1.0)));
+
+        for m in [mirror_x(), mirror_y(), mirror_z()].iter() {
+            assert_eq!(m * m, Mat4::identity());
+            assert_approx_eq!(m.determinant(), -1.0);
+        }
+
+        for v in GenVec3::new(-2, 2) {
+            let t = Transform::new().translate(v);
+            assert_eq!(t.mirror_x().transform_point(v), vec3!(-2.0 * v.x, 2.0 * v.y, 2.0 * v.z));
+            assert_eq!(t.mirror_y().transform_point(v), vec3!(2.0 * v.x, -2.0 * v.y, 2.0 * v.z));
+            assert_eq!(t.mirror_z().transform_point(v), vec3!(2.0 * v.x, 2.0 * v.y, -2.0 * v.z));
+        }
+
+        let t = Transform::new().rotate_y(Angle::from_degrees(30.0));
+        assert!(!t.is_mirroring());
+        assert!(t.mirror_x().is_mirroring());
+        assert!(!t.mirror_x().mirror_z().is_mirroring());
+        assert!(t.mirror_x().mirror_y().mirror_z().is_mirroring());
+        assert_eq!(t.is_right_handed(1e-6), Some(true));
+        assert_eq!(t.mirror_x().is_right_handed(1e-6), Some(false));
+        assert!(t.mirror_x().winding_flipped());
+        assert!(!t.mirror_x().mirror_y().winding_flipped());
+        assert_eq!(t.scale(vec3!(1.0, 0.0, 1.0)).is_right_handed(1e-6), None);
+    }
+
+    #[test]
+    fn test_shear() {
+        let test_func = |v: Vec3, amt1, amt2| {
+            let expectedx = vec3!(v[0], v[1] + v[0] * amt1, v[2] + v[0] * amt2);
+            let expectedy = vec3!(v[0] + v[1] * amt1, v[1], v[2] + v[1] * amt2);
+            let expectedz = vec3!(v[0] + v[2] * amt1, v[1] + v[2] * amt2, v[2]);
+
+            let v = v.homogeneous();
+
+            let mx = shear_x(amt1, amt2);
+            let my = shear_y(amt1, amt2);
+            let mz = shear_z(amt1, amt2);
+
+            let vtx = (mx * v).homogenize();
+            let vty = (my * v).homogenize();
+            let vtz = (mz * v).homogenize();
+
+            assert_approx_eq!(
+                vtx,
+                expectedx,
+                "Failure with v = {:?}, shear_x({}, {}). Expected {:?}, got {:?}.",
+                v,
+                amt1,
+                amt2,
+                expectedx,
+                vtx
+            );
+            assert_approx_eq!(
+                vty,
+                expectedy,
+                "Failure with v = {:?}, shear_y({}, {}). Expected {:?}, got {:?}.",
+                v,
+                amt1,
+                amt2,
+                expectedy,
+                vty
+            );
+            assert_approx_eq!(
+                vtz,
+                expectedz,
+                "Failure with v = {:?}, shear_z({}, {}). Expected {:?}, got {:?}.",
+                v,
+                amt1,
+                amt2,
+                expectedz,
+                vtz
+            );
+        };
+
+        for v in GenVec3::new(-4, 4) {
+            for amt1 in -4..=4 {
+                for amt2 in -4..=4 {
+                    test_func(v, amt1 as f32, amt2 as f32);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_rotate_axes() {
+        for deg in (-720..=720).step_by(15) {
+            let a = Angle::from_degrees(deg as f32);
+
+            let pairs = [
+                (rotate_x(a), rotate(Vec3::x(), a), "x"),
+                (rotate_y(a), rotate(Vec3::y(), a), "y"),
+                (rotate_z(a), rotate(Vec3::z(), a), "z"),
+            ];
+
+            for (actual, expected, axis) in pairs.iter() {
+                assert_within_threshold!(
+                    *actual,
+                    *expected,
+                    Mat4::ones() * 1e-5,
+                    "Failure with rotate_{}({} degrees). Expected {:?}, got {:?}.",
+                    axis,
+                    deg,
+                    expected,
+                    actual
+                );
+            }
+
+            assert_eq!(Transform::new().rotate_x(a).finish(), rotate_x(a));
+            assert_eq!(Transform::new().rotate_y(a).finish(), rotate_y(a));
+            assert_eq!(Transform::new().rotate_z(a).finish(), rotate_z(a));
+        }
+    }
+
+    #[test]
+    fn test_rotation_euler() {
+        use EulerOrder::*;
+
+        let thresh = Mat4::ones() * 1e-5;
+        let angle_sets = [(30.0, -45.0, 10.0), (-120.0, 80.0, 200.0), (5.0, 170.0, -60.0)];
+        for &(d0, d1, d2) in angle_sets.iter() {
+            let a = Angle::from_degrees(d0);
+            let b = Angle::from_degrees(d1);
+            let c = Angle::from_degrees(d2);
+            let t = Transform::new();
+            let cases = [
+                (XYZ, t.rotate_z(c).rotate_y(b).rotate_x(a)),
+                (XZY, t.rotate_y(c).rotate_z(b).rotate_x(a)),
+                (YXZ, t.rotate_z(c).rotate_x(b).rotate_y(a)),
+                (YZX, t.rotate_x(c).rotate_z(b).rotate_y(a)),
+                (ZXY, t.rotate_y(c).rotate_x(b).rotate_z(a)),
+                (ZYX, t.rotate_x(c).rotate_y(b).rotate_z(a)),
+            ];
+
+            for (order, chain) in cases.iter() {
+                let m = rotation_euler([a, b, c], *order);
+                assert_within_threshold!(m, chain.finish(), thresh, "Order {:?}", order);
+                assert_eq!(Transform::new().rotate_euler([a, b, c], *order).finish(), m);
+            }
+
+            for (i, (o1, _)) in cases.iter().enumerate() {
+                for (o2, _) in cases.iter().skip(i + 1) {
+                    let m1 = rotation_euler([a, b, c], *o1);
+                    let m2 = rotation_euler([a, b, c], *o2);
+                    assert!(
+                        !m1.within_threshold(m2, Mat4::ones() * 1e-3),
+                        "Orders {:?} and {:?} agree",
+                        o1,
+                        o2
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_euler_from_matrix() {
+        use EulerOrder::*;
+
+        let thresh = Mat4::ones() * 1e-4;
+        let orders = [XYZ, XZY, YXZ, YZX, ZXY, ZYX];
+        let middles = [
+            -180.0, -135.0, -90.01, -90.0, -89.99, -45.0, 0.0, 30.0, 89.9, 90.0, 90.001, 150.0,
+        ];
+
+        for &order in orders.iter() {
+            for d0 in (-180..=180).step_by(45) {
+                for &d1 in middles.iter() {
+                    for d2 in (-165..=180).step_by(55) {
+                        let angles = [
+                            Angle::from_degrees(d0 as f32),
+                            Angle::from_degrees(d1),
+                            Angle::from_degrees(d2 as f32),
+                        ];
+                        let m = rotation_euler(angles, order);
+                        let recovered = euler_from_matrix(&m, order);
+
+                        assert_within_threshold!(
+                            rotation_euler(recovered, order),
+                            m,
+                            thresh,
+                            "Round trip of {:?} {:?} gave {:?}",
+                            order,
+                            angles,
+                            recovered
+                        );
+
+                        let [a, b, c] = recovered;
+                        assert!(b.degrees().abs() <= 90.0, "{:?} out of range", recovered);
+                        assert!(a.degrees().abs() <= 180.0, "{:?} out of range", recovered);
+                        assert!(c.degrees().abs() <= 180.0, "{:?} out of range", recovered);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_euler_from_matrix_angles() {
+        use EulerOrder::*;
+
+        for &order in [XYZ, XZY, YXZ, YZX, ZXY, ZYX].iter() {
+            let angles = [
+                Angle::from_degrees(-120.0),
+                Angle::from_degrees(35.0),
+                Angle::from_degrees(70.0),
+            ];
+            let recovered = euler_from_matrix(&rotation_euler(angles, order), order);
+            for (a, r) in angles.iter().zip(recovered.iter()) {
+                assert_within_threshold!(a.degrees(), r.degrees(), 1e-3, "Order {:?}", order);
+            }
+
+            // Gimbal lock zeroes the last angle.
+            let locked = [
+                Angle::from_degrees(20.0),
+                Angle::from_degrees(90.0),
+                Angle::from_degrees(30.0),
+            ];
+            let recovered = euler_from_matrix(&rotation_euler(locked, order), order);
+            assert_eq!(recovered[2], Angle::zero());
+            assert_within_threshold!(recovered[1].degrees(), 90.0, 1e-3);
+        }
+    }
+
+    #[test]
+    fn test_axis_angle_from_matrix() {
+        let thresh = Mat4::ones() * 1e-4;
+        for axis in GenVec3::new(-2, 2) {
+            if axis == Vec3::zeros() {
+                continue;
+            }
+
+            for deg in (-360..=360).step_by(15) {
+                let m = rotate(axis, Angle::from_degrees(deg as f32));
+                let (k, a) = axis_angle_from_matrix(&m).unwrap_or_else(|| {
+                    panic!("No axis-angle for rotate({:?}, {} degrees)", axis, deg)
+                });
+
+                assert_within_threshold!(k.length(), 1.0, 1e-5);
+                assert!(a.degrees() >= 0.0 && a.degrees() <= 180.0 + 1e-3);
+                assert_within_threshold!(
+                    rotate(k, a),
+                    m,
+                    thresh,
+                    "Round trip of rotate({:?}, {} degrees) gave ({:?}, {:?})",
+                    axis,
+                    deg,
+                    k,
+                    a
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_se3_round_trip() {
+        let mut rng = Lcg::new(0x5e3);
+        let mut checked = 0;
+        while checked < 200 {
+            let axis = rng.vec3(-1.0, 1.0);
+            if axis.length() < 0.1 {
+                continue;
+            }
+
+            let deg = rng.next_f32(-179.0, 179.0);
+            let m = translate(rng.vec3(-5.0, 5.0)) * rotate(axis, Angle::from_degrees(deg));
+            let (omega, v) = log_se3(&m).unwrap_or_else(|| panic!("No log for {:?}", m));
+            assert!(omega.length() <= std::f32::consts::PI + 1e-5);
+            assert_within_threshold!(exp_se3(omega, v), m, Mat4::ones() * 1e-4, "m: {:?}", m);
+
+            let (omega2, v2) = log_se3(&exp_se3(omega, v)).unwrap();
+            assert_within_threshold!(omega2, omega, Vec3::ones() * 1e-4);
+            assert_within_threshold!(v2, v, Vec3::ones() * 1e-3);
+            checked += 1;
+        }
+    }
+
+    #[test]
+    fn test_se3_special_angles() {
+        // Pure translation: the twist is just the translation itself.
+        let (omega, v) = log_se3(&translate(vec3!(1.0, -2.0, 3.0))).unwrap();
+        assert_eq!(omega, Vec3::zeros());
+        assert_eq!(v, vec3!(1.0, -2.0, 3.0));
+        assert_eq!(exp_se3(Vec3::zeros(), v), translate(v));
+
+        // Small angles, on both sides of the series threshold.
+        for &deg in [1e-4, 1e-2, 0.5, 5.0, 5.7, 6.0].iter() {
+            let m = translate(vec3!(0.5, 1.0, -1.0)) * rotate(vec3!(1.0, 2.0, 2.0),
+                Angle::from_degrees(deg));
+            let (omega, v) = log_se3(&m).unwrap();
+            assert_within_threshold!(omega.length(), deg.to_radians(), 1e-6);
+            assert_within_threshold!(exp_se3(omega, v), m, Mat4::ones() * 1e-5);
+
+            let omega = vec3!(1.0, 2.0, 2.0).unit() * deg.to_radians();
+            let v = vec3!(0.3, -0.2, 1.0);
+            let r = exp_se3(omega, v);
+            assert_within_threshold!(r.truncate(),
+                rotate(omega, Angle::from_degrees(deg)).truncate(), Mat3::ones() * 1e-6);
+        }
+
+        // Close to a half turn.
+        for &deg in [179.0, 179.9, 180.0].iter() {
+            let m = translate(vec3!(2.0, 0.0, 1.0)) * rotate(vec3!(0.0, 1.0, 1.0),
+                Angle::from_degrees(deg));
+            let (omega, v) = log_se3(&m).unwrap();
+            assert_within_threshold!(omega.length(), deg.to_radians(), 1e-4);
+            assert_within_threshold!(exp_se3(omega, v), m, Mat4::ones() * 1e-4);
+        }
+
+        // A half turn about z with translation along the axis is a pure screw.
+        let (omega, v) = log_se3(&(translate(Vec3::z()) * rotate_z(Angle::half_circle()))).unwrap();
+        assert_within_threshold!(omega.cross(&Vec3::z()), Vec3::zeros(), Vec3::ones() * 1e-5);
+        assert_within_threshold!(v, Vec3::z(), Vec3::ones() * 1e-5);
+
+        assert_eq!(log_se3(&scale(vec3!(1.0, 2.0, 1.0))), None);
+        assert_eq!(log_se3(&reflect(Vec3::x())), None);
+        assert_eq!(log_se3(&perspective(Angle::from_degrees(60.0), 1.0, 0.1, 10.0)), None);
+    }
+
+    #[test]
+    fn test_screw_lerp() {
+        let a = translate(vec3!(1.0, 0.0, 0.0)) * rotate(Vec3::y(), Angle::from_degrees(10.0));
+        let b = translate(vec3!(-2.0, 3.0, 1.0)) * rotate(vec3!(1.0, 1.0, 0.0),
+            Angle::from_degrees(120.0));
+
+        assert_eq!(screw_lerp(&a, &b, 0.0), Some(a));
+        assert_eq!(screw_lerp(&a, &b, 1.0), Some(b));
+
+        // Equal steps along the screw are congruent.
+        let step = a.try_inverse().unwrap() * screw_lerp(&a, &b, 0.1).unwrap();
+        let mut prev = a;
+        for i in 1..=10 {
+            let t = i as f32 / 10.0;
+            let m = screw_lerp(&a, &b, t).unwrap();
+            assert!(m.is_rotation(1e-5) && m.is_affine(1e-6), "t = {}: {:?}", t, m);
+            assert_within_threshold!(prev.try_inverse().unwrap() * m, step, Mat4::ones() * 1e-4);
+            prev = m;
+        }
+        assert_within_threshold!(prev, b, Mat4::ones() * 1e-4);
+
+        assert_eq!(screw_lerp(&scale(Vec3::ones() * 2.0), &b, 0.5), None);
+        assert_eq!(screw_lerp(&a, &shear_x(0.5, 0.0), 0.5), None);
+    }
+
+    #[test]
+    fn test_axis_angle_from_matrix_special() {
+        let (k, a) = axis_angle_from_matrix(&Mat4::identity()).unwrap();
+        assert_within_threshold!(k.length(), 1.0, 1e-5);
+        assert_eq!(a, Angle::zero());
+
+        for &axis in [Vec3::x(), Vec3::y(), -Vec3::z(), vec3!(1.0, -2.0, 3.0)].iter() {
+            let m = rotate(axis, Angle::half_circle());
+            let (k, a) = axis_angle_from_matrix(&m).unwrap();
+            assert_within_threshold!(a.degrees(), 180.0, 1e-3);
+            assert_within_threshold!(k.cross(&axis.unit()), Vec3::zeros(), Vec3::ones() * 1e-5);
+            assert_within_threshold!(rotate(k, a), m, Mat4::ones() * 1e-5);
+        }
+
+        assert!(axis_angle_from_matrix(&scale(vec3!(1.0, 2.0, 1.0))).is_none());
+        assert!(axis_angle_from_matrix(&reflect(Vec3::y())).is_none());
+        assert!(axis_angle_from_matrix(&shear_x(0.5, 0.0)).is_none());
+        assert!(axis_angle_from_matrix(&(rotate_x(Angle::from_degrees(30.0)) * 2.0)).is_none());
+    }
+
+    #[test]
+    fn test_rotate_about() {
+        let thresh = Vec3::ones() * 1e-4;
+
+        for point in GenVec3::new(-2, 2) {
+            for axis in GenVec3::new(-1, 1) {
+                if axis.approx_eq(Vec3::zeros()) {
+                    continue;
+                }
+
+                for deg in (-180..=180).step_by(45) {
+                    let a = Angle::from_degrees(deg as f32);
+                    let m = rotate_about(point, axis, a);
+
+                    let fixed = (m * point.homogeneous()).homogenize();
+                    assert_within_threshold!(
+                        fixed,
+                        point,
+                        thresh,
+                        "Failure with point = {:?}, axis = {:?}, angle = {}. Got {:?}.",
+                        point,
+                        axis,
+                        deg,
+                        fixed
+                    );
+
+                    let on_axis = point + 2.0 * axis;
+                    let fixed = (m * on_axis.homogeneous()).homogenize();
+                    assert_within_threshold!(
+                        fixed,
+                        on_axis,
+                        thresh,
+                        "Failure with point = {:?}, axis = {:?}, angle = {}. Expected {:?}, got {:?}.",
+                        point,
+                        axis,
+                        deg,
+                        on_axis,
+                        fixed
+                    );
+                }
+            }
+        }
+
+        let t = Transform::new()
+            .translate(vec3!(1.0, 0.0, 0.0))
+            .rotate_about(vec3!(1.0, 1.0, 0.0), Vec3::z(), Angle::from_degrees(90.0));
+        assert_eq!(
+            t.finish(),
+            rotate_about(vec3!(1.0, 1.0, 0.0), Vec3::z(), Angle::from_degrees(90.0))
+                * translate(vec3!(1.0, 0.0, 0.0))
+        );
+    }
+
+    fn sample_chain(v: Vec3, deg: f32) -> Transform {
+        Transform::new()
+            .scale(v + vec3!(5.0, 5.0, 5.0))
+            .shear_y(v.x, v.z)
+            .rotate(v + Vec3::ones() * 0.5, Angle::from_degrees(deg))
+            .translate(v)
+    }
+
+    #[test]
+    fn test_then_before() {
+        let mut deg = 0.0;
+        for v in GenVec3::new(-2, 2) {
+            let a = sample_chain(v, deg);
+            let b = sample_chain(-v, 45.0 - deg);
+            deg += 7.0;
+
+            assert_eq!(a.then(b).finish(), b.finish() * a.finish());
+            assert_eq!(a.before(b).finish(), a.finish() * b.finish());
+            assert_eq!(a.before(b), b.then(a));
+        }
+
+        let t = Transform::new().rotate_z(Angle::from_degrees(30.0));
+        let offset = vec3!(1.0, 2.0, 3.0);
+        assert_eq!(t.then(Transform::new().translate(offset)), t.translate(offset));
+    }
+
+    #[test]
+    fn test_pre_variants() {
+        let a = Angle::from_degrees(35.0);
+        for v in GenVec3::new(-2, 2) {
+            let t = sample_chain(v, 20.0);
+
+            assert_eq!(t.pre_translate(v).finish(), t.finish() * translate(v));
+            assert_eq!(t.pre_scale(v).finish(), t.finish() * scale(v));
+            assert_eq!(t.pre_shear_x(v.x, v.y).finish(), t.finish() * shear_x(v.x, v.y));
+            assert_eq!(t.pre_shear_y(v.y, v.z).finish(), t.finish() * shear_y(v.y, v.z));
+            assert_eq!(t.pre_shear_z(v.z, v.x).finish(), t.finish() * shear_z(v.z, v.x));
+            assert_eq!(
+                t.pre_rotate(v + Vec3::ones() * 0.5, a).finish(),
+                t.finish() * rotate(v + Vec3::ones() * 0.5, a)
+            );
+            assert_eq!(
+                t.pre_arbitrary(shear_x(1.0, 2.0)).finish(),
+                t.finish() * shear_x(1.0, 2.0)
+            );
+        }
+    }
+
+    #[test]
+    fn test_invertible_transform() {
+        let thresh = Mat4::ones() * 1e-4;
+
+        let mut deg = 10.0;
+        for v in GenVec3::new(-1, 1) {
+            let a = Angle::from_degrees(deg);
+            deg += 13.0;
+
+            let t = InvertibleTransform::new()
+                .translate(v)
+                .rotate(v + vec3!(0.5, 0.25, 1.0), a)
+                .shear_x(v.y * 0.25, v.z * 0.25)
+                .scale(vec3!(2.0, 0.5, 1.0))
+                .rotate_x(a)
+                .shear_y(v.z * 0.25, v.x * 0.25)
+                .rotate_about(v, Vec3::z(), a)
+                .scale_about(v, vec3!(0.5, 1.0, 2.0))
+                .shear_z(v.x * 0.25, v.y * 0.25)
+                .rotate_y(a)
+                .reflect(v + 3.0 * Vec3::x())
+                .uniform_scale(1.5)
+                .reflect_plane(Vec3::y(), v)
+                .rotate_z(-a)
+                .uniform_scale(1.0 / 1.5)
+                .arbitrary(translate(Vec3::x()), translate(-Vec3::x()));
+
+            let (fwd, inv) = t.finish_pair();
+            assert_within_threshold!(fwd * inv, Mat4::identity(), thresh,
+                "fwd: {:?}\ninv: {:?}\nfwd * inv: {:?}", fwd, inv, fwd * inv);
+            assert_within_threshold!(inv * fwd, Mat4::identity(), thresh,
+                "fwd: {:?}\ninv: {:?}\ninv * fwd: {:?}", fwd, inv, inv * fwd);
+
+            assert_eq!(t.finish(), fwd);
+            assert_eq!(t.finish_inverse(), inv);
+        }
+    }
+
+    #[test]
+    fn test_finish_inverse() {
+        let thresh = Mat4::ones() * 1e-4;
+        let mut deg = 0.0;
+        for v in GenVec3::new(-2, 2) {
+            let t = sample_chain(v, deg)
+                .shear_x(0.5, -0.5)
+                .rotate_y(Angle::from_degrees(deg * 2.0))
+                .uniform_scale(0.5)
+                .shear_z(-0.25, 0.75);
+            deg += 11.0;
+
+            let inv = t.finish_inverse();
+            assert_within_threshold!(t.finish() * inv, Mat4::identity(), thresh,
+                "m: {:?}\ninv: {:?}\nm * inv: {:?}", t.finish(), inv, t.finish() * inv);
+            assert_within_threshold!(inv * t.finish(), Mat4::identity(), thresh,
+                "m: {:?}\ninv: {:?}\ninv * m: {:?}", t.finish(), inv, inv * t.finish());
+        }
+
+        let projective = Transform::new()
+            .translate(vec3!(1.0, 2.0, 3.0))
+            .arbitrary(perspective(Angle::from_degrees(60.0), 1.5, 0.1, 10.0));
+        let inv = projective.finish_inverse();
+        assert_within_threshold!(projective.finish() * inv, Mat4::identity(), thresh);
+    }
+
+    #[test]
+    fn test_try_finish_inverse_singular() {
+        let t = Transform::new()
+            .rotate_x(Angle::from_degrees(30.0))
+            .scale(vec3!(1.0, 0.0, 1.0));
+        assert!(t.try_finish_inverse().is_none());
+
+        let t = Transform::new().arbitrary(Mat4::ones());
+        assert!(t.try_finish_inverse().is_none());
+
+        let t = Transform::new().scale(vec3!(1e-39, 1.0, 1.0));
+        assert!(t.try_finish_inverse().is_none());
+        let t = Transform::new()
+            .arbitrary(perspective(Angle::from_degrees(60.0), 1.0, 0.1, 100.0))
+            .scale(vec3!(1.0, 1.0, 1e-39));
+        assert!(t.try_finish_inverse().is_none());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_finish_inverse_singular() {
+        Transform::new().uniform_scale(0.0).finish_inverse();
+    }
+
+    #[test]
+    fn test_transform_point_vector() {
+        let offset = vec3!(3.0, -2.0, 1.0);
+        for v in GenVec3::new(-2, 2) {
+            let t = sample_chain(v, 30.0).translate(offset);
+            let m = t.finish();
+
+            for p in GenVec3::new(-2, 2) {
+                assert_eq!(t.transform_point(p), (m * p.homogeneous()).homogenize());
+                assert_eq!(t.transform_vector(p), (m * p.extend(0.0)).truncate());
+                assert_approx_eq!(
+                    t.transform_vector(p),
+                    t.translate(-offset).transform_vector(p)
+                );
+            }
+
+            let mut points: Vec<_> = GenVec3::new(-1, 1).collect();
+            t.transform_points(&mut points);
+            for (p, orig) in points.iter().zip(GenVec3::new(-1, 1)) {
+                assert_eq!(*p, t.transform_point(orig));
+            }
+        }
+
+        let proj =
+            Transform::new().arbitrary(perspective(Angle::from_degrees(90.0), 1.0, 1.0, 3.0));
+        let p = vec3!(1.0, 1.0, -2.0);
+        assert_eq!(proj.transform_point(p), (proj.finish() * p.homogeneous()).homogenize());
+    }
+
+    #[test]
+    fn test_mul_ops() {
+        let mut deg = 0.0;
+        for v in GenVec3::new(-1, 1) {
+            let a = sample_chain(v, deg);
+            let b = sample_chain(-v, deg + 60.0);
+            let c = Transform::new().shear_z(v.x, -v.y).rotate_x(Angle::from_degrees(deg));
+            deg += 17.0;
+
+            assert_eq!((a * b).finish(), a.finish() * b.finish());
+            assert_eq!(a * b, b.then(a));
+            assert_eq!(a * b, a.before(b));
+            assert_within_threshold!(
+                ((a * b) * c).finish(),
+                (a * (b * c)).finish(),
+                Mat4::ones() * 1e-4
+            );
+
+            for p in GenVec3::new(-1, 1) {
+                assert_eq!(a * p.homogeneous(), a.finish() * p.homogeneous());
+                assert_eq!(a * p, (a.finish() * p.homogeneous()).homogenize());
+                assert_eq!(a * p, a.transform_point(p));
+            }
+        }
+    }
+
+    #[test]
+    fn test_mat4_conversions() {
+        let mut deg = 0.0;
+        for v in GenVec3::new(-2, 2) {
+            let m = sample_chain(v, deg).finish()
+                * perspective(Angle::from_degrees(75.0), 1.2, 0.5, 9.0);
+            deg += 9.0;
+
+            let t = Transform::from(m);
+            assert_eq!(Mat4::from(t), m);
+            assert_eq!(t.finish(), m);
+            assert_eq!(*t.as_ref(), m);
+
+            let back: Mat4 = t.into();
+            assert_eq!(back, m);
+        }
+    }
+
+    #[test]
+    fn test_look_at_builder() {
+        let center = vec3!(0.5, -0.5, 0.0);
+        let up = Vec3::z();
+        for eye in GenVec3::new(-2, 2) {
+            if eye.x == center.x && eye.y == center.y {
+                continue;
+            }
+
+            let model = sample_chain(eye, 40.0);
+            assert_eq!(
+                model.look_at(eye, center, up).finish(),
+                look_at(&eye, &center, &up) * model.finish()
+            );
+        }
+    }
+
+    #[test]
+    fn test_try_look_at() {
+        let center = vec3!(0.5, -0.5, 0.0);
+        let up = Vec3::z();
+        for eye in GenVec3::new(-2, 2) {
+            if eye.x == center.x && eye.y == center.y {
+                assert_eq!(try_look_at(eye, center, up), Err(LookAtError::UpParallelToFacing));
+                assert_eq!(try_look_at(eye, center, -up), Err(LookAtError::UpParallelToFacing));
+            } else {
+                assert_eq!(try_look_at(eye, center, up), Ok(look_at(&eye, &center, &up)));
+            }
+
+            assert_eq!(try_look_at(eye, eye, up), Err(LookAtError::EyeAtCenter));
+            if eye != center {
+                assert_eq!(try_look_at(eye, center, Vec3::zeros()), Err(LookAtError::ZeroUpVector));
+            }
+        }
+
+        let eye = vec3!(0.0, 0.0, 10.0);
+        let nearly = vec3!(1e-6, 0.0, 1.0);
+        assert_eq!(try_look_at(eye, Vec3::zeros(), nearly), Err(LookAtError::UpParallelToFacing));
+
+        let tilted = vec3!(1e-2, 0.0, 1.0);
+        assert!(try_look_at(eye, Vec3::zeros(), tilted).is_ok());
+
+        // Degenerate and extreme vector lengths.
+        let subnormal = Vec3::x() * (f32::MIN_POSITIVE / 2.0);
+        assert_eq!(try_look_at(eye, eye + subnormal, Vec3::y()), Err(LookAtError::EyeAtCenter));
+        assert_eq!(try_look_at(eye, Vec3::zeros(), subnormal), Err(LookAtError::ZeroUpVector));
+        assert!(try_look_at(eye, Vec3::zeros(), Vec3::x() * 1e30).is_ok());
+
+        assert_eq!(
+            LookAtError::UpParallelToFacing.to_string(),
+            "up vector is parallel to the facing direction"
+        );
+    }
+
+    #[test]
+    fn test_shadow_projection() {
+        let thresh = Vec3::ones() * 1e-3;
+        let normals = [Vec3::z(), vec3!(1.0, 2.0, 3.0), vec3!(0.0, -1.0, 0.5)];
+        let lights = [
+            vec4!(1.0, 2.0, 20.0, 1.0),
+            vec4!(-10.0, 15.0, 12.0, 1.0),
+            vec4!(0.3, 0.2, 1.0, 0.0),
+            vec4!(1.0, 1.0, 0.5, 0.0),
+        ];
+
+        for &n in normals.iter() {
+            for &plane_point in &[Vec3::zeros(), vec3!(0.5, -1.0, -2.0)] {
+                for &light in lights.iter() {
+                    let m = shadow_projection(n, plane_point, light);
+
+                    for p in GenVec3::new(-2, 2) {
+                        let dist = n.unit().dot(&(p - plane_point));
+                        let shadow = (m * p.homogeneous()).homogenize();
+
+                        if dist == 0.0 {
+                            assert_within_threshold!(shadow, p, thresh);
+                            continue;
+                        }
+
+                        let light_dir = if light.w == 0.0 {
+                            -light.truncate()
+                        } else {
+                            p - light.homogenize()
+                        };
+
+                        // Skip points whose line to the light (nearly) misses the plane.
+                        if light_dir.unit().dot(&n.unit()).abs() < 0.1 {
+                            continue;
+                        }
+
+                        assert_within_threshold!(n.unit().dot(&(shadow - plane_point)), 0.0, 1e-3);
+                        let along = (shadow - p).cross(&light_dir).length();
+                        assert_within_threshold!(along, 0.0, 1e-2, "off-line by {}", along);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_shadow_projection_zero_normal() {
+        shadow_projection(Vec3::zeros(), Vec3::zeros(), vec4!(0.0, 0.0, 1.0, 1.0));
+    }
+
+    #[test]
+    fn test_look_at_orthonormal() {
+        let center = vec3!(0.5, -0.5, 0.0);
+        for eye in GenVec3::new(-2, 2) {
+            if eye.x == center.x && eye.y == center.y {
+                continue;
+            }
+
+            let view = look_at(&eye, &center, &vec3!(0.0, 0.0, 2.0));
+            let linear = Transform::from(view).orthonormalize().finish();
+            assert_within_threshold!(view, linear, Mat4::ones() * 1e-4);
+            assert_within_threshold!(view.determinant(), 1.0, 1e-4);
+        }
+    }
+
+    #[test]
+    fn test_look_at_v() {
+        for eye in GenVec3::new(-2, 2) {
+            for center in GenVec3::new(-1, 1) {
+                for &up in &[Vec3::z(), vec3!(0.0, 2.0, 3.0), vec3!(-1.0, 0.5, 0.25)] {
+                    if try_look_at(eye, center, up).is_err() {
+                        continue;
+                    }
+
+                    let expected = look_at(&eye, &center, &up);
+                    let actual = look_at_v(eye, center, up);
+                    for c in 0..4 {
+                        for r in 0..4 {
+                            assert_eq!(actual[c][r].to_bits(), expected[c][r].to_bits());
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_look_to() {
+        let thresh = Mat4::ones() * 1e-4;
+
+        for eye in GenVec3::new(-2, 2) {
+            for center in GenVec3::new(-2, 2) {
+                for &up in &[Vec3::z(), vec3!(0.0, 1.0, 1.0), vec3!(-1.0, 0.5, 2.0)] {
+                    let dir = center - eye;
+                    let expected = try_look_at(eye, center, up);
+                    assert_eq!(try_look_to(eye, dir, up).is_ok(), expected.is_ok());
+
+                    if let Ok(expected) = expected {
+                        let actual = look_to(eye, dir, up);
+                        assert_within_threshold!(
+                            actual,
+                            expected,
+                            thresh,
+                            "Failure with eye = {:?}, center = {:?}, up = {:?}.",
+                            eye,
+                            center,
+                            up
+                        );
+                        assert_within_threshold!(look_to(eye, dir * 3.0, up), actual, thresh);
+                        assert_eq!(try_look_to(eye, dir, up), Ok(actual));
+                    }
+                }
+            }
+        }
+
+        assert_eq!(
+            try_look_to(Vec3::ones(), Vec3::zeros(), Vec3::z()),
+            Err(LookAtError::EyeAtCenter)
+        );
+        assert_eq!(
+            try_look_to(Vec3::ones(), vec3!(0.0, 0.0, -2.0), Vec3::z()),
+            Err(LookAtError::UpParallelToFacing)
+        );
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic]
+    fn test_look_at_degenerate_debug() {
+        look_at(&Vec3::z(), &Vec3::zeros(), &Vec3::z());
+    }
+
+    #[test]
+    fn test_try_ortho_frustum() {
+        use ProjectionError::*;
+
+        for &(l, r, b, t, n, f) in &[
+            (-1.0, 1.0, -1.0, 1.0, 0.1, 10.0),
+            (-2.0, 3.0, -0.5, 1.5, 1.0, 100.0),
+            (0.0, 4.0, 0.0, 3.0, 2.0, 2.5),
+        ] {
+            assert_eq!(try_ortho(l, r, b, t, n, f), Ok(ortho(l, r, b, t, n, f)));
+            assert_eq!(try_frustum(l, r, b, t, n, f), Ok(frustum(l, r, b, t, n, f)));
+
+            assert_eq!(try_ortho(l, l, b, t, n, f), Err(ZeroWidth));
+            assert_eq!(try_ortho(l, r, t, t, n, f), Err(ZeroHeight));
+            assert_eq!(try_ortho(l, r, b, t, f, f), Err(ZeroDepth));
+            assert!(try_ortho(l, r, b, t, -n, f).is_ok());
+
+            assert_eq!(try_frustum(r, r, b, t, n, f), Err(ZeroWidth));
+            assert_eq!(try_frustum(l, r, b, b, n, f), Err(ZeroHeight));
+            assert_eq!(try_frustum(l, r, b, t, n, n), Err(ZeroDepth));
+            assert_eq!(try_frustum(l, r, b, t, 0.0, f), Err(NonPositiveNear));
+            assert_eq!(try_frustum(l, r, b, t, -n, f), Err(NonPositiveNear));
+        }
+
+        assert_eq!(ZeroDepth.to_string(), "view volume has zero depth");
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic]
+    fn test_frustum_degenerate_debug() {
+        frustum(-1.0, 1.0, -1.0, 1.0, 0.0, 1.0);
+    }
+
+    #[test]
+    fn test_zero_to_one_projections() {
+        let (l, r, b, t, n, f) = (-2.0, 3.0, -1.0, 1.5, 0.5, 20.0);
+        let fovy = Angle::from_degrees(70.0);
+        let aspect = 1.6;
+
+        let pairs = [
+            (ortho(l, r, b, t, n, f), ortho_zo(l, r, b, t, n, f)),
+            (frustum(l, r, b, t, n, f), frustum_zo(l, r, b, t, n, f)),
+            (perspective(fovy, aspect, n, f), perspective_zo(fovy, aspect, n, f)),
+        ];
+
+        for (gl, zo) in pairs.iter() {
+            for xy in GenVec2::new(-2, 2) {
+                for &depth in &[n, 1.0, 5.0, f] {
+                    let p = (xy * depth * 0.25).extend(-depth).homogeneous();
+                    let p_gl = (*gl * p).homogenize();
+                    let p_zo = (*zo * p).homogenize();
+
+                    assert_within_threshold!(p_zo.truncate(), p_gl.truncate(), Vec2::ones() * 1e-5);
+                    assert_within_threshold!(p_zo.z, (p_gl.z + 1.0) / 2.0, 1e-5);
+
+                    if depth == n {
+                        assert_within_threshold!(p_zo.z, 0.0, 1e-5);
+                    } else if depth == f {
+                        assert_within_threshold!(p_zo.z, 1.0, 1e-5);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_convert_depth_range() {
+        let thresh = Mat4::ones() * 1e-5;
+
+        for &(n, f) in &[(0.1, 100.0), (1.0, 10.0), (0.5, 5000.0)] {
+            for deg in (30..=120).step_by(30) {
+                let fovy = Angle::from_degrees(deg as f32);
+                for &aspect in &[0.75, 1.0, 16.0 / 9.0] {
+                    assert_within_threshold!(
+                        convert_depth_range(perspective(fovy, aspect, n, f)),
+                        perspective_zo(fovy, aspect, n, f),
+                        thresh
+                    );
+                }
+            }
+
+            let (l, r, b, t) = (-2.0, 3.0, -1.0, 1.5);
+            assert_within_threshold!(
+                convert_depth_range(ortho(l, r, b, t, n, f)),
+                ortho_zo(l, r, b, t, n, f),
+                thresh
+            );
+            assert_within_threshold!(
+                convert_depth_range(frustum(l, r, b, t, n, f)),
+                frustum_zo(l, r, b, t, n, f),
+                thresh
+            );
+        }
+    }
+
+    #[test]
+    fn test_reversed_projections() {
+        let (l, r, b, t, n, f) = (-2.0, 3.0, -1.0, 1.5, 0.5, 20.0);
+        let fovy = Angle::from_degrees(70.0);
+        let aspect = 1.6;
+
+        let pairs = [
+            (ortho_zo(l, r, b, t, n, f), ortho_reversed_zo(l, r, b, t, n, f)),
+            (perspective_zo(fovy, aspect, n, f), perspective_reversed_zo(fovy, aspect, n, f)),
+        ];
+
+        for (zo, rev) in pairs.iter() {
+            for xy in GenVec2::new(-2, 2) {
+                let mut last_depth = f32::INFINITY;
+
+                for i in 0..=20 {
+                    let depth = n + (f - n) * i as f32 / 20.0;
+                    let p = (xy * depth * 0.25).extend(-depth).homogeneous();
+                    let p_zo = (*zo * p).homogenize();
+                    let p_rev = (*rev * p).homogenize();
+
+                    let xy_thresh = Vec2::ones() * 1e-5;
+                    assert_within_threshold!(p_rev.truncate(), p_zo.truncate(), xy_thresh);
+                    assert_within_threshold!(p_rev.z, 1.0 - p_zo.z, 1e-5);
+                    assert!(p_rev.z < last_depth);
+                    last_depth = p_rev.z;
+
+                    if i == 0 {
+                        assert_within_threshold!(p_rev.z, 1.0, 1e-5);
+                    } else if i == 20 {
+                        assert_within_threshold!(p_rev.z, 0.0, 1e-5);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_perspective_from_tangents() {
+        let (n, f) = (0.1, 50.0);
+
+        for deg in (30..=120).step_by(15) {
+            let fovy = Angle::from_degrees(deg as f32);
+            let t = (fovy / 2.0).tan();
+
+            for &aspect in &[0.5, 1.0, 1.5, 16.0 / 9.0] {
+                let ta = aspect * t;
+                assert_eq!(
+                    perspective_from_tangents(-ta, ta, t, -t, n, f),
+                    perspective(fovy, aspect, n, f)
+                );
+            }
+        }
+
+        let (tl, tr, tu, td) = (-0.9, 1.3, 1.1, -0.7);
+        let expected = frustum(n * tl, n * tr, n * td, n * tu, n, f);
+        assert_within_threshold!(
+            perspective_from_tangents(tl, tr, tu, td, n, f),
+            expected,
+            Mat4::ones() * 1e-5
+        );
+
+        let m = perspective_from_tangents(tl, tr, tu, td, n, f);
+        let corner = (m * vec3!(tl, tu, -1.0).homogeneous()).homogenize();
+        assert_within_threshold!(corner.truncate(), vec2!(-1.0, 1.0), Vec2::ones() * 1e-5);
+    }
+
+    #[test]
+    fn test_oblique_near_plane() {
+        let fovy = Angle::from_degrees(60.0);
+        let (n, f) = (0.5, 20.0);
+        let cases = [
+            (perspective(fovy, 1.5, n, f), false),
+            (frustum(-0.4, 0.6, -0.3, 0.5, n, f), false),
+            (ortho(-2.0, 2.0, -1.5, 1.5, n, f), false),
+            (perspective_zo(fovy, 1.5, n, f), true),
+            (ortho_zo(-2.0, 2.0, -1.5, 1.5, n, f), true),
+        ];
+        let planes = [
+            vec4!(0.0, 0.0, -1.0, -2.0),
+            vec4!(0.0, 0.5, -1.0, -3.0),
+            vec4!(-0.3, 0.2, -1.0, -1.0),
+        ];
+        let steps = [-1.0, -0.5, 0.0, 0.5, 1.0];
+
+        for &(proj, zo) in cases.iter() {
+            let near_depth = if zo { 0.0 } else { -1.0 };
+            let inv = proj.inverse();
+
+            for &plane in planes.iter() {
+                let m = if zo {
+                    oblique_near_plane_zo(proj, plane)
+                } else {
+                    oblique_near_plane(proj, plane)
+                };
+
+                assert_eq!(m.get_row(0), proj.get_row(0));
+                assert_eq!(m.get_row(1), proj.get_row(1));
+                assert_eq!(m.get_row(3), proj.get_row(3));
+
+                for &x in steps.iter() {
+                    for &y in steps.iter() {
+                        // A point on the clip plane, found along the view ray through (x, y).
+                        let far = (inv * vec4!(x, y, 1.0, 1.0)).homogenize();
+                        let near = (inv * vec4!(x, y, near_depth, 1.0)).homogenize();
+                        let (d_near, d_far) =
+                            (plane.dot(&near.homogeneous()), plane.dot(&far.homogeneous()));
+                        if d_near * d_far >= 0.0 {
+                            continue;
+                        }
+
+                        let t = d_near / (d_near - d_far);
+                        let on_plane = near + (far - near) * t;
+                        let z = (m * on_plane.homogeneous()).homogenize().z;
+                        assert_within_threshold!(z, near_depth, 1e-4, "z = {}", z);
+
+                        // Points of the original volume beyond the plane stay within depth range.
+                        for &depth in steps.iter() {
+                            let depth = if zo { (depth + 1.0) / 2.0 } else { depth };
+                            let v = (inv * vec4!(x, y, depth, 1.0)).homogenize();
+                            if plane.dot(&v.homogeneous()) <= 0.0 {
+                                continue;
+                            }
+
+                            let z = (m * v.homogeneous()).homogenize().z;
+                            assert!(z >= near_depth - 1e-4 && z <= 1.0 + 1e-4, "z = {}", z);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_viewport() {
+        let (x, y, w, h) = (10.0, 20.0, 640.0, 480.0);
+
+        for &(dn, df) in &[(0.0, 1.0), (0.25, 0.75), (1.0, 0.0)] {
+            let vp = viewport(x, y, w, h, dn, df);
+
+            for &cx in &[-1.0, 1.0] {
+                for &cy in &[-1.0, 1.0] {
+                    for &cz in &[-1.0, 1.0] {
+                        let win = (vp * vec3!(cx, cy, cz).homogeneous()).homogenize();
+                        let expected = vec3!(
+                            if cx < 0.0 { x } else { x + w },
+                            if cy < 0.0 { y } else { y + h },
+                            if cz < 0.0 { dn } else { df }
+                        );
+                        assert_approx_eq!(win, expected, "Expected {:?}, got {:?}", expected, win);
+                    }
+                }
+            }
+        }
+
+        assert_eq!(viewport_rect(x, y, w, h), viewport(x, y, w, h, 0.0, 1.0));
+
+        // The full pipeline sends the view direction to the viewport center.
+        let view = look_at(&vec3!(3.0, 3.0, 3.0), &Vec3::zeros(), &Vec3::z());
+        let proj = perspective(Angle::from_degrees(60.0), w / h, 0.1, 100.0);
+        let m = viewport_rect(x, y, w, h) * proj * view;
+        let win = (m * Vec3::zeros().homogeneous()).homogenize();
+        let center = vec2!(x + w / 2.0, y + h / 2.0);
+        assert_within_threshold!(win.truncate(), center, Vec2::ones() * 1e-3);
+        assert!(win.z > 0.0 && win.z < 1.0);
+    }
+
+    #[test]
+    fn test_project_unproject() {
+        let vp = (10.0, 20.0, 800.0, 600.0);
+        let view = look_at(&vec3!(4.0, -6.0, 3.0), &Vec3::zeros(), &Vec3::z());
+        let model = Transform::new()
+            .rotate(vec3!(1.0, 2.0, 3.0), Angle::from_degrees(30.0))
+            .translate(vec3!(0.5, -0.5, 0.25))
+            .finish();
+        let modelview = view * model;
+        let projections = [
+            perspective(Angle::from_degrees(60.0), 4.0 / 3.0, 0.5, 50.0),
+            ortho(-4.0, 4.0, -3.0, 3.0, 0.5, 50.0),
+        ];
+
+        for proj in projections.iter() {
+            for p in GenVec3::new(-2, 2) {
+                let p = p * 0.5;
+                let window = project(p, &modelview, proj, vp).unwrap();
+                let expected = (viewport_rect(vp.0, vp.1, vp.2, vp.3) * proj * modelview
+                    * p.homogeneous())
+                .homogenize();
+                assert_within_threshold!(window, expected, Vec3::ones() * 1e-3);
+
+                let back = unproject(window, &modelview, proj, vp).unwrap();
+                assert_within_threshold!(
+                    back,
+                    p,
+                    Vec3::ones() * 1e-3,
+                    "Failure with p = {:?}: got {:?}",
+                    p,
+                    back
+                );
+            }
+        }
+
+        // The camera position homogenizes with w = 0 under a perspective projection.
+        let eye = vec3!(4.0, -6.0, 3.0);
+        assert_eq!(project(eye, &view, &projections[0], vp), None);
+
+        let singular = scale(vec3!(1.0, 0.0, 1.0));
+        assert_eq!(unproject(Vec3::zeros(), &singular, &projections[0], vp), None);
+        let subnormal = scale(vec3!(1.0, 1e-39, 1.0));
+        assert_eq!(unproject(Vec3::zeros(), &subnormal, &projections[0], vp), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "[transform::oblique_near_plane] Projection matrix is singular")]
+    fn test_oblique_near_plane_singular() {
+        oblique_near_plane(Mat4::zeros(), vec4!(0.0, 0.0, -1.0, -1.0));
+    }
+
+    #[test]
+    fn test_pick_ray() {
+        let thresh = Vec3::ones() * 1e-3;
+        let vp = (10.0, 20.0, 800.0, 600.0);
+        let (w, h) = (vp.2, vp.3);
+        let aspect = w / h;
+        let eye = vec3!(4.0, -6.0, 3.0);
+        let center = vec3!(0.5, 0.5, 0.0);
+        let view = look_at(&eye, &center, &Vec3::z());
+        let inv_view = view.inverse();
+        let look_dir = (center - eye).unit();
+        let screen_center = vec2!(vp.0 + w / 2.0, vp.1 + h / 2.0);
+
+        let fovy = Angle::from_degrees(60.0);
+        let tan = (fovy / 2.0).tan();
+        let proj = perspective(fovy, aspect, 0.5, 50.0);
+
+        let (origin, dir) = pick_ray(screen_center, vp, &view, &proj).unwrap();
+        assert_within_threshold!(dir, look_dir, thresh);
+        assert_within_threshold!((origin - eye).unit(), look_dir, thresh);
+
+        for &(sx, sy) in &[(-1.0, -1.0), (-1.0, 1.0), (1.0, -1.0), (1.0, 1.0)] {
+            let screen = vec2!(vp.0 + (sx + 1.0) * w / 2.0, vp.1 + (sy + 1.0) * h / 2.0);
+            let (origin, dir) = pick_ray(screen, vp, &view, &proj).unwrap();
+
+            let corner = vec3!(sx * aspect * tan, sy * tan, -1.0).unit();
+            let expected = (inv_view * corner.extend(0.0)).truncate();
+            assert_within_threshold!(dir, expected, thresh, "{:?} vs {:?}", dir, expected);
+            assert_within_threshold!((origin - eye).unit(), expected, thresh);
+        }
+
+        let proj = ortho(-4.0, 4.0, -3.0, 3.0, 0.5, 50.0);
+        let mut origins = Vec::new();
+        for &(sx, sy) in &[(0.0, 0.0), (-1.0, -1.0), (1.0, 0.5), (0.25, 1.0)] {
+            let screen = vec2!(vp.0 + (sx + 1.0) * w / 2.0, vp.1 + (sy + 1.0) * h / 2.0);
+            let (origin, dir) = pick_ray(screen, vp, &view, &proj).unwrap();
+            assert_within_threshold!(dir, look_dir, thresh);
+
+            let offset = vec3!(sx * 4.0, sy * 3.0, -0.5);
+            let expected = (inv_view * offset.homogeneous()).homogenize();
+            assert_within_threshold!(origin, expected, thresh);
+            origins.push(origin);
+        }
+        assert!(!origins[0].approx_eq(origins[1]));
+    }
+
+    #[test]
+    fn test_frustum_planes() {
+        let (l, r, b, t, n, f) = (-2.0, 3.0, -1.0, 1.5, 0.5, 20.0);
+        let thresh = Vec4::ones() * 1e-5;
+
+        let expected = [
+            vec4!(1.0, 0.0, 0.0, -l),
+            vec4!(-1.0, 0.0, 0.0, r),
+            vec4!(0.0, 1.0, 0.0, -b),
+            vec4!(0.0, -1.0, 0.0, t),
+            vec4!(0.0, 0.0, -1.0, -n),
+            vec4!(0.0, 0.0, 1.0, f),
+        ];
+        let planes = [
+            frustum_planes(&ortho(l, r, b, t, n, f)),
+            frustum_planes_zo(&ortho_zo(l, r, b, t, n, f)),
+        ];
+        for planes in planes.iter() {
+            for (plane, expected) in planes.iter().zip(expected.iter()) {
+                assert_within_threshold!(*plane, *expected, thresh);
+            }
+        }
+
+        let view = look_at(&vec3!(4.0, -6.0, 3.0), &vec3!(0.5, 0.5, 0.0), &Vec3::z());
+        let fovy = Angle::from_degrees(60.0);
+        let cases = [
+            (perspective(fovy, 1.5, n, f), false),
+            (frustum(l * 0.1, r * 0.1, b * 0.1, t * 0.1, n, f), false),
+            (ortho(l, r, b, t, n, f), false),
+            (perspective_zo(fovy, 1.5, n, f), true),
+            (ortho_zo(l, r, b, t, n, f), true),
+        ];
+
+        for &(proj, zo) in cases.iter() {
+            let proj_view = proj * view;
+            let inv = proj_view.inverse();
+            let planes = if zo {
+                frustum_planes_zo(&proj_view)
+            } else {
+                frustum_planes(&proj_view)
+            };
+
+            for plane in planes.iter() {
+                assert_within_threshold!(plane.truncate().length(), 1.0, 1e-5);
+            }
+
+            let (z_near, z_far) = if zo { (0.0, 1.0) } else { (-1.0, 1.0) };
+            let z_mid = (z_near + z_far) / 2.0;
+            let ndc_to_world = |x: f32, y: f32, z: f32| (inv * vec4!(x, y, z, 1.0)).homogenize();
+
+            for &x in &[-0.9, 0.0, 0.9] {
+                for &y in &[-0.9, 0.0, 0.9] {
+                    for &z in &[z_near + 0.01, z_mid, z_far - 0.01] {
+                        let p = ndc_to_world(x, y, z).homogeneous();
+                        assert!(planes.iter().all(|plane| plane.dot(&p) > 0.0));
+                    }
+                }
+            }
+
+            // Points just outside each face, in the same order as the planes.
+            let outside = [
+                ndc_to_world(-1.1, 0.0, z_mid),
+                ndc_to_world(1.1, 0.0, z_mid),
+                ndc_to_world(0.0, -1.1, z_mid),
+                ndc_to_world(0.0, 1.1, z_mid),
+                ndc_to_world(0.0, 0.0, z_near - 0.05),
+                ndc_to_world(0.0, 0.0, z_far + 0.01),
+            ];
+            for (i, (plane, p)) in planes.iter().zip(outside.iter()).enumerate() {
+                let dist = plane.dot(&p.homogeneous());
+                assert!(dist < 0.0, "Plane {} had distance {}", i, dist);
+            }
+        }
+    }
+
+    #[test]
+    fn test_ortho2d() {
+        assert_eq!(ortho2d(-2.0, 3.0, -1.0, 4.0), ortho(-2.0, 3.0, -1.0, 4.0, -1.0, 1.0));
+
+        for &(w, h) in &[(800.0, 600.0), (1920.0, 1080.0), (1.0, 1.0), (333.0, 77.0)] {
+            for &y_down in &[false, true] {
+                let m = ortho2d_pixel(w, h, y_down);
+                let ndc = |x: f32, y: f32| (m * vec3!(x, y, 0.0).homogeneous()).homogenize();
+                let flip = if y_down { -1.0 } else { 1.0 };
+
+                assert_eq!(ndc(0.0, 0.0), vec3!(-1.0, -flip, 0.0));
+                assert_eq!(ndc(w, 0.0), vec3!(1.0, -flip, 0.0));
+                assert_eq!(ndc(0.0, h), vec3!(-1.0, flip, 0.0));
+                assert_eq!(ndc(w, h), vec3!(1.0, flip, 0.0));
+                assert_eq!(ndc(w / 2.0, h / 2.0), vec3!(0.0, 0.0, 0.0));
+            }
+        }
+    }
+
+    #[test]
+    fn test_fit_ortho() {
+        let min = vec3!(-2.0, -1.0, -20.0);
+        let max = vec3!(3.0, 1.5, -0.5);
+        assert_eq!(ortho_from_aabb(min, max), ortho(-2.0, 3.0, -1.0, 1.5, 0.5, 20.0));
+
+        let views = [
+            Mat4::identity(),
+            look_to(vec3!(10.0, -5.0, 8.0), vec3!(-1.0, 0.5, -1.0), Vec3::z()),
+            look_at(&vec3!(0.0, 0.0, 50.0), &Vec3::zeros(), &Vec3::y()),
+        ];
+        let point_sets: [Vec<Vec3>; 4] = [
+            GenVec3::new(-2, 2).collect(),
+            vec![vec3!(1.0, 2.0, 3.0)],
+            vec![vec3!(0.0, 0.0, 0.0), vec3!(1.0, 0.0, 0.0), vec3!(0.0, 1.0, 0.0)],
+            vec![vec3!(1.0e4, -2.0e4, 3.0e3), vec3!(1.0e4, -2.0e4, 3.0e3)],
+        ];
+
+        for view in views.iter() {
+            for points in point_sets.iter() {
+                let proj = fit_ortho(points, view).unwrap();
+                assert!(proj.determinant() != 0.0);
+
+                for &p in points.iter() {
+                    let ndc = (proj * view * p.homogeneous()).homogenize();
+                    let inside = |c: f32| c.abs() <= 1.0 + 1e-4;
+                    assert!(inside(ndc.x) && inside(ndc.y) && inside(ndc.z), "{:?}", ndc);
+                }
+            }
+        }
+
+        assert_eq!(fit_ortho(&[], &Mat4::identity()), None);
+    }
+
+    #[test]
+    fn test_linearize_depth() {
+        let fovy = Angle::from_degrees(60.0);
+
+        for &(n, f) in &[(0.1, 100.0), (1.0, 10.0), (0.5, 5000.0), (100.0, 1.0e6)] {
+            let gl = perspective(fovy, 1.5, n, f);
+            let zo = perspective_zo(fovy, 1.5, n, f);
+            let rev = perspective_reversed_zo(fovy, 1.5, n, f);
+
+            for &t in &[0.0, 0.001, 0.1, 0.5, 0.9, 1.0] {
+                let d: f32 = n + (f - n) * t;
+                let p = vec3!(0.3, -0.2, -d).homogeneous();
+                // Non-linear depth loses precision quickly with distance, so allow a relative
+                // error.
+                let thresh = d * 1e-3;
+
+                let ndc_gl = (gl * p).homogenize().z;
+                let ndc_zo = (zo * p).homogenize().z;
+                let ndc_rev = (rev * p).homogenize().z;
+
+                assert_within_threshold!(linearize_depth(ndc_gl, n, f), d, thresh);
+                assert_within_threshold!(linearize_depth_zo(ndc_zo, n, f), d, thresh);
+                assert_within_threshold!(eye_z_from_ndc(ndc_gl, &gl), -d, thresh);
+                assert_within_threshold!(eye_z_from_ndc(ndc_zo, &zo), -d, thresh);
+                assert_within_threshold!(eye_z_from_ndc(ndc_rev, &rev), -d, thresh);
+
+                assert_within_threshold!(delinearize_depth(d, n, f), ndc_gl, 1e-5);
+                assert_within_threshold!(delinearize_depth_zo(d, n, f), ndc_zo, 1e-5);
+            }
+        }
+    }
+
+    #[test]
+    fn test_decompose_projections() {
+        for deg in (10..=170).step_by(20) {
+            let fovy = Angle::from_degrees(deg as f32);
+
+            for &aspect in &[0.5, 1.0, 16.0 / 9.0, 3.0] {
+                for &(n, f) in &[(0.1, 100.0), (1.0, 10.0), (1.0e-4, 50.0), (0.5, 1.0e4)] {
+                    // See the precision note on `decompose_perspective`.
+                    let far_thresh = (f * f32::EPSILON * (f / n) * 2.0).max(f * 1e-5);
+
+                    let gl = decompose_perspective(&perspective(fovy, aspect, n, f)).unwrap();
+                    let zo = decompose_perspective_zo(&perspective_zo(fovy, aspect, n, f)).unwrap();
+                    let rev = perspective_reversed_zo(fovy, aspect, n, f);
+                    let rev = decompose_perspective_reversed_zo(&rev).unwrap();
+
+                    let cases = [(gl, far_thresh), (zo, far_thresh), (rev, f * 1e-5)];
+                    for &(params, far_thresh) in cases.iter() {
+                        assert_within_threshold!(params.fovy, fovy, Angle::from_degrees(1e-3));
+                        assert_within_threshold!(params.aspect_xy, aspect, 1e-5);
+                        assert_within_threshold!(params.near, n, n * 1e-5);
+                        assert_within_threshold!(params.far, f, far_thresh);
+                    }
+                }
+            }
+        }
+
+        for &(l, r, b, t, n, f) in &[
+            (-2.0, 3.0, -1.0, 1.5, 0.5, 20.0),
+            (0.0, 800.0, 600.0, 0.0, -1.0, 1.0),
+            (-1.0e3, 1.0e3, -5.0e2, 5.0e2, 1.0, 1.0e4),
+        ] {
+            let params = decompose_ortho(&ortho(l, r, b, t, n, f)).unwrap();
+            let thresh = |x: f32| 1e-5 * x.abs().max(1.0);
+            assert_within_threshold!(params.left, l, thresh(l));
+            assert_within_threshold!(params.right, r, thresh(r));
+            assert_within_threshold!(params.bottom, b, thresh(b));
+            assert_within_threshold!(params.top, t, thresh(t));
+            let depth_thresh = 4.0 * f32::EPSILON * (n.abs() + f.abs());
+            assert_within_threshold!(params.near, n, depth_thresh.max(thresh(n)));
+            assert_within_threshold!(params.far, f, depth_thresh.max(thresh(f)));
+        }
+
+        let persp = perspective(Angle::from_degrees(60.0), 1.5, 0.1, 100.0);
+        let orth = ortho(-2.0, 3.0, -1.0, 1.5, 0.5, 20.0);
+        let fovy = Angle::from_degrees(60.0);
+        assert_eq!(decompose_perspective(&perspective_reversed_zo(fovy, 1.5, 0.1, 100.0)), None);
+        assert_eq!(decompose_perspective(&perspective_infinite(fovy, 1.5, 0.1)), None);
+        assert_eq!(decompose_perspective_zo(&perspective_reversed_zo(fovy, 1.5, 0.1, 100.0)), None);
+        assert_eq!(decompose_perspective_reversed_zo(&persp), None);
+        assert_eq!(decompose_perspective_reversed_zo(&perspective_zo(fovy, 1.5, 0.1, 100.0)), None);
+        let infinite = perspective_infinite_reversed_zo(fovy, 1.5, 0.1);
+        assert_eq!(decompose_perspective_reversed_zo(&infinite), None);
+        assert_eq!(decompose_perspective(&orth), None);
+        assert_eq!(decompose_ortho(&persp), None);
+        assert_eq!(decompose_perspective(&(persp * shear_x(0.5, 0.0))), None);
+        assert_eq!(decompose_perspective(&frustum(-0.4, 0.6, -0.3, 0.5, 0.5, 20.0)), None);
+        assert_eq!(decompose_ortho(&(orth * rotate_z(Angle::from_degrees(30.0)))), None);
+    }
+
+    #[test]
+    fn test_perspective_fov() {
+        let thresh = Mat4::ones() * 1e-5;
+        let (n, f) = (0.1, 100.0);
+
+        for deg in (20..=150).step_by(10) {
+            let fovy = Angle::from_degrees(deg as f32);
+
+            for &aspect in &[0.5, 1.0, 4.0 / 3.0, 16.0 / 9.0, 3.0] {
+                let expected = perspective(fovy, aspect, n, f);
+                let fovx = Angle::from_radians(2.0 * (aspect * (fovy / 2.0).tan()).atan());
+
+                assert_within_threshold!(perspective_fov(fovx, fovy, n, f), expected, thresh);
+                assert_within_threshold!(perspective_fovx(fovx, aspect, n, f), expected, thresh);
+            }
+        }
+
+        let a = Angle::from_degrees(70.0);
+        assert_eq!(perspective_fov(a, a, n, f), perspective(a, 1.0, n, f));
+    }
+
+    #[test]
+    fn test_perspective_from_focal() {
+        let thresh = Mat4::ones() * 1e-5;
+        let (n, f) = (0.1, 100.0);
+
+        for &focal in &[18.0_f32, 35.0, 50.0, 85.0, 200.0] {
+            for &(w, h) in &[(36.0, 24.0), (23.6, 15.7), (24.0, 36.0)] {
+                let fovy = Angle::from_radians(2.0 * (h / (2.0 * focal)).atan());
+                let expected = perspective(fovy, w / h, n, f);
+                let actual = perspective_from_focal(focal, w, h, n, f);
+                assert_within_threshold!(actual, expected, thresh);
+
+                let (sx, sy) = (3.0, -1.5);
+                let s = n / focal;
+                let expected = frustum(
+                    (sx - w / 2.0) * s,
+                    (sx + w / 2.0) * s,
+                    (sy - h / 2.0) * s,
+                    (sy + h / 2.0) * s,
+                    n,
+                    f,
+                );
+                let actual = perspective_from_focal_shifted(focal, w, h, sx, sy, n, f);
+                assert_within_threshold!(actual, expected, thresh);
+            }
+        }
+    }
+
+    #[test]
+    fn test_infinite_projections() {
+        let fovy = Angle::from_degrees(70.0);
+        let aspect = 1.6;
+        let n = 0.5;
+
+        let inf = perspective_infinite(fovy, aspect, n);
+        let inf_rev = perspective_infinite_reversed_zo(fovy, aspect, n);
+        let finite = perspective(fovy, aspect, n, 1.0e4);
+
+        for xy in GenVec2::new(-2, 2) {
+            let p = (xy * n * 0.25).extend(-n).homogeneous();
+            let p_inf = (inf * p).homogenize();
+            let p_rev = (inf_rev * p).homogenize();
+
+            let p_finite = (finite * p).homogenize();
+            assert_within_threshold!(p_inf.truncate(), p_finite.truncate(), Vec2::ones() * 1e-5);
+            assert_within_threshold!(p_inf.z, -1.0, 1e-5);
+            assert_within_threshold!(p_rev.z, 1.0, 1e-5);
+
+            let mut last_depth = (f32::NEG_INFINITY, f32::INFINITY);
+            for &depth in &[1.0, 10.0, 1.0e3, 1.0e6] {
+                let p = (xy * depth * 0.25).extend(-depth).homogeneous();
+                let p_inf = (inf * p).homogenize();
+                let p_rev = (inf_rev * p).homogenize();
+
+                assert!(p_inf.z > last_depth.0 && p_inf.z < 1.0);
+                assert!(p_rev.z < last_depth.1 && p_rev.z > 0.0);
+                last_depth = (p_inf.z, p_rev.z);
+            }
+
+            assert_within_threshold!(last_depth.0, 1.0, 1e-5);
+            assert_within_threshold!(last_depth.1, 0.0, 1e-5);
+        }
+    }
+
+    #[test]
+    fn test_trs() {
+        let axis = vec3!(1.0, 2.0, -1.0);
+        for t in GenVec3::new(-2, 2) {
+            for s in GenVec3::new(-2, 2) {
+                for deg in (-360..=360).step_by(60) {
+                    let a = Angle::from_degrees(deg as f32);
+                    let expected = Transform::new().scale(s).rotate(axis, a).translate(t);
+                    let actual = Transform::from_trs(t, axis, a, s);
+
+                    assert_approx_eq!(
+                        actual.finish(),
+                        expected.finish(),
+                        "Failure with t = {:?}, s = {:?}, angle = {}. Expected {:?}, got {:?}.",
+                        t,
+                        s,
+                        deg,
+                        expected,
+                        actual
+                    );
+                    assert_eq!(actual.finish(), trs(t, axis, a, s));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_trs_quat() {
+        let axis = vec3!(1.0, 2.0, -1.0);
+        for t in GenVec3::new(-2, 2) {
+            for s in GenVec3::new(-2, 2) {
+                for deg in (-360..=360).step_by(60) {
+                    let q = Quaternion::from_axis_angle(axis, Angle::from_degrees(deg as f32));
+                    let q = q * Quaternion::real(2.0);
+                    let expected = translate(t) * rotation_from_quat(q) * scale(s);
+                    let actual = trs_quat(t, q, s);
+
+                    assert_approx_eq!(
+                        actual,
+                        expected,
+                        "Failure with t = {:?}, s = {:?}, q = {:?}. Expected {:?}, got {:?}.",
+                        t,
+                        s,
+                        q,
+                        expected,
+                        actual
+                    );
+                    assert_eq!(Transform::from_trs_quat(t, q, s).finish(), actual);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_decompose() {
+        let axis = vec3!(-1.0, 0.5, 2.0);
+        let scales = [
+            vec3!(1.0, 2.0, 3.0),
+            vec3!(0.5, 0.5, 0.5),
+            vec3!(-1.0, 2.0, 0.25),
+            vec3!(-3.0, 1.0, 1.0),
+        ];
+
+        for t in GenVec3::new(-2, 2) {
+            for s in scales.iter() {
+                for deg in (-150..=150).step_by(50) {
+                    let a = Angle::from_degrees(deg as f32);
+                    let d = Transform::from_trs(t, axis, a, *s).decompose().unwrap();
+
+                    assert_eq!(d.translation, t);
+                    assert_within_threshold!(d.scale, *s, Vec3::ones() * 1e-5,
+                        "Expected scale {:?}, got {:?}", s, d.scale);
+                    assert_within_threshold!(d.rotation, rotate(axis, a), Mat4::ones() * 1e-5,
+                        "Expected rotation {:?}, got {:?}", rotate(axis, a), d.rotation);
+                    assert_within_threshold!(
+                        d.recompose().finish(),
+                        trs(t, axis, a, *s),
+                        Mat4::ones() * 1e-5
+                    );
+                }
+            }
+        }
+
+        let d = Transform::new()
+            .scale(vec3!(-1.0, -1.0, 2.0))
+            .decompose()
+            .unwrap();
+        assert_approx_eq!(d.scale, vec3!(1.0, 1.0, 2.0));
+        assert_approx_eq!(d.rotation, rotate_z(Angle::half_circle()));
+    }
+
+    #[test]
+    fn test_decompose_fails() {
+        assert!(Transform::new().shear_x(0.5, 0.0).decompose().is_none());
+        assert!(Transform::new().scale(vec3!(1.0, 0.0, 1.0)).decompose().is_none());
+        assert!(Transform::new()
+            .arbitrary(perspective(Angle::from_degrees(60.0), 1.0, 0.1, 10.0))
+            .decompose()
+            .is_none());
+    }
+
+    #[test]
+    fn test_determinant() {
+        for deg in (-360..=360).step_by(30) {
+            let a = Angle::from_degrees(deg as f32);
+            let t = Transform::new().rotate(vec3!(1.0, 2.0, 3.0), a).translate(Vec3::ones());
+            assert_approx_eq!(t.determinant(), 1.0, "det = {}", t.determinant());
+            assert!(t.is_invertible());
+
+            let t = t.reflect(vec3!(1.0, -1.0, 0.0));
+            assert_approx_eq!(t.determinant(), -1.0, "det = {}", t.determinant());
+            assert!(t.is_invertible());
+        }
+
+        for s in -4..=4 {
+            let s = s as f32 * 0.5;
+            let t = Transform::new().uniform_scale(s).rotate_x(Angle::from_degrees(30.0));
+            assert_approx_eq!(t.determinant(), s * s * s, "det = {}", t.determinant());
+            assert_eq!(t.is_invertible(), s != 0.0);
+        }
+
+        let t = Transform::new().scale(vec3!(1.0, 0.0, 2.0)).translate(Vec3::ones());
+        assert_eq!(t.determinant(), 0.0);
+        assert!(!t.is_invertible());
+
+        let p = perspective(Angle::from_degrees(90.0), 1.0, 1.0, 3.0);
+        assert_approx_eq!(Transform::from(p).determinant(), p.determinant());
+    }
+
+    #[test]
+    fn test_rotate_about_line() {
+        let thresh = Vec3::ones() * 1e-4;
+
+        // Distance from `p` to the line through `point` with unit direction `dir`.
+        let dist = |p: Vec3, point: Vec3, dir: Vec3| {
+            let d = p - point;
+            (d - dir * dir.dot(&d)).length()
+        };
+
+        for point in GenVec3::new(-2, 2) {
+            for dir in GenVec3::new(-1, 1) {
+                if dir == Vec3::zeros() {
+                    continue;
+                }
+
+                for deg in (-180..=180).step_by(45) {
+                    let a = Angle::from_degrees(deg as f32);
+                    let m = rotate_about_line(point, dir, a);
+
+                    assert_within_threshold!(m, rotate_about(point, dir, a), Mat4::ones() * 1e-4);
+                    assert_eq!(Transform::new().rotate_about_line(point, dir, a).finish(), m);
+
+                    for &s in &[-2.0, 0.0, 1.5] {
+                        let on_line = point + s * dir;
+                        let fixed = (m * on_line.homogeneous()).homogenize();
+                        assert_within_threshold!(
+                            fixed,
+                            on_line,
+                            thresh,
+                            "Failure with point = {:?}, dir = {:?}, angle = {}. Got {:?}.",
+                            point,
+                            dir,
+                            deg,
+                            fixed
+                        );
+                    }
+
+                    let unit = dir.unit();
+                    for p in GenVec3::new(-1, 1) {
+                        let mapped = (m * p.homogeneous()).homogenize();
+                        assert_within_threshold!(
+                            dist(mapped, point, unit),
+                            dist(p, point, unit),
+                            1e-4
+                        );
+                    }
+                }
+            }
+        }
+
+        let a = Angle::from_degrees(90.0);
+        assert_eq!(rotate_about_line(Vec3::zeros(), Vec3::z(), a), rotate(Vec3::z(), a));
+
+        let m = rotate_about_line(Vec3::x(), Vec3::z(), a);
+        let origin = (m * Vec3::zeros().homogeneous()).homogenize();
+        assert_within_threshold!(origin, vec3!(1.0, -1.0, 0.0), thresh);
+        assert!(!m.approx_eq(rotate(Vec3::z(), a)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_rotate_about_line_zero() {
+        rotate_about_line(Vec3::ones(), Vec3::zeros(), Angle::from_degrees(30.0));
+    }
+
+    #[test]
+    fn test_billboards() {
+        let thresh = Vec3::ones() * 1e-4;
+        let mat_thresh = Mat3::ones() * 1e-4;
+
+        for object in GenVec3::new(-1, 1) {
+            for camera in GenVec3::new(-2, 2) {
+                let to_camera = camera - object;
+                if to_camera == Vec3::zeros() {
+                    continue;
+                }
+
+                let up = Vec3::z();
+                if to_camera.unit().cross(&up).length() > 1e-3 {
+                    let m = billboard_spherical(object, camera, up);
+                    assert_within_threshold!(m[2].truncate(), to_camera.unit(), thresh);
+                    assert_eq!(m[3], object.homogeneous());
+                    assert_within_threshold!(m[1].truncate().dot(&up).min(0.0), 0.0, 1e-4);
+                    let r = m.truncate();
+                    assert_within_threshold!(r.transpose() * r, Mat3::identity(), mat_thresh);
+                    assert_within_threshold!(m.determinant(), 1.0, 1e-4);
+                }
+
+                for &axis in &[Vec3::z(), vec3!(0.0, 2.0, 0.0), vec3!(1.0, 1.0, 1.0)] {
+                    let m = billboard_cylindrical(object, camera, axis);
+                    assert_eq!(m[1].truncate(), axis.unit());
+                    assert_eq!(m[3], object.homogeneous());
+                    let r = m.truncate();
+                    assert_within_threshold!(r.transpose() * r, Mat3::identity(), mat_thresh);
+                    assert_within_threshold!(m.determinant(), 1.0, 1e-4);
+
+                    let z = m[2].truncate();
+                    assert!(z.dot(&to_camera) >= -1e-4);
+                }
+            }
+        }
+
+        // Camera (nearly and exactly) directly above the object.
+        let object = vec3!(1.0, 2.0, 0.0);
+        for &offset in &[vec3!(1e-4, 0.0, 10.0), vec3!(0.0, 0.0, 10.0)] {
+            let m = billboard_cylindrical(object, object + offset, Vec3::z());
+            assert_eq!(m[1].truncate(), Vec3::z());
+            assert!(!m[2].x.is_nan() && !m[2].y.is_nan());
+            assert_within_threshold!(m[2].truncate().length(), 1.0, 1e-5);
+            assert_eq!(m[2].z, 0.0);
+        }
+    }
 
-    mat[0][0] = 2.0 / (right - left);
-    mat[1][1] = 2.0 / (top - bottom);
-    mat[2][2] = -2.0 / (far - near);
+    #[test]
+    fn test_rotate_vec3() {
+        let thresh = Vec3::ones() * 1e-4;
 
-    mat[3][0] = -(right + left) / (right - left);
-    mat[3][1] = -(top + bottom) / (top - bottom);
-    mat[3][2] = -(far + near) / (far - near);
+        for axis in GenVec3::new(-1, 1) {
+            if axis == Vec3::zeros() {
+                continue;
+            }
 
-    mat
-}
+            for deg in (-360..=360).step_by(30) {
+                let a = Angle::from_degrees(deg as f32);
+                let m = rotate(axis, a);
 
-/// Construct a frustum normalization matrix.
-///
-/// The resulting frustum has its apex at the origin, and its near and far faces centered on and
-/// perpendicular to the negative _z_ axis at the specified distances. The near face has the given
-/// width and height. This volume is then mapped to the canonical viewing volume (the 2x2x2 cube
-/// centered at the origin).
-///
-/// The near and far planes are mapped to normalized _z_ coordinates -1 and 1 respectively (the
-/// OpenGL convention).
-pub fn frustum(left: f32, right: f32, bottom: f32, top: f32, near: f32, far: f32) -> Mat4 {
-    let mut mat = Mat4::zeros();
+                for v in GenVec3::new(-2, 2) {
+                    let expected = (m * v.homogeneous()).homogenize();
+                    let actual = rotate_vec3(axis, a, v);
+                    assert_within_threshold!(
+                        actual,
+                        expected,
+                        thresh,
+                        "Failure with axis = {:?}, angle = {}, v = {:?}. Expected {:?}, got {:?}.",
+                        axis,
+                        deg,
+                        v,
+                        expected,
+                        actual
+                    );
+                }
+            }
+        }
 
-    mat[0][0] = (2.0 * near) / (right - left);
-    mat[1][1] = (2.0 * near) / (top - bottom);
+        assert!(rotate_vec3(Vec3::zeros(), Angle::from_degrees(30.0), Vec3::x()).x.is_nan());
+    }
 
-    mat[2][0] = (right + left) / (right - left);
-    mat[2][1] = (top + bottom) / (top - bottom);
-    mat[2][2] = -(far + near) / (far - near);
-    mat[2][3] = -1.0;
+    #[test]
+    fn test_normal_matrix() {
+        let thresh = Mat3::ones() * 1e-4;
 
-    mat[3][2] = -(2.0 * far * near) / (far - near);
+        for axis in GenVec3::new(-1, 1) {
+            if axis == Vec3::zeros() {
+                continue;
+            }
 
-    mat
-}
+            for deg in (-180..=180).step_by(45) {
+                let a = Angle::from_degrees(deg as f32);
+                let t = Transform::new().rotate(axis, a).translate(axis * 3.0);
+                let n = t.normal_matrix().unwrap();
+                assert_within_threshold!(n, t.finish().truncate(), thresh);
+            }
+        }
 
-/// Build a perspective normalization matrix.
-///
-/// The resulting view volume is a symmetric frustum centered on the _z_ axis with its apex at the
-/// origin, near plane at _z_ = `-near`, and far plane at _z_ = `-far`. `fovy` gives the vertical
-/// field of view, with the horizontal field of view determined from this by `aspect_xy`, which is
-/// the ratio width / height of the viewport dimensions.
-///
-/// This volume is mapped to the canonical viewing volume (the 2x2x2 cube centered at the origin).
-/// The near and far planes are mapped to normalized _z_ coordinates -1 and 1 respectively.
-pub fn perspective(fovy: Angle, aspect_xy: f32, near: f32, far: f32) -> Mat4 {
-    let tan_half_fov = (fovy / 2.0).tan();
-    let mut mat = Mat4::zeros();
+        // Tangents spanning a few surfaces, with their normals.
+        let surfaces = [
+            (Vec3::x(), Vec3::y()),
+            (vec3!(1.0, 1.0, 0.0), Vec3::z()),
+            (vec3!(1.0, -2.0, 0.5), vec3!(0.0, 1.0, 3.0)),
+        ];
 
-    mat[0][0] = 1.0 / (aspect_xy * tan_half_fov);
-    mat[1][1] = 1.0 / tan_half_fov;
-    mat[2][2] = -(far + near) / (far - near);
+        for s in GenVec3::new(-2, 2) {
+            if s.x == 0.0 || s.y == 0.0 || s.z == 0.0 {
+                continue;
+            }
 
-    mat[2][3] = -1.0;
-    mat[3][2] = -(2.0 * far * near) / (far - near);
+            let model = sample_chain(s, 30.0).finish();
+            let n_mat = normal_matrix(&model).unwrap();
+            let linear = model.truncate();
 
-    mat
-}
+            for &(t1, t2) in surfaces.iter() {
+                let normal = t1.cross(&t2);
+                let normal = (n_mat * normal).unit();
+                let (t1, t2) = ((linear * t1).unit(), (linear * t2).unit());
 
-#[cfg(test)]
-mod test {
-    use super::*;
-    use crate::test_util::*;
+                assert_within_threshold!(normal.dot(&t1), 0.0, 1e-4);
+                assert_within_threshold!(normal.dot(&t2), 0.0, 1e-4);
+            }
+        }
+
+        assert_eq!(normal_matrix(&scale(vec3!(1.0, 0.0, 2.0))), None);
+
+        // Nonzero, but subnormal: the inverse overflows, which must not panic.
+        assert_eq!(normal_matrix(&scale(vec3!(1e-39, 1.0, 1.0))), None);
+        assert!(normal_matrix(&scale(vec3!(1e-30, 1.0, 1.0))).is_some());
+    }
 
     #[test]
-    fn test_translate() {
-        let test_func = |v: Vec3, offset| {
-            let expected = v + offset;
-            let v = v.homogeneous();
+    fn test_rotation_between() {
+        let thresh = Vec3::ones() * 1e-4;
+        let mat_thresh = Mat4::ones() * 1e-4;
 
-            let m = translate(offset);
-            let vt = m * v;
-            let vt = vt.homogenize();
-            assert_approx_eq!(
-                vt,
-                expected,
-                "Failure with v = {:?}, offset = {:?}. Expected {:?}, got {:?}.",
-                v,
-                offset,
-                expected,
-                vt
-            );
-        };
+        for from in GenVec3::new(-2, 2) {
+            if from == Vec3::zeros() {
+                continue;
+            }
 
-        for v in GenVec3::new(-4, 4) {
-            for o in GenVec3::new(-4, 4) {
-                test_func(v, o);
+            for to in GenVec3::new(-2, 2) {
+                if to == Vec3::zeros() {
+                    continue;
+                }
+
+                let m = rotation_between(from, to);
+                let mapped = (m * from.unit().extend(0.0)).truncate();
+                assert_within_threshold!(
+                    mapped,
+                    to.unit(),
+                    thresh,
+                    "Failure with from = {:?}, to = {:?}. Got {:?}.",
+                    from,
+                    to,
+                    mapped
+                );
+                assert_within_threshold!(m.transpose() * m, Mat4::identity(), mat_thresh);
+                assert_within_threshold!(m.determinant(), 1.0, 1e-4);
+                assert!(m.is_affine(0.0));
+
+                let t = sample_chain(from, 30.0);
+                assert_eq!(t.align(from, to), t.arbitrary(m));
             }
         }
+
+        assert_eq!(rotation_between(vec3!(1.0, 2.0, 3.0), vec3!(2.0, 4.0, 6.0)), Mat4::identity());
+
+        for &v in &[Vec3::x(), Vec3::y(), Vec3::z(), vec3!(1.0, 1.0, 1.0), vec3!(0.0, 3.0, -4.0)] {
+            let m = rotation_between(v, -v);
+            let mapped = (m * v.unit().extend(0.0)).truncate();
+            assert_within_threshold!(mapped, -v.unit(), thresh);
+            assert_within_threshold!(m.determinant(), 1.0, 1e-4);
+        }
     }
 
     #[test]
-    fn test_scale() {
-        let test_func = |v: Vec3, s: Vec3| {
-            let expected = vec3!(v[0] * s[0], v[1] * s[1], v[2] * s[2]);
-            let v = v.homogeneous();
+    #[should_panic]
+    fn test_rotation_between_zero() {
+        rotation_between(Vec3::zeros(), Vec3::x());
+    }
 
-            let m = scale(s);
-            let vt = m * v;
-            let vt = vt.homogenize();
-            assert_approx_eq!(
-                vt,
-                expected,
-                "Failure with v = {:?}, scale = {:?}. Expected {:?}, got {:?}.",
-                v,
-                s,
-                expected,
-                vt
-            );
-        };
+    #[test]
+    fn test_translate_axis() {
+        let p = Transform::new().arbitrary(perspective(Angle::from_degrees(60.0), 1.5, 0.1, 10.0));
 
-        for v in GenVec3::new(-4, 4) {
-            for s in GenVec3::new(-4, 4) {
-                test_func(v, s);
+        for v in GenVec3::new(-2, 2) {
+            let t = sample_chain(v, 30.0);
+
+            for &d in &[-2.5, 0.0, 0.75, 3.0] {
+                assert_eq!(translate_x(d), translate(vec3!(d, 0.0, 0.0)));
+                assert_eq!(translate_y(d), translate(vec3!(0.0, d, 0.0)));
+                assert_eq!(translate_z(d), translate(vec3!(0.0, 0.0, d)));
+
+                for base in &[t, p] {
+                    assert_eq!(base.translate_x(d), base.translate(vec3!(d, 0.0, 0.0)));
+                    assert_eq!(base.translate_y(d), base.translate(vec3!(0.0, d, 0.0)));
+                    assert_eq!(base.translate_z(d), base.translate(vec3!(0.0, 0.0, d)));
+                }
             }
         }
     }
 
     #[test]
-    fn test_shear() {
-        let test_func = |v: Vec3, amt1, amt2| {
-            let expectedx = vec3!(v[0], v[1] + v[0] * amt1, v[2] + v[0] * amt2);
-            let expectedy = vec3!(v[0] + v[1] * amt1, v[1], v[2] + v[1] * amt2);
-            let expectedz = vec3!(v[0] + v[2] * amt1, v[1] + v[2] * amt2, v[2]);
+    fn test_is_identity_is_affine() {
+        assert!(Transform::new().is_identity(0.0));
+        assert!(Transform::new().is_affine(0.0));
 
-            let v = v.homogeneous();
+        let a = Angle::from_degrees(30.0);
+        let round_trip = Transform::new().rotate_x(a).rotate_x(-a);
+        assert!(round_trip.is_identity(1e-5));
 
-            let mx = shear_x(amt1, amt2);
-            let my = shear_y(amt1, amt2);
-            let mz = shear_z(amt1, amt2);
+        let near = Transform::new().translate(vec3!(1e-5, 0.0, 0.0));
+        assert!(near.is_identity(1e-4));
+        assert!(!near.is_identity(1e-6));
 
-            let vtx = (mx * v).homogenize();
-            let vty = (my * v).homogenize();
-            let vtz = (mz * v).homogenize();
+        let t = sample_chain(vec3!(1.0, 2.0, 3.0), 45.0);
+        assert!(!t.is_identity(1e-3));
+        assert!(t.is_affine(0.0));
 
-            assert_approx_eq!(
-                vtx,
-                expectedx,
-                "Failure with v = {:?}, shear_x({}, {}). Expected {:?}, got {:?}.",
-                v,
-                amt1,
-                amt2,
-                expectedx,
-                vtx
+        let p = Transform::new().arbitrary(perspective(Angle::from_degrees(60.0), 1.0, 0.1, 10.0));
+        assert!(!p.is_affine(1e-3));
+        assert!(!p.is_identity(1e-3));
+    }
+
+    #[test]
+    fn test_apply_if_opt() {
+        let t = sample_chain(vec3!(1.0, -1.0, 0.5), 30.0);
+        let offset = vec3!(2.0, 0.0, -1.0);
+
+        assert_eq!(t.apply_if(true, |t| t.translate(offset)), t.translate(offset));
+        assert_eq!(t.apply_if(false, |t| t.translate(offset)), t);
+
+        assert_eq!(t.apply_opt(Some(offset), |t, o| t.translate(o)), t.translate(offset));
+        assert_eq!(t.apply_opt(None, |t, o| t.translate(o)), t);
+
+        assert_eq!(
+            Transform::new().apply_if(false, |t| t.uniform_scale(2.0)).finish(),
+            Mat4::identity()
+        );
+    }
+
+    #[test]
+    fn test_repeat() {
+        let step = Transform::new()
+            .rotate_z(Angle::from_degrees(15.0))
+            .translate(vec3!(0.0, 0.0, 0.2));
+
+        assert_eq!(step.repeat(0).finish(), Mat4::identity());
+        assert_eq!(step.repeat(1), step);
+
+        let mut naive = Mat4::identity();
+        for n in 0..=64 {
+            let actual = step.repeat(n).finish();
+            assert_within_threshold!(
+                actual,
+                naive,
+                Mat4::ones() * 1e-4,
+                "Failure with n = {}. Expected {:?}, got {:?}.",
+                n,
+                naive,
+                actual
             );
-            assert_approx_eq!(
-                vty,
-                expectedy,
-                "Failure with v = {:?}, shear_y({}, {}). Expected {:?}, got {:?}.",
-                v,
-                amt1,
-                amt2,
-                expectedy,
-                vty
+            assert_eq!(matrix_power(step.finish(), n), actual);
+
+            naive = step.finish() * naive;
+        }
+
+        let full_turn = step.repeat(24).finish();
+        assert_within_threshold!(
+            full_turn,
+            translate(vec3!(0.0, 0.0, 24.0 * 0.2)),
+            Mat4::ones() * 1e-4
+        );
+    }
+
+    #[test]
+    fn test_general_shear() {
+        for amt in -4..=4 {
+            let amt = amt as f32;
+            assert_eq!(shear(Vec3::x(), Vec3::y(), amt), shear_x(amt, 0.0));
+            assert_eq!(shear(Vec3::x(), Vec3::z(), amt), shear_x(0.0, amt));
+            assert_eq!(shear(Vec3::y(), Vec3::x(), amt), shear_y(amt, 0.0));
+            assert_eq!(shear(Vec3::y(), Vec3::z(), amt), shear_y(0.0, amt));
+            assert_eq!(shear(Vec3::z(), Vec3::x(), amt), shear_z(amt, 0.0));
+            assert_eq!(shear(Vec3::z(), Vec3::y(), amt), shear_z(0.0, amt));
+            assert_eq!(shear(2.0 * Vec3::x(), vec3!(0.0, 1.0, -1.0), amt), shear_x(amt, -amt));
+        }
+
+        let n = vec3!(1.0, 1.0, 1.0);
+        let d = vec3!(1.0, -1.0, 0.0);
+        let m = shear(n, d, 1.5);
+        assert_eq!(Transform::new().shear(n, d, 1.5).finish(), m);
+        assert_approx_eq!(m.determinant(), 1.0);
+
+        for v in GenVec3::new(-2, 2) {
+            let on_plane = v - n * (v.dot(&n) / n.dot(&n));
+            assert_within_threshold!(
+                (m * on_plane.homogeneous()).homogenize(),
+                on_plane,
+                Vec3::ones() * 1e-5
             );
-            assert_approx_eq!(
-                vtz,
-                expectedz,
-                "Failure with v = {:?}, shear_z({}, {}). Expected {:?}, got {:?}.",
-                v,
-                amt1,
-                amt2,
-                expectedz,
-                vtz
+
+            let expected = v + d * (1.5 * v.dot(&n.unit()));
+            assert_within_threshold!(
+                (m * v.homogeneous()).homogenize(),
+                expected,
+                Vec3::ones() * 1e-5
             );
-        };
+        }
+    }
 
-        for v in GenVec3::new(-4, 4) {
-            for amt1 in -4..=4 {
-                for amt2 in -4..=4 {
-                    test_func(v, amt1 as f32, amt2 as f32);
-                }
+    #[test]
+    fn test_rotate_quat() {
+        for axis in GenVec3::new(-1, 1) {
+            if axis.approx_eq(Vec3::zeros()) {
+                continue;
+            }
+
+            for deg in (0..=360).step_by(30) {
+                let a = Angle::from_degrees(deg as f32);
+                let q = Quaternion::from_axis_angle(axis, a);
+                let expected = rotate(axis, a);
+
+                assert_approx_eq!(
+                    rotation_from_quat(q),
+                    expected,
+                    "Failure with axis = {:?}, angle = {}. Expected {:?}, got {:?}.",
+                    axis,
+                    deg,
+                    expected,
+                    rotation_from_quat(q)
+                );
+                assert_approx_eq!(rotation_from_quat(q * Quaternion::real(3.0)), expected);
+                assert_eq!(Transform::new().rotate_quat(q).finish(), rotation_from_quat(q));
+            }
+        }
+
+        let q = Quaternion::from_axis_angle(Vec3::y(), Angle::zero());
+        assert_approx_eq!(rotation_from_quat(q), Mat4::identity());
+        let q = Quaternion::from_axis_angle(Vec3::y(), Angle::full_circle());
+        assert_approx_eq!(rotation_from_quat(q), Mat4::identity());
+    }
+
+    #[test]
+    fn test_orthonormalize() {
+        let noise = |v: Vec3| vec3!(v.y * 0.01, -v.z * 0.02, v.x * 0.015);
+        let mut deg = 0.0;
+
+        for v in GenVec3::new(-2, 2) {
+            let mut m = rotate(v + vec3!(0.5, 0.0, 0.0), Angle::from_degrees(deg)) * translate(v);
+            deg += 10.0;
+            for i in 0..3 {
+                m[i] += noise(v + Vec3::ones() * i as f32).extend(0.0);
+            }
+
+            for mirrored in [false, true].iter() {
+                let m = if *mirrored { mirror_y() * m } else { m };
+                let o = orthonormalize(m);
+                let block = o.truncate();
+
+                assert_within_threshold!(block.transpose() * block, Mat3::identity(),
+                    Mat3::ones() * 1e-5, "M^T M = {:?}", block.transpose() * block);
+                assert_eq!(o[3], m[3]);
+                assert_eq!(o.get_row(3), Vec4::w());
+                assert_eq!(
+                    Transform::from(m).is_mirroring(),
+                    Transform::from(o).is_mirroring()
+                );
+                assert_eq!(Transform::from(m).orthonormalize().finish(), o);
+            }
+        }
+
+        let r = rotate(vec3!(1.0, 2.0, 3.0), Angle::from_degrees(40.0));
+        assert_approx_eq!(orthonormalize(r), r);
+    }
+
+    #[test]
+    fn test_quat_from_rotation() {
+        for axis in GenVec3::new(-1, 1) {
+            if axis.approx_eq(Vec3::zeros()) {
+                continue;
+            }
+
+            for deg in (-360..=360).step_by(30) {
+                let m = rotate(axis, Angle::from_degrees(deg as f32));
+                let q = quat_from_rotation(&m);
+                assert_approx_eq!(q.magnitude(), 1.0);
+                assert_within_threshold!(rotation_from_quat(q), m, Mat4::ones() * 1e-5,
+                    "Failure with axis = {:?}, angle = {}.", axis, deg);
+            }
+        }
+    }
+
+    #[test]
+    fn test_lerp() {
+        let a = Transform::from_trs(vec3!(1.0, 2.0, 3.0), Vec3::z(), Angle::zero(), Vec3::ones());
+        let b = Transform::from_trs(
+            vec3!(3.0, 2.0, 1.0),
+            Vec3::z(),
+            Angle::from_degrees(90.0),
+            vec3!(2.0, 2.0, 4.0),
+        );
+
+        assert_eq!(a.lerp(&b, 0.0), a);
+        assert_eq!(a.lerp(&b, 1.0), b);
+
+        let expected = Transform::from_trs(
+            vec3!(2.0, 2.0, 2.0),
+            Vec3::z(),
+            Angle::from_degrees(45.0),
+            vec3!(1.5, 1.5, 2.5),
+        );
+        assert_approx_eq!(a.lerp(&b, 0.5).finish(), expected.finish());
+
+        let expected = Transform::from_trs(
+            vec3!(5.0, 2.0, -1.0),
+            Vec3::z(),
+            Angle::from_degrees(180.0),
+            vec3!(3.0, 3.0, 7.0),
+        );
+        assert_within_threshold!(a.lerp(&b, 2.0).finish(), expected.finish(), Mat4::ones() * 1e-5);
+
+        let ra = Transform::new().rotate(vec3!(1.0, 1.0, 0.0), Angle::from_degrees(20.0));
+        let rb = Transform::new().rotate(vec3!(1.0, 1.0, 0.0), Angle::from_degrees(100.0));
+        let mid = ra.lerp(&rb, 0.5);
+        assert_approx_eq!(mid.finish(), rotate(vec3!(1.0, 1.0, 0.0), Angle::from_degrees(60.0)));
+        assert_approx_eq!(mid.determinant(), 1.0);
+
+        assert_eq!(a.try_lerp(&b, 0.5), Some(a.lerp(&b, 0.5)));
+        let s = Transform::new().shear_x(1.0, 0.0);
+        assert_eq!(s.try_lerp(&a, 0.25), None);
+        assert_eq!(a.try_lerp(&s, 0.0), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "[Transform::lerp] Transformation cannot be decomposed")]
+    fn test_lerp_undecomposable() {
+        let s = Transform::new().shear_x(1.0, 0.0);
+        Transform::new().lerp(&s, 0.5);
+    }
+
+    #[test]
+    fn test_interpolate_affine() {
+        let a = rotate(Vec3::z(), Angle::zero());
+        let b = rotate(Vec3::z(), Angle::from_degrees(90.0));
+
+        let mid = interpolate_affine(&a, &b, 0.5).unwrap();
+        assert!(mid.is_rotation(1e-5), "mid: {:?}", mid);
+        assert_approx_eq!(mid, rotate(Vec3::z(), Angle::from_degrees(45.0)));
+        assert!(!lerp_matrices(&a, &b, 0.5).is_orthogonal(1e-2));
+
+        let a = trs(vec3!(1.0, -2.0, 0.5), vec3!(1.0, 1.0, 0.0), Angle::from_degrees(30.0),
+            vec3!(1.0, 2.0, 3.0));
+        let b = trs(vec3!(-4.0, 0.0, 2.0), vec3!(0.0, 1.0, 1.0), Angle::from_degrees(-70.0),
+            vec3!(0.5, 0.5, 2.0));
+        assert_eq!(interpolate_affine(&a, &b, 0.0), Some(a));
+        assert_eq!(interpolate_affine(&a, &b, 1.0), Some(b));
+
+        let mid = interpolate_affine(&a, &b, 0.5).unwrap();
+        let d = Transform::from(mid).decompose().unwrap();
+        assert_approx_eq!(d.translation, vec3!(-1.5, -1.0, 1.25));
+        assert_approx_eq!(d.scale, vec3!(0.75, 1.25, 2.5));
+        assert_eq!(Transform::from(a).lerp(&Transform::from(b), 0.5).finish(), mid);
+
+        let sheared = Transform::new().shear_x(1.0, 0.0).finish();
+        assert_eq!(interpolate_affine(&sheared, &a, 0.5), None);
+        assert_eq!(interpolate_affine(&a, &sheared, 0.0), None);
+        assert_eq!(Transform::from(a).try_lerp(&Transform::from(sheared), 0.5), None);
+        let proj = perspective(Angle::from_degrees(60.0), 1.0, 0.1, 10.0);
+        assert_eq!(interpolate_affine(&a, &proj, 0.5), None);
+    }
+
+    #[test]
+    fn test_recorded_transform() {
+        let a = Angle::from_degrees(30.0);
+        let t = Transform::recorded()
+            .translate(vec3!(1.0, 2.0, 3.0))
+            .scale(vec3!(2.0, 1.0, 0.5))
+            .uniform_scale(3.0)
+            .shear_x(1.0, 0.5)
+            .shear_y(-1.0, 0.0)
+            .shear_z(0.0, 2.0)
+            .rotate(vec3!(1.0, 1.0, 0.0), a)
+            .rotate_x(a)
+            .rotate_y(-a)
+            .rotate_z(a * 2.0)
+            .reflect(Vec3::z())
+            .arbitrary(translate(Vec3::x()));
+
+        let expected = Transform::new()
+            .translate(vec3!(1.0, 2.0, 3.0))
+            .scale(vec3!(2.0, 1.0, 0.5))
+            .uniform_scale(3.0)
+            .shear_x(1.0, 0.5)
+            .shear_y(-1.0, 0.0)
+            .shear_z(0.0, 2.0)
+            .rotate(vec3!(1.0, 1.0, 0.0), a)
+            .rotate_x(a)
+            .rotate_y(-a)
+            .rotate_z(a * 2.0)
+            .reflect(Vec3::z())
+            .arbitrary(translate(Vec3::x()));
+
+        assert_eq!(t.transform(), expected);
+        assert_eq!(t.finish(), expected.finish());
+        assert_eq!(t.replay(), expected);
+        assert_eq!(t.steps().len(), 12);
+        assert_eq!(t.steps()[0], TransformStep::Translate(vec3!(1.0, 2.0, 3.0)));
+        assert_eq!(t.steps()[7], TransformStep::RotateX(a));
+        assert_eq!(Transform::from(t.clone()), expected);
+
+        let lines: Vec<_> = t.to_string().lines().map(String::from).collect();
+        assert_eq!(lines.len(), 12);
+        assert_eq!(lines[2], "3. scale uniformly by 3");
+        assert_eq!(lines[3], "4. shear x by (1, 0.5)");
+
+        assert_eq!(Transform::recorded().to_string(), "identity");
+    }
+
+    #[test]
+    fn test_recorded_transform_all_steps() {
+        let a = Angle::from_degrees(25.0);
+        let q = Quaternion::from_axis_angle(vec3!(0.0, 1.0, 1.0).unit(), a);
+        let p = vec3!(1.0, -1.0, 2.0);
+        let angles = [a, -a, a * 2.0];
+        let shifted = translate(vec3!(0.0, 1.0, 0.0));
+        let other = Transform::new().rotate_x(a).translate_z(2.0);
+
+        let t = Transform::recorded()
+            .translate_xyz(1.0, 2.0, 3.0)
+            .translate_x(1.0)
+            .translate_y(-2.0)
+            .translate_z(0.5)
+            .scale_xyz(1.0, 2.0, 3.0)
+            .scale_about(p, vec3!(2.0, 1.0, 0.5))
+            .uniform_scale_about(p, 1.5)
+            .reflect_plane(Vec3::x(), p)
+            .mirror_x()
+            .mirror_y()
+            .mirror_z()
+            .shear(Vec3::y(), Vec3::x(), 0.5)
+            .rotate_quat(q)
+            .rotate_about(p, Vec3::z(), a)
+            .rotate_about_line(p, Vec3::y(), -a)
+            .align(Vec3::x(), vec3!(0.0, 1.0, 1.0))
+            .rotate_euler(angles, EulerOrder::ZYX)
+            .look_at(vec3!(3.0, 4.0, 5.0), Vec3::zeros(), Vec3::z())
+            .pre_translate(p)
+            .pre_scale(vec3!(1.0, 2.0, 1.0))
+            .pre_shear_x(0.5, 0.0)
+            .pre_shear_y(0.0, 0.5)
+            .pre_shear_z(0.5, 0.5)
+            .pre_rotate(Vec3::x(), a)
+            .pre_arbitrary(shifted)
+            .apply_if(true, |t| t.uniform_scale(2.0))
+            .apply_if(false, |t| t.uniform_scale(3.0))
+            .apply_opt(Some(4.0), |t, d| t.translate_x(d))
+            .apply_opt(None, |t, d: f32| t.translate_x(d))
+            .repeat(2)
+            .orthonormalize()
+            .then(other)
+            .before(other);
+
+        let expected = Transform::new()
+            .translate_xyz(1.0, 2.0, 3.0)
+            .translate_x(1.0)
+            .translate_y(-2.0)
+            .translate_z(0.5)
+            .scale_xyz(1.0, 2.0, 3.0)
+            .scale_about(p, vec3!(2.0, 1.0, 0.5))
+            .uniform_scale_about(p, 1.5)
+            .reflect_plane(Vec3::x(), p)
+            .mirror_x()
+            .mirror_y()
+            .mirror_z()
+            .shear(Vec3::y(), Vec3::x(), 0.5)
+            .rotate_quat(q)
+            .rotate_about(p, Vec3::z(), a)
+            .rotate_about_line(p, Vec3::y(), -a)
+            .align(Vec3::x(), vec3!(0.0, 1.0, 1.0))
+            .rotate_euler(angles, EulerOrder::ZYX)
+            .look_at(vec3!(3.0, 4.0, 5.0), Vec3::zeros(), Vec3::z())
+            .pre_translate(p)
+            .pre_scale(vec3!(1.0, 2.0, 1.0))
+            .pre_shear_x(0.5, 0.0)
+            .pre_shear_y(0.0, 0.5)
+            .pre_shear_z(0.5, 0.5)
+            .pre_rotate(Vec3::x(), a)
+            .pre_arbitrary(shifted)
+            .apply_if(true, |t| t.uniform_scale(2.0))
+            .apply_if(false, |t| t.uniform_scale(3.0))
+            .apply_opt(Some(4.0), |t, d| t.translate_x(d))
+            .apply_opt(None, |t, d: f32| t.translate_x(d))
+            .repeat(2)
+            .orthonormalize()
+            .then(other)
+            .before(other);
+
+        assert_eq!(t.transform(), expected);
+        assert_eq!(t.replay(), expected);
+        assert_eq!(t.steps().len(), 31);
+        assert_eq!(t.steps()[0], TransformStep::Translate(vec3!(1.0, 2.0, 3.0)));
+        assert_eq!(t.steps()[4], TransformStep::Scale(vec3!(1.0, 2.0, 3.0)));
+        assert_eq!(t.steps()[25], TransformStep::UniformScale(2.0));
+        assert_eq!(t.steps()[26], TransformStep::TranslateX(4.0));
+        assert_eq!(t.steps()[29], TransformStep::Arbitrary(other.finish()));
+        assert_eq!(t.steps()[30], TransformStep::PreArbitrary(other.finish()));
+
+        let lines: Vec<_> = t.to_string().lines().map(String::from).collect();
+        assert_eq!(lines.len(), 31);
+        assert_eq!(lines[1], "2. translate along x by 1");
+        assert_eq!(lines[8], "9. mirror x");
+        assert_eq!(lines[27], "28. repeat 2 times");
+        assert_eq!(lines[28], "29. orthonormalize");
+    }
+
+    #[test]
+    fn test_cross_matrix() {
+        for v in GenVec3::new(-3, 3) {
+            let k = cross_matrix(v);
+            let k2 = cross_matrix_squared(v);
+            assert_eq!(k.transpose(), -k);
+            assert_eq!(k2, k * k);
+
+            for w in GenVec3::new(-3, 3) {
+                assert_eq!(k * w, v.cross(&w), "[{:?}]x * {:?}", v, w);
+                assert_eq!(k2 * w, v.cross(&v.cross(&w)), "[{:?}]x^2 * {:?}", v, w);
             }
         }
     }
 
     #[test]
-    #[cfg(dont_compile_this_lol)]
     fn test_rotate() {
-        let _test_func = |v: Vec3, a: Angle| {
-            // TODO
-        };
+        let thresh = Mat3::ones() * 1e-5;
+        for axis in GenVec3::new(-3, 3) {
+            if axis == Vec3::zeros() {
+                continue;
+            }
+
+            let k = axis.unit();
+            for deg in (-360..=360).step_by(15) {
+                let a = Angle::from_degrees(deg as f32);
+                let (s, c) = a.sin_cos();
+                let expected =
+                    Mat3::identity() + cross_matrix(k) * s + cross_matrix_squared(k) * (1.0 - c);
+                let m = rotate(axis, a);
+
+                assert_within_threshold!(
+                    m.truncate(),
+                    expected,
+                    thresh,
+                    "Failure with rotate({:?}, {} degrees)",
+                    axis,
+                    deg
+                );
+                assert_eq!(m[3], Vec4::w());
+                assert_eq!(m.get_row(3), Vec4::w());
+                assert_eq!(Transform::new().rotate(axis, a).finish(), m);
+            }
+        }
     }
 }
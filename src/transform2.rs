@@ -0,0 +1,313 @@
+//! Assorted utilities for constructing 2D homogeneous transformation matrices.
+
+use super::*;
+
+/// A builder struct for 2D homogeneous transformation matrices.
+///
+/// A `Transform2` is the two-dimensional counterpart of [`Transform`]. It is used to construct
+/// arbitrary 2D affine transformations starting from the identity transformation, by composing
+/// translation, scaling, shear, and rotation transformations. The final 3x3 homogeneous matrix is
+/// obtained via the `finish()` method.
+///
+/// # Example
+/// ```rust
+/// # #[macro_use] extern crate gramit;
+/// # use gramit::*;
+/// use gramit::transform2::Transform2;
+///
+/// # fn main() {
+/// // `mat` is a matrix that represents the effect of _first_ scaling by 2 along the x axis,
+/// // _then_ rotating 90 degrees counterclockwise.
+/// let mat: Mat3 = Transform2::new()
+///     .scale(vec2!(2.0, 1.0))
+///     .rotate(Angle::from_degrees(90.0))
+///     .finish();
+///
+/// assert_approx_eq!((mat * vec2!(1.0, 0.0).homogeneous()).homogenize(), vec2!(0.0, 2.0));
+/// # }
+/// ```
+///
+/// As with `Transform`, each builder method applies its transformation _after_ those of preceding
+/// builder methods. In matrix terms, this corresponds to multiplying the new transformation on the
+/// _left_, rather than the right.
+///
+/// [`Transform`]: ../transform/struct.Transform.html
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+#[repr(transparent)]
+pub struct Transform2 {
+    mat: Mat3,
+}
+
+impl Transform2 {
+    /// Create a new `Transform2`.
+    ///
+    /// `Transform2`s initially represent the identity transformation (i.e. no transformation at
+    /// all), and are built into more useful transformations via other methods on the struct.
+    #[inline(always)]
+    pub fn new() -> Transform2 {
+        Transform2 {
+            mat: Mat3::identity(),
+        }
+    }
+
+    /// Translate by the given offset.
+    #[inline(always)]
+    pub fn translate(self, offset: Vec2) -> Transform2 {
+        Transform2 {
+            mat: translate2(offset) * self.mat,
+        }
+    }
+
+    /// Scale by the given factors.
+    ///
+    /// The scaling is performed independently per-axis, using the corresponding factor from the
+    /// factor vector.
+    #[inline(always)]
+    pub fn scale(self, factor: Vec2) -> Transform2 {
+        Transform2 {
+            mat: scale2(factor) * self.mat,
+        }
+    }
+
+    /// Rotate counterclockwise about the origin by the given angle.
+    #[inline(always)]
+    pub fn rotate(self, angle: Angle) -> Transform2 {
+        Transform2 {
+            mat: rotate2(angle) * self.mat,
+        }
+    }
+
+    /// Shear by the given amount parallel to the _y_ axis, fixing the _y_ axis.
+    #[inline(always)]
+    pub fn shear_x(self, amount: f32) -> Transform2 {
+        Transform2 {
+            mat: shear2_x(amount) * self.mat,
+        }
+    }
+
+    /// Shear by the given amount parallel to the _x_ axis, fixing the _x_ axis.
+    #[inline(always)]
+    pub fn shear_y(self, amount: f32) -> Transform2 {
+        Transform2 {
+            mat: shear2_y(amount) * self.mat,
+        }
+    }
+
+    /// Apply an arbitrary affine transformation, represented by a homogeneous matrix.
+    #[inline(always)]
+    pub fn arbitrary(self, transform: Mat3) -> Transform2 {
+        Transform2 {
+            mat: transform * self.mat,
+        }
+    }
+
+    /// Compose with another transformation, applying `next` after `self`.
+    #[inline(always)]
+    pub fn then(self, next: Transform2) -> Transform2 {
+        Transform2 {
+            mat: next.mat * self.mat,
+        }
+    }
+
+    /// Acquire the resulting homogeneous transformation matrix.
+    #[inline(always)]
+    pub fn finish(&self) -> Mat3 {
+        self.mat
+    }
+
+    /// Apply this transformation to a point.
+    ///
+    /// The point is extended with a _z_ component of 1 before transformation, and homogenized
+    /// afterward.
+    #[inline(always)]
+    pub fn transform_point(&self, point: Vec2) -> Vec2 {
+        (self.mat * point.homogeneous()).homogenize()
+    }
+
+    /// Apply this transformation to a direction vector.
+    ///
+    /// The vector is extended with a _z_ component of 0 before transformation, so translations do
+    /// not affect it.
+    #[inline(always)]
+    pub fn transform_vector(&self, vector: Vec2) -> Vec2 {
+        (self.mat * vector.extend(0.0)).truncate()
+    }
+}
+
+impl From<Mat3> for Transform2 {
+    #[inline(always)]
+    fn from(mat: Mat3) -> Transform2 {
+        Transform2 { mat }
+    }
+}
+
+impl From<Transform2> for Mat3 {
+    #[inline(always)]
+    fn from(t: Transform2) -> Mat3 {
+        t.mat
+    }
+}
+
+/// Get the 2D homogeneous transformation matrix of a translation by the given offset.
+pub fn translate2(offset: Vec2) -> Mat3 {
+    let mut mat = Mat3::identity();
+    mat.set_col(2, offset.homogeneous());
+    mat
+}
+
+/// Get the 2D homogeneous transformation matrix of a scale by the given factors.
+///
+/// Scaling is computed independently per-axis, using the corresponding factors in the given
+/// vector.
+pub fn scale2(factor: Vec2) -> Mat3 {
+    let mut mat = Mat3::identity();
+    mat[0][0] = factor.x;
+    mat[1][1] = factor.y;
+    mat
+}
+
+/// Get the 2D homogeneous transformation matrix of a counterclockwise rotation about the origin by
+/// the given angle.
+pub fn rotate2(angle: Angle) -> Mat3 {
+    let (s, c) = angle.sin_cos();
+    let mut mat = Mat3::identity();
+    mat[0][0] = c;
+    mat[0][1] = s;
+    mat[1][0] = -s;
+    mat[1][1] = c;
+    mat
+}
+
+/// Get the 2D homogeneous transformation matrix of a shear fixing the _y_ axis by the given amount
+/// parallel to the _y_ axis.
+pub fn shear2_x(amount: f32) -> Mat3 {
+    let mut mat = Mat3::identity();
+    mat[0][1] = amount;
+    mat
+}
+
+/// Get the 2D homogeneous transformation matrix of a shear fixing the _x_ axis by the given amount
+/// parallel to the _x_ axis.
+pub fn shear2_y(amount: f32) -> Mat3 {
+    let mut mat = Mat3::identity();
+    mat[1][0] = amount;
+    mat
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_util::GenVec2;
+
+    #[test]
+    fn test_translate2() {
+        for v in GenVec2::new(-4, 4) {
+            for o in GenVec2::new(-4, 4) {
+                let expected = v + o;
+                let vt = (translate2(o) * v.homogeneous()).homogenize();
+                assert_approx_eq!(
+                    vt,
+                    expected,
+                    "Failure with v = {:?}, offset = {:?}. Expected {:?}, got {:?}.",
+                    v,
+                    o,
+                    expected,
+                    vt
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_scale2() {
+        for v in GenVec2::new(-4, 4) {
+            for s in GenVec2::new(-4, 4) {
+                let expected = vec2!(v.x * s.x, v.y * s.y);
+                let vt = (scale2(s) * v.homogeneous()).homogenize();
+                assert_approx_eq!(
+                    vt,
+                    expected,
+                    "Failure with v = {:?}, factor = {:?}. Expected {:?}, got {:?}.",
+                    v,
+                    s,
+                    expected,
+                    vt
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_rotate2() {
+        let thresh = Vec2::ones() * 1e-4;
+
+        for v in GenVec2::new(-4, 4) {
+            for deg in (-360..=360).step_by(15) {
+                let a = Angle::from_degrees(deg as f32);
+                let (s, c) = a.sin_cos();
+                let expected = vec2!(v.x * c - v.y * s, v.x * s + v.y * c);
+                let vt = (rotate2(a) * v.homogeneous()).homogenize();
+                assert_within_threshold!(
+                    vt,
+                    expected,
+                    thresh,
+                    "Failure with v = {:?}, angle = {}. Expected {:?}, got {:?}.",
+                    v,
+                    deg,
+                    expected,
+                    vt
+                );
+                assert_within_threshold!(vt.length(), v.length(), 1e-4);
+            }
+        }
+    }
+
+    #[test]
+    fn test_shear2() {
+        for v in GenVec2::new(-4, 4) {
+            for amt in -4..=4 {
+                let amt = amt as f32 * 0.5;
+
+                let expected = vec2!(v.x, v.y + v.x * amt);
+                let vt = (shear2_x(amt) * v.homogeneous()).homogenize();
+                assert_approx_eq!(vt, expected);
+
+                let expected = vec2!(v.x + v.y * amt, v.y);
+                let vt = (shear2_y(amt) * v.homogeneous()).homogenize();
+                assert_approx_eq!(vt, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_builder() {
+        let a = Angle::from_degrees(30.0);
+
+        for v in GenVec2::new(-2, 2) {
+            let t = Transform2::new()
+                .scale(vec2!(2.0, 0.5))
+                .shear_x(1.0)
+                .shear_y(-0.5)
+                .rotate(a)
+                .translate(v);
+
+            let expected =
+                translate2(v) * rotate2(a) * shear2_y(-0.5) * shear2_x(1.0) * scale2(vec2!(2.0, 0.5));
+            assert_approx_eq!(t.finish(), expected);
+            assert_eq!(Transform2::new().arbitrary(t.finish()), t);
+            assert_eq!(Mat3::from(t), t.finish());
+            assert_eq!(Transform2::from(t.finish()), t);
+
+            let s = Transform2::new().rotate(a);
+            assert_eq!(t.then(s), t.rotate(a));
+
+            for p in GenVec2::new(-2, 2) {
+                let tp = t.transform_point(p);
+                assert_approx_eq!(tp, (expected * p.homogeneous()).homogenize());
+
+                let tv = t.transform_vector(p);
+                assert_approx_eq!(tv, (expected * p.extend(0.0)).truncate());
+            }
+        }
+    }
+}
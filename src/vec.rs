@@ -4,6 +4,8 @@
 //! default vector types useful for graphics programming: [`Vec2`], [`Vec3`], and [`Vec4`], vectors
 //! in the 2, 3, and 4 dimensional real spaces respectively. To facilitate interfaces with common
 //! graphics APIs, all three are represented as contiguous tuples of 32-bit floating point numbers.
+//! Each type is `#[repr(C)]`, with its components laid out in order with no padding; this layout
+//! is a stable guarantee, and is exposed through [`as_slice`] and [`as_ptr`].
 //!
 //! The vector types defined here implement a full set of arithmetic operators on both scalars and
 //! other vectors:
@@ -62,11 +64,14 @@
 //! [`vec3`](../macro.vec3.html), and [`vec4`](../macro.vec4.html) for creating new vectors.
 //!
 //! [`Vector`]: trait.Vector.html
+//! [`as_slice`]: struct.Vec3.html#method.as_slice
+//! [`as_ptr`]: struct.Vec3.html#method.as_ptr
 //! [`Vec2`]: struct.Vec2.html
 //! [`Vec3`]: struct.Vec3.html
 //! [`Vec4`]: struct.Vec4.html
 
 use std::convert::*;
+use std::fmt::{self, Display, Formatter};
 use std::ops::*;
 
 #[cfg(feature = "serde")]
@@ -157,15 +162,27 @@ macro_rules! decl_vec {
         impl $name {
             #[doc = "Create a new vector with the given components."]
             #[inline(always)]
-            pub fn new($($dims: f32),+) -> Self {
+            pub const fn new($($dims: f32),+) -> Self {
                 $name {
                     $($dims),+
                 }
             }
 
+            #[doc = "The vector with all components zero."]
+            pub const ZERO: $name = $name::filled(0.0);
+
+            #[doc = "The vector with all components one."]
+            pub const ONE: $name = $name::filled(1.0);
+
+            #[doc = "Create a new vector with all components set to `x`."]
+            #[inline(always)]
+            pub const fn filled(x: f32) -> Self {
+                $name::new($(replace_tt!($dims, x)),+)
+            }
+
             $(#[doc = "Create a unit vector along this axis."]
-            pub fn $dims() -> Self {
-                let mut v = Self::zeros();
+            pub const fn $dims() -> Self {
+                let mut v = Self::ZERO;
                 v.$dims = 1.0;
                 v
             })+
@@ -174,6 +191,96 @@ macro_rules! decl_vec {
             pub fn angle_between(&self, other: &$name) -> Angle {
                 Angle::acos(self.dot(&other) / self.length() / other.length()).unwrap()
             }
+
+            #[doc = "View the components of this vector as a slice."]
+            #[inline(always)]
+            pub fn as_slice(&self) -> &[f32] {
+                self.as_ref()
+            }
+
+            #[doc = "View the components of this vector as a mutable slice."]
+            #[inline(always)]
+            pub fn as_mut_slice(&mut self) -> &mut [f32] {
+                self.as_mut()
+            }
+
+            #[doc = "Get the component at the given index, or `None` if it is out of bounds."]
+            pub fn get(&self, idx: usize) -> Option<f32> {
+                self.as_ref().get(idx).cloned()
+            }
+
+            #[doc = "Get a mutable reference to the component at the given index, or `None` if"]
+            #[doc = "it is out of bounds."]
+            pub fn get_mut(&mut self, idx: usize) -> Option<&mut f32> {
+                self.as_mut().get_mut(idx)
+            }
+
+            #[doc = "Get the unit vector in the direction of this vector, if it has one."]
+            #[doc = ""]
+            #[doc = "Returns `None` if the length of this vector is less than `f32::MIN_POSITIVE`"]
+            #[doc = "(i.e. it is zero or subnormal), or if any component is not finite."]
+            #[doc = ""]
+            #[doc = "Unlike `unit`, the length is computed with the vector scaled by its largest"]
+            #[doc = "component, so vectors whose squared length would overflow or underflow"]
+            #[doc = "are still normalized correctly."]
+            pub fn try_unit(&self) -> Option<$name> {
+                if $(!self.$dims.is_finite())||+ {
+                    return None;
+                }
+
+                let max = 0.0f32 $(.max(self.$dims.abs()))+;
+                if max == 0.0 {
+                    return None;
+                }
+
+                let scaled = self / max;
+                let scaled_len = scaled.length();
+                if max * scaled_len < f32::MIN_POSITIVE {
+                    return None;
+                }
+
+                Some(scaled / scaled_len)
+            }
+
+            #[doc = "Get the unit vector in the direction of this vector, or `fallback` if it"]
+            #[doc = "has none."]
+            #[doc = ""]
+            #[doc = "See `try_unit`."]
+            pub fn unit_or(&self, fallback: $name) -> $name {
+                self.try_unit().unwrap_or(fallback)
+            }
+
+            #[doc = "Get the unit vector in the direction of this vector, or the zero vector if"]
+            #[doc = "it has none."]
+            #[doc = ""]
+            #[doc = "See `try_unit`."]
+            pub fn unit_or_zero(&self) -> $name {
+                self.unit_or($name::ZERO)
+            }
+
+            #[doc = "Compare two vectors for equality, within an absolute tolerance."]
+            #[doc = ""]
+            #[doc = "Every pair of components must be equal within `eps`; see"]
+            #[doc = "[`fp::approx_eq_eps`](../fp/fn.approx_eq_eps.html)."]
+            pub fn approx_eq_eps(&self, other: &$name, eps: f32) -> bool {
+                $(fp::approx_eq_eps(self.$dims, other.$dims, eps))&&+
+            }
+
+            #[doc = "Compare two vectors for equality, within a relative tolerance."]
+            #[doc = ""]
+            #[doc = "Every pair of components must be equal within `max_rel`; see"]
+            #[doc = "[`fp::approx_eq_rel`](../fp/fn.approx_eq_rel.html)."]
+            pub fn approx_eq_rel(&self, other: &$name, max_rel: f32) -> bool {
+                $(fp::approx_eq_rel(self.$dims, other.$dims, max_rel))&&+
+            }
+
+            #[doc = "Get a pointer to the first component of this vector."]
+            #[doc = ""]
+            #[doc = "The components follow contiguously, in order."]
+            #[inline(always)]
+            pub fn as_ptr(&self) -> *const f32 {
+                self as *const $name as *const f32
+            }
         }
 
         impl Vector for $name {
@@ -191,12 +298,12 @@ macro_rules! decl_vec {
 
             #[inline(always)]
             fn ones() -> Self {
-                $name::new($(replace_tt!($dims, 1.0)),+)
+                $name::ONE
             }
 
             #[inline(always)]
             fn zeros() -> Self {
-                $name::new($(replace_tt!($dims, 0.0)),+)
+                $name::ZERO
             }
 
             fn unit(&self) -> Self {
@@ -243,9 +350,9 @@ macro_rules! decl_vec {
             }
         }
 
-        impl Into<Vec<f32>> for $name {
-            fn into(self) -> Vec<f32> {
-                Vec::from(self.as_ref())
+        impl From<$name> for Vec<f32> {
+            fn from(v: $name) -> Vec<f32> {
+                Vec::from(v.as_ref())
             }
         }
 
@@ -265,6 +372,25 @@ macro_rules! decl_vec {
             }
         }
 
+        impl Display for $name {
+            #[doc = "Prints this vector as a tuple of its components, e.g. `(1, 2, 3)`."]
+            #[doc = ""]
+            #[doc = "The precision and width of the formatter, if any, apply to each component,"]
+            #[doc = "so that e.g. `{:6.2}` prints each component right-aligned to six characters"]
+            #[doc = "with two decimal places."]
+            fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+                let width = f.width().unwrap_or(0);
+                write!(f, "(")?;
+                for (i, e) in self.as_ref().iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{:>width$}", fp::format_element(f, *e), width = width)?;
+                }
+                write!(f, ")")
+            }
+        }
+
         impl ApproxEq for $name {
             fn approx_eq(self, rhs: $name) -> bool {
                 $(self.$dims.approx_eq(rhs.$dims))&+
@@ -412,6 +538,12 @@ decl_vec!(Vec3, x, y, z);
 decl_vec!(Vec4, x, y, z, w);
 
 impl Vec2 {
+    /// The unit vector along the _x_ axis.
+    pub const X: Vec2 = Vec2::x();
+
+    /// The unit vector along the _y_ axis.
+    pub const Y: Vec2 = Vec2::y();
+
     /// Extend this `Vec2` to a `Vec3`, with the given _z_ component.
     #[inline(always)]
     pub fn extend(self, z: f32) -> Vec3 {
@@ -421,6 +553,14 @@ impl Vec2 {
             z,
         }
     }
+
+    /// Get a homogeneous (point) representation of this `Vec2`.
+    ///
+    /// This is equivalent to calling `vec2.extend(1.0)`.
+    #[inline(always)]
+    pub fn homogeneous(self) -> Vec3 {
+        self.extend(1.0)
+    }
 }
 
 #[cfg(feature = "vulkano")]
@@ -432,6 +572,15 @@ impl Into<ClearValue> for Vec2 {
 }
 
 impl Vec3 {
+    /// The unit vector along the _x_ axis.
+    pub const X: Vec3 = Vec3::x();
+
+    /// The unit vector along the _y_ axis.
+    pub const Y: Vec3 = Vec3::y();
+
+    /// The unit vector along the _z_ axis.
+    pub const Z: Vec3 = Vec3::z();
+
     /// Compute the cross product `self` &times; `other`.
     pub fn cross(self, other: &Vec3) -> Vec3 {
         Vec3 {
@@ -468,6 +617,20 @@ impl Vec3 {
             y: self.y,
         }
     }
+
+    /// Convert a homogeneous 3-vector to the corresponding point in 2-space.
+    pub fn homogenize(self) -> Vec2 {
+        self.truncate() / self.z
+    }
+
+    /// Compute the outer product `self otherᵀ`.
+    ///
+    /// Column `j` of the result is `self * other[j]`, so in the crate's column-major indexing
+    /// `result[j][i] == self[i] * other[j]`. Multiplying the result by a vector `v` gives
+    /// `self * other.dot(&v)`.
+    pub fn outer(&self, other: &Vec3) -> Mat3 {
+        Mat3::new(*self * other.x, *self * other.y, *self * other.z)
+    }
 }
 
 #[cfg(feature = "vulkano")]
@@ -479,6 +642,18 @@ impl Into<ClearValue> for Vec3 {
 }
 
 impl Vec4 {
+    /// The unit vector along the _x_ axis.
+    pub const X: Vec4 = Vec4::x();
+
+    /// The unit vector along the _y_ axis.
+    pub const Y: Vec4 = Vec4::y();
+
+    /// The unit vector along the _z_ axis.
+    pub const Z: Vec4 = Vec4::z();
+
+    /// The unit vector along the _w_ axis.
+    pub const W: Vec4 = Vec4::w();
+
     /// Truncate the _w_ component of this `Vec4` to produce a `Vec3`.
     #[inline(always)]
     pub fn truncate(self) -> Vec3 {
@@ -493,6 +668,20 @@ impl Vec4 {
     pub fn homogenize(self) -> Vec3 {
         self.truncate() / self.w
     }
+
+    /// Compute the outer product `self otherᵀ`.
+    ///
+    /// Column `j` of the result is `self * other[j]`, so in the crate's column-major indexing
+    /// `result[j][i] == self[i] * other[j]`. Multiplying the result by a vector `v` gives
+    /// `self * other.dot(&v)`.
+    pub fn outer(&self, other: &Vec4) -> Mat4 {
+        Mat4::new(
+            *self * other.x,
+            *self * other.y,
+            *self * other.z,
+            *self * other.w,
+        )
+    }
 }
 
 #[cfg(feature = "vulkano")]
@@ -541,6 +730,161 @@ macro_rules! test_vec {
             assert_eq!($name::DIMS, count_args!($($dims),+));
         }
 
+        #[test]
+        fn checked_access() {
+            let n = count_args!($($dims),+);
+            let mut v = $name::zeros();
+            for i in 0..n {
+                v[i] = i as f32 + 1.0;
+            }
+
+            for i in 0..n {
+                assert_eq!(v.get(i), Some(v[i]));
+            }
+            assert_eq!(v.get(n - 1), Some(n as f32));
+            assert_eq!(v.get(n), None);
+            assert_eq!(v.get(usize::MAX), None);
+            assert!(v.get_mut(n).is_none());
+
+            *v.get_mut(n - 1).unwrap() = -1.0;
+            assert_eq!(v[n - 1], -1.0);
+        }
+
+        #[test]
+        fn assign_ops() {
+            let mut a = $name::zeros();
+            let mut b = $name::ones();
+            for i in 0..count_args!($($dims),+) {
+                a[i] = i as f32 * 1.5 + 0.25;
+                b[i] = 2.0 - i as f32 * 0.75;
+            }
+
+            let cases: [(fn(&mut $name, $name), fn($name, $name) -> $name); 4] = [
+                (|x, y| *x += y, |x, y| x + y),
+                (|x, y| *x -= y, |x, y| x - y),
+                (|x, y| *x *= y, |x, y| x * y),
+                (|x, y| *x /= y, |x, y| x / y),
+            ];
+            for (assign, binary) in cases.iter() {
+                let mut v = a;
+                assign(&mut v, b);
+                assert_eq!(v, binary(a, b));
+            }
+
+            let mut v = a;
+            v += &b;
+            assert_eq!(v, a + b);
+            v -= &b;
+            assert_eq!(v, a + b - b);
+
+            let mut v = a;
+            v *= 2.5;
+            assert_eq!(v, a * 2.5);
+            v /= 2.5;
+            assert_eq!(v, (a * 2.5) / 2.5);
+        }
+
+        #[test]
+        fn approx_eq_eps_rel() {
+            let a = $name::ones();
+            let b = a * 1.5;
+            assert!(a.approx_eq_eps(&b, 0.5));
+            assert!(!a.approx_eq_eps(&b, 0.49));
+            assert!(a.approx_eq_rel(&b, 1.0 / 3.0));
+            assert!(!a.approx_eq_rel(&b, 0.33));
+
+            let mut nan = a;
+            nan[0] = f32::NAN;
+            assert!(!nan.approx_eq_eps(&nan, f32::INFINITY));
+            assert!(!nan.approx_eq_rel(&nan, f32::INFINITY));
+
+            let big = a * 1000.0;
+            let big2 = a * 1000.5;
+            assert!(!big.approx_eq_eps(&big2, 0.1));
+            assert!(big.approx_eq_rel(&big2, 1e-3));
+
+            let small = a * 1e-6;
+            let small2 = a * 2e-6;
+            assert!(small.approx_eq_eps(&small2, 1e-3));
+            assert!(!small.approx_eq_rel(&small2, 0.1));
+
+            let inf = a * f32::INFINITY;
+            assert!(inf.approx_eq_eps(&inf, 0.0));
+            assert!(inf.approx_eq_rel(&inf, 0.0));
+            assert!(!inf.approx_eq_rel(&-inf, 1e-6));
+            assert!(!inf.approx_eq_rel(&a, 1e-6));
+        }
+
+        #[test]
+        fn consts() {
+            static ZERO: $name = $name::ZERO;
+            const ONE: $name = $name::ONE;
+            const FILLED: $name = $name::filled(-3.0);
+            const AXES: [$name; count_args!($($dims),+)] = [$($name::$dims()),+];
+
+            assert_eq!(ZERO, $name::zeros());
+            assert_eq!(ONE, $name::ones());
+            assert_eq!(FILLED, $name::ones() * -3.0);
+            for (i, axis) in AXES.iter().enumerate() {
+                for j in 0..count_args!($($dims),+) {
+                    assert_eq!(axis[j], if i == j { 1.0 } else { 0.0 });
+                }
+            }
+        }
+
+        #[test]
+        fn try_unit() {
+            let n = count_args!($($dims),+);
+            let mut v = $name::zeros();
+            for i in 0..n {
+                v[i] = i as f32 - 1.5;
+            }
+            let expected = v.unit();
+            assert_within_threshold!(v.try_unit().unwrap(), expected, $name::ones() * 1e-6);
+            assert_within_threshold!(v.try_unit().unwrap().length(), 1.0, 1e-6);
+
+            assert_eq!($name::zeros().try_unit(), None);
+            assert_eq!($name::zeros().unit_or($name::x()), $name::x());
+            assert_eq!($name::zeros().unit_or_zero(), $name::zeros());
+            assert_eq!((-$name::zeros()).try_unit(), None);
+
+            // Tiny, subnormal, and huge vectors.
+            let tiny = v * 1e-30;
+            assert!(tiny.unit().as_ref().iter().any(|e| !e.is_finite()));
+            assert_within_threshold!(tiny.try_unit().unwrap(), expected, $name::ones() * 1e-6);
+
+            let subnormal = $name::ones() * (f32::MIN_POSITIVE / 4.0);
+            assert_eq!(subnormal.try_unit(), None);
+            assert_eq!(subnormal.unit_or_zero(), $name::zeros());
+
+            let huge = v * 1e30;
+            assert!(huge.dot(&huge).is_infinite());
+            assert_within_threshold!(huge.try_unit().unwrap(), expected, $name::ones() * 1e-6);
+            let max = $name::ones() * f32::MAX;
+            assert_within_threshold!(max.try_unit().unwrap().length(), 1.0, 1e-6);
+
+            let mut inf = $name::ones();
+            inf[0] = f32::INFINITY;
+            assert_eq!(inf.try_unit(), None);
+            let mut nan = $name::ones();
+            nan[n - 1] = f32::NAN;
+            assert_eq!(nan.unit_or($name::x()), $name::x());
+        }
+
+        #[test]
+        fn slices() {
+            let mut v = $name::zeros();
+            for (i, e) in v.as_mut_slice().iter_mut().enumerate() {
+                *e = i as f32 + 1.0;
+            }
+
+            assert_eq!(v.as_slice().len(), count_args!($($dims),+));
+            for i in 0..count_args!($($dims),+) {
+                assert_eq!(v[i], i as f32 + 1.0);
+                assert_eq!(unsafe { *v.as_ptr().add(i) }, v[i]);
+            }
+        }
+
         #[test]
         fn ones() {
             let v = $name::ones();
@@ -659,6 +1003,12 @@ mod test_vec2 {
         assert_eq!(offset_of!(Vec2, x), 0);
         assert_eq!(offset_of!(Vec2, y), 4);
     }
+
+    #[test]
+    fn display() {
+        assert_eq!(format!("{}", vec2!(1.0, -2.5)), "(1, -2.5)");
+        assert_eq!(format!("{:.2}", vec2!(1.0, -2.5)), "(1.00, -2.50)");
+    }
 }
 
 #[cfg(test)]
@@ -677,6 +1027,47 @@ mod test_vec3 {
         assert_eq!(offset_of!(Vec3, y), 4);
         assert_eq!(offset_of!(Vec3, z), 8);
     }
+
+    #[test]
+    fn display() {
+        let v = vec3!(1.0, -2.5, 100.0);
+        assert_eq!(format!("{}", v), "(1, -2.5, 100)");
+        assert_eq!(format!("{:.1}", v), "(1.0, -2.5, 100.0)");
+        assert_eq!(format!("{:6.1}", v), "(   1.0,   -2.5,  100.0)");
+    }
+
+    #[test]
+    fn axis_consts() {
+        static AXES: [Vec3; 3] = [Vec3::X, Vec3::Y, Vec3::Z];
+        assert_eq!(AXES, [Vec3::x(), Vec3::y(), Vec3::z()]);
+        assert_eq!(Vec2::X, Vec2::x());
+        assert_eq!(Vec2::Y, Vec2::y());
+        assert_eq!(Vec4::W, Vec4::w());
+    }
+
+    #[test]
+    fn outer() {
+        let m = vec3!(1.0, 2.0, 3.0).outer(&vec3!(4.0, 5.0, 6.0));
+        let expected = Mat3::new(
+            vec3!(4.0, 8.0, 12.0),
+            vec3!(5.0, 10.0, 15.0),
+            vec3!(6.0, 12.0, 18.0),
+        );
+        assert_eq!(m, expected);
+        assert_eq!(m[2][0], 6.0);
+        assert_eq!(Vec3::x().outer(&Vec3::y()), Mat3::new(Vec3::zeros(), Vec3::x(), Vec3::zeros()));
+
+        let vals = [-2.0, -0.5, 0.0, 1.0, 3.0];
+        for &a in vals.iter() {
+            for &b in vals.iter() {
+                let u = vec3!(a, b, 1.0);
+                let v = vec3!(b, 2.0, a);
+                let w = vec3!(1.0, a, -b);
+                assert_eq!(u.outer(&v) * w, u * v.dot(&w));
+                assert_eq!(u.outer(&v).transpose(), v.outer(&u));
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -696,4 +1087,35 @@ mod test_vec4 {
         assert_eq!(offset_of!(Vec4, z), 8);
         assert_eq!(offset_of!(Vec4, w), 12);
     }
+
+    #[test]
+    fn display() {
+        assert_eq!(format!("{}", Vec4::w()), "(0, 0, 0, 1)");
+        assert_eq!(format!("{:.3}", vec4!(0.5, 0.25, -0.125, 2.0)),
+            "(0.500, 0.250, -0.125, 2.000)");
+    }
+
+    #[test]
+    fn outer() {
+        let m = vec4!(1.0, 2.0, 3.0, 4.0).outer(&vec4!(0.0, 1.0, -1.0, 2.0));
+        let expected = Mat4::new(
+            Vec4::zeros(),
+            vec4!(1.0, 2.0, 3.0, 4.0),
+            vec4!(-1.0, -2.0, -3.0, -4.0),
+            vec4!(2.0, 4.0, 6.0, 8.0),
+        );
+        assert_eq!(m, expected);
+        assert_eq!(m[3][1], 4.0);
+
+        let vals = [-2.0, -0.5, 0.0, 1.0, 3.0];
+        for &a in vals.iter() {
+            for &b in vals.iter() {
+                let u = vec4!(a, b, 1.0, -a);
+                let v = vec4!(b, 2.0, a, 0.5);
+                let w = vec4!(1.0, a, -b, 2.0);
+                assert_eq!(u.outer(&v) * w, u * v.dot(&w));
+                assert_eq!(u.outer(&v).transpose(), v.outer(&u));
+            }
+        }
+    }
 }